@@ -32,9 +32,9 @@ fn main() {
         }
 
         gate_builder
-    });
+    }).unwrap();
 
-    let (quantum_register, classical_register) = algorithm.run();
+    let (quantum_register, classical_register) = algorithm.run().unwrap();
 
     println!("{}", quantum_register);
     for (hidden, measured) in hidden.iter().zip(classical_register.state().iter()) {
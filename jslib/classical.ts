@@ -0,0 +1,41 @@
+import { Bit, Comparison, GateBuilder } from "@/pkg/qukit";
+import * as WasmLib from "@/pkg/qukit";
+
+export function classicalXor(cbits: Bit[], target: Bit, sameStep: boolean = false): void {
+    const bits = Uint32Array.from(cbits.map(cbit => cbit.index));
+    if (sameStep) {
+        WasmLib.classical_xor_same_step(bits, target);
+    } else {
+        WasmLib.classical_xor(bits, target);
+    }
+}
+
+export function classicalAnd(cbits: Bit[], target: Bit, sameStep: boolean = false): void {
+    const bits = Uint32Array.from(cbits.map(cbit => cbit.index));
+    if (sameStep) {
+        WasmLib.classical_and_same_step(bits, target);
+    } else {
+        WasmLib.classical_and(bits, target);
+    }
+}
+
+export function classicalOr(cbits: Bit[], target: Bit, sameStep: boolean = false): void {
+    const bits = Uint32Array.from(cbits.map(cbit => cbit.index));
+    if (sameStep) {
+        WasmLib.classical_or_same_step(bits, target);
+    } else {
+        WasmLib.classical_or(bits, target);
+    }
+}
+
+export function classicalNot(cbit: Bit, target: Bit, sameStep: boolean = false): void {
+    if (sameStep) {
+        WasmLib.classical_not_same_step(cbit, target);
+    } else {
+        WasmLib.classical_not(cbit, target);
+    }
+}
+
+export function condition(gateBuilder: GateBuilder, cbits: Bit[], comparison: Comparison, value: number, gates: () => void): void {
+    gateBuilder.condition(Uint32Array.from(cbits.map(cbit => cbit.index)), comparison, value, gates);
+}
@@ -1,6 +1,11 @@
-export {QBit, Bit, Algorithm, AlgorithmResult, ClassicalRegister, QuantumRegister, GateBuilder} from "@/pkg/qukit";
+export {QBit, Bit, Algorithm, AlgorithmResult, MpsAlgorithmResult, SnapshotAlgorithmResult, SparseAlgorithmResult, StabilizerAlgorithmResult, ClassicalRegister, QuantumRegister, SchmidtDecomposition, Branch, Branches, MpsRegister, Snapshot, Snapshots, SparseRegister, StabilizerRegister, GateBuilder, Gate, CircuitStats, Comparison, Hamiltonian, TrotterOrder, PauliString, PauliSum} from "@/pkg/qukit";
 export * from "./gates";
 export * from "./measurement";
+export * from "./initialize";
 export * from "./rotation-u";
 export * from "./controlled-rotation-u";
 export * from "./controlled-controlled-rotation-u";
+export * from "./classical";
+export * from "./trotter-evolution";
+export * from "./variational";
+export * from "./pauli-string";
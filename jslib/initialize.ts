@@ -0,0 +1,5 @@
+import { QBit, Complex, GateBuilder } from "@/pkg/qukit";
+
+export function initialize(gateBuilder: GateBuilder, qbits: QBit[], amplitudes: Complex[]): void {
+    gateBuilder.initialize(Uint32Array.from(qbits.map(qbit => qbit.index)), amplitudes);
+}
@@ -1,4 +1,4 @@
-import { QBit, Bit } from "@/pkg/qukit";
+import { QBit, Bit, Complex, GateBuilder } from "@/pkg/qukit";
 import * as WasmLib from "@/pkg/qukit";
 
 export function measurement(qbits: QBit | QBit[], cbits: Bit | Bit[], basis: "x" | "y" | "z" = "z", sameStep: boolean = false): void {
@@ -42,3 +42,32 @@ export function measurement(qbits: QBit | QBit[], cbits: Bit | Bit[], basis: "x"
         }
     }
 }
+
+export function measurementPauli(qbits: QBit[], paulis: string, cbit: Bit, sameStep: boolean = false): void {
+    const wires = Uint32Array.from(qbits.map(qbit => qbit.index));
+    if (sameStep) {
+        WasmLib.measurement_pauli_same_step(wires, paulis, cbit);
+    } else {
+        WasmLib.measurement_pauli(wires, paulis, cbit);
+    }
+}
+
+export function measurementBasis(gateBuilder: GateBuilder, qbits: QBit[], unitary: Complex[], cbits: Bit[], sameStep: boolean = false): void {
+    const wires = Uint32Array.from(qbits.map(qbit => qbit.index));
+    const bits = Uint32Array.from(cbits.map(cbit => cbit.index));
+    if (sameStep) {
+        gateBuilder.measurementBasisSameStep(wires, unitary, bits);
+    } else {
+        gateBuilder.measurementBasis(wires, unitary, bits);
+    }
+}
+
+export function measurementPovm(gateBuilder: GateBuilder, qbits: QBit[], kraus: Complex[][], cbits: Bit[], sameStep: boolean = false): void {
+    const wires = Uint32Array.from(qbits.map(qbit => qbit.index));
+    const bits = Uint32Array.from(cbits.map(cbit => cbit.index));
+    if (sameStep) {
+        gateBuilder.measurementPovmSameStep(wires, kraus.flat(), bits);
+    } else {
+        gateBuilder.measurementPovm(wires, kraus.flat(), bits);
+    }
+}
@@ -0,0 +1,9 @@
+import { QBit, GateBuilder, PauliString } from "@/pkg/qukit";
+
+export function rotationPauliString(gateBuilder: GateBuilder, theta: number, qbits: QBit[], string: PauliString): void {
+    gateBuilder.rotationPauliString(theta, Uint32Array.from(qbits.map(qbit => qbit.index)), string);
+}
+
+export function pauliExponential(gateBuilder: GateBuilder, theta: number, qbits: QBit[], string: PauliString): void {
+    gateBuilder.pauliExponential(theta, Uint32Array.from(qbits.map(qbit => qbit.index)), string);
+}
@@ -0,0 +1,5 @@
+import { QBit, GateBuilder, Hamiltonian, TrotterOrder } from "@/pkg/qukit";
+
+export function trotterEvolution(gateBuilder: GateBuilder, qbits: QBit[], hamiltonian: Hamiltonian, time: number, steps: number, order: TrotterOrder): void {
+    gateBuilder.trotterEvolution(Uint32Array.from(qbits.map(qbit => qbit.index)), hamiltonian, time, steps, order);
+}
@@ -0,0 +1,25 @@
+import { QBit, GateBuilder, Hamiltonian } from "@/pkg/qukit";
+
+export function qaoa(gateBuilder: GateBuilder, qbits: QBit[], cost: Hamiltonian, gammas: number[], betas: number[]): void {
+    gateBuilder.qaoa(Uint32Array.from(qbits.map(qbit => qbit.index)), cost, Float64Array.from(gammas), Float64Array.from(betas));
+}
+
+export function qaoaCost(gateBuilder: GateBuilder, qbits: QBit[], cost: Hamiltonian, gamma: number): void {
+    gateBuilder.qaoaCost(Uint32Array.from(qbits.map(qbit => qbit.index)), cost, gamma);
+}
+
+export function qaoaMixer(gateBuilder: GateBuilder, qbits: QBit[], beta: number): void {
+    gateBuilder.qaoaMixer(Uint32Array.from(qbits.map(qbit => qbit.index)), beta);
+}
+
+export function hardwareEfficient(gateBuilder: GateBuilder, qbits: QBit[], layers: number, parameters: number[]): void {
+    gateBuilder.hardwareEfficient(Uint32Array.from(qbits.map(qbit => qbit.index)), layers, Float64Array.from(parameters));
+}
+
+export function uccExcitation(gateBuilder: GateBuilder, theta: number, occupied: QBit[], unoccupied: QBit[]): void {
+    gateBuilder.uccExcitation(
+        theta,
+        Uint32Array.from(occupied.map(qbit => qbit.index)),
+        Uint32Array.from(unoccupied.map(qbit => qbit.index)),
+    );
+}
@@ -1,16 +1,34 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
 use core::ops::{Deref, DerefMut};
 use core::stringify;
 use core::concat;
 use core::f64::consts::PI;
+use paste::paste;
 use spin::Mutex;
 use tinyvec::TinyVec;
-use crate::api::{ClassicalRegister, QuantumRegister};
-use crate::runtime::ket::Ket;
+use crate::complex::Complex;
+use crate::api::{Branch, Branches, CircuitStats, ClassicalRegister, Comparison, Gate, MpsOptions, MpsRegister, QuantumRegister, Snapshot, Snapshots, SparseRegister, StabilizerRegister, TrotterOrder};
+use crate::api::hamiltonian::Hamiltonian;
+use crate::runtime::pauli::{Pauli, PauliString};
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::classical::{BitwiseOperator, ClassicalOperation, Condition};
+use crate::runtime::ket::{seed, Ket};
+use crate::runtime::mps::Mps;
 use crate::runtime::non_unitary::measurement::{Measurement, MeasurementBasis};
+use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+use crate::runtime::non_unitary::povm::Povm;
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
 use crate::runtime::non_unitary::reset::Reset;
 use crate::runtime::register::Register;
+use crate::runtime::simulator::Simulator;
+use crate::runtime::sparse_ket::SparseKet;
+use crate::runtime::stabilizer::Tableau;
 use crate::runtime::unitary::UnitaryOperator;
+use crate::toolbox::conditional::condition_since;
 use crate::toolbox::controlled::Controlled;
 use crate::toolbox::operator::c::C;
 use crate::toolbox::operator::hadamard::Hadamard;
@@ -34,46 +52,353 @@ use crate::toolbox::rotation::hadamard::RotationHadamard;
 use crate::toolbox::rotation::swap::RotationSwap;
 use crate::toolbox::rotation::Rotation;
 use crate::toolbox::rotation::u::RotationU;
+use crate::toolbox::state_preparation::prepare_state;
+use crate::toolbox::pauli::{pauli_gates, pauli_rotation};
+use crate::toolbox::trotter::trotter_circuit;
+use crate::toolbox::variational::{excitation, hardware_efficient_ansatz, qaoa_circuit, qaoa_cost_layer, qaoa_mixer_layer};
 use crate::toolbox::Tool;
 
 #[derive(Clone)]
 pub struct Algorithm {
-    pub(crate) ket: Ket,
+    pub(crate) size: usize,
+    /// The state after the last completed step, created on first use
+    pub(crate) ket: Option<Ket>,
     pub(crate) reg: Register,
     pub(crate) tools: Vec<TinyVec<[Tool; 1]>>,
+    /// The named snapshot markers with the index of the step they precede
+    pub(crate) snapshots: Vec<(String, usize)>,
+    /// The named qregs given by their name, their first qbit and their size
+    pub(crate) qregs: Vec<(String, usize, usize)>,
     pub(crate) pos: usize,
     pub(crate) sub_pos: usize,
     pub(crate) steps: usize,
 }
 
+/// The largest number of qbits, whose process fidelity is computed, as it runs both algorithms on every basis state.
+const MAX_PROCESS_SIZE: usize = 12;
+
+/// Returns the given state or `|0...0>`, if the algorithm has not been started yet.
+fn initial_ket(ket: Option<Ket>, size: usize) -> Result<Ket> {
+    match ket {
+        Some(ket) => Ok(ket),
+        None => Ket::new(size),
+    }
+}
+
 impl Algorithm {
-    pub fn new<F: Fn(GateBuilder) -> GateBuilder>(f: F) -> Algorithm {
-        let builder = GateBuilder(Mutex::new(0), Mutex::new(0), Mutex::new(Vec::new()));
+    /// Creates the algorithm from the gates added by the builder.
+    /// Fails if there are no qbits or a gate uses a qbit or bit outside of the registers or the same qbit twice.
+    pub fn new<F: Fn(GateBuilder) -> GateBuilder>(f: F) -> Result<Algorithm> {
+        let builder = GateBuilder(
+            Mutex::new(0),
+            Mutex::new(0),
+            Mutex::new(Vec::new()),
+            Mutex::new(Vec::new()),
+            Mutex::new(Vec::new()),
+            Mutex::new(Vec::new()),
+        );
         let mut builder = f(builder);
-        Algorithm {
-            ket: Ket::new(*builder.0.get_mut()).unwrap(),
-            reg: Register::new(*builder.1.get_mut()),
-            tools: builder.2.get_mut().clone(),
+
+        Algorithm::from_parts(
+            *builder.0.get_mut(),
+            *builder.1.get_mut(),
+            builder.2.get_mut().clone(),
+            builder.3.get_mut().clone(),
+        )?.with_registers(builder.4.get_mut().clone(), builder.5.get_mut().clone())
+    }
+
+    pub(crate) fn from_parts(
+        size: usize,
+        bits: usize,
+        tools: Vec<TinyVec<[Tool; 1]>>,
+        snapshots: Vec<(String, usize)>,
+    ) -> Result<Algorithm> {
+        if size < 1 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one qbit, but the algorithm has none")
+            ))
+        }
+        for tool in tools.iter().flatten() {
+            let wires = tool.wires();
+            for (i, wire) in wires.iter().enumerate() {
+                if *wire >= size {
+                    return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                        "Expecting qbits below {} in {} but was {}", size, tool.name(), wire
+                    )))
+                }
+                if wires[..i].contains(wire) {
+                    return Err(QuantumError(ErrorKind::DuplicateWire, format!(
+                        "Expecting distinct qbits in {} but {} occurs twice", tool.name(), wire
+                    )))
+                }
+            }
+            if let Some(bit) = tool.bits().into_iter().find(|bit| *bit >= bits) {
+                return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                    "Expecting bits below {} in {} but was {}", bits, tool.name(), bit
+                )))
+            }
+        }
+
+        Ok(Algorithm {
+            size,
+            ket: None,
+            reg: Register::new(bits),
+            tools,
+            snapshots,
+            qregs: Vec::new(),
             pos: 0,
             sub_pos: 0,
             steps: 1,
+        })
+    }
+
+    /// Names ranges of the qbits and bits, each given by its name, its first index and its size.
+    /// Fails if a name occurs twice or a range exceeds its register.
+    pub(crate) fn with_registers(
+        mut self,
+        qregs: Vec<(String, usize, usize)>,
+        cregs: Vec<(String, usize, usize)>,
+    ) -> Result<Algorithm> {
+        for (registers, size, kind) in [(&qregs, self.size, "qbits"), (&cregs, self.reg.bits().len(), "bits")] {
+            for (i, (name, start, len)) in registers.iter().enumerate() {
+                if registers[..i].iter().any(|(other, _, _)| other == name) {
+                    return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                        "Expecting distinct register names but {} occurs twice", name
+                    )))
+                }
+                if start + len > size {
+                    return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                        "Expecting {} below {} in {} but the register ends at {}", kind, size, name, start + len
+                    )))
+                }
+            }
         }
+        self.qregs = qregs;
+        self.reg = self.reg.with_names(cregs);
+
+        Ok(self)
     }
 
-    pub fn into_stepper(self, steps: usize) -> Algorithm {
-        assert!(steps > 0);
-        Algorithm {
+    /// Creates an algorithm, which starts from the state and the classical register of the snapshot instead of `|0...0>`.
+    /// The builder has to allocate the same number of qbits and at most as many bits as the snapshot contains.
+    pub fn from_snapshot<F: Fn(GateBuilder) -> GateBuilder>(snapshot: &Snapshot, f: F) -> Result<Algorithm> {
+        let mut algorithm = Algorithm::new(f)?;
+        algorithm.with_snapshot(snapshot)?;
+
+        Ok(algorithm)
+    }
+
+    /// Starts the algorithm in the basis state, where qbit i is given by bit i of the state.
+    pub fn with_basis_state(mut self, state: usize) -> Result<Algorithm> {
+        self.ket = Some(Ket::from_basis_state(self.size, state, seed())?);
+
+        Ok(self)
+    }
+
+    /// Starts the algorithm in the state given by a normalized vector of `2^n` amplitudes.
+    pub fn with_amplitudes(mut self, amplitudes: &[Complex]) -> Result<Algorithm> {
+        let ket = Ket::from_amplitudes(amplitudes, seed())?;
+        if ket.size() != self.size {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting amplitudes of {} qbits but got {} amplitudes", self.size, amplitudes.len()
+            )))
+        }
+        self.ket = Some(ket);
+
+        Ok(self)
+    }
+
+    /// Starts the algorithm in a product state, given by `alpha|0> + beta|1>` for each qbit.
+    pub fn with_product_state(mut self, qbits: &[(Complex, Complex)]) -> Result<Algorithm> {
+        if qbits.len() != self.size {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting {} qbit states but got {}", self.size, qbits.len())
+            ))
+        }
+        self.ket = Some(Ket::from_product_state(qbits, seed())?);
+
+        Ok(self)
+    }
+
+    pub(crate) fn with_snapshot(&mut self, snapshot: &Snapshot) -> Result<()> {
+        if self.size != snapshot.0.size() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting {} qbits as in the snapshot, but the algorithm has {}", snapshot.0.size(), self.size
+            )))
+        }
+        if self.reg.bits().len() > snapshot.1.bits().len() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting at most {} bits as in the snapshot, but the algorithm has {}",
+                snapshot.1.bits().len(),
+                self.reg.bits().len()
+            )))
+        }
+        self.ket = Some(snapshot.0.clone());
+        self.reg = snapshot.1.clone().with_names(self.reg.names().to_vec());
+
+        Ok(())
+    }
+
+    /// Splits every step into the given number of partial steps, fails if there are none.
+    pub fn into_stepper(self, steps: usize) -> Result<Algorithm> {
+        if steps < 1 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting at least one partial step, but was {}", steps)
+            ))
+        }
+
+        Ok(Algorithm {
+            size: self.size,
             ket: self.ket,
             reg: self.reg,
             tools: self.tools,
+            snapshots: self.snapshots,
+            qregs: self.qregs,
             pos: self.pos,
             sub_pos: self.sub_pos,
             steps,
+        })
+    }
+
+    /// Packs the remaining gates as soon as possible into shared steps, a gate moves in front of all preceding gates,
+    /// which share neither a qbit nor a bit with it. Gates are never moved across a snapshot marker.
+    pub fn into_layered(self) -> Algorithm {
+        let mut tools = Vec::from(&self.tools[..self.pos]);
+        let mut snapshots = self.snapshots.iter()
+            .filter(|(_, step)| *step < self.pos)
+            .cloned()
+            .collect::<Vec<_>>();
+        // The first step each qbit and bit is free in
+        let mut qbit_steps = vec![self.pos; self.size];
+        let mut bit_steps = vec![self.pos; self.reg.bits().len()];
+        let mut first = self.pos;
+        for step in self.pos..=self.tools.len() {
+            for (name, _) in self.snapshots.iter().filter(|(_, snapshot)| *snapshot == step) {
+                first = tools.len();
+                snapshots.push((name.clone(), first));
+            }
+            for tool in self.tools.get(step).into_iter().flatten() {
+                if let Tool::None = tool {
+                    continue
+                }
+                let wires = tool.wires();
+                let bits = tool.bits();
+                let layer = wires.iter().map(|wire| qbit_steps[*wire])
+                    .chain(bits.iter().map(|bit| bit_steps[*bit]))
+                    .fold(first, usize::max);
+                if layer == tools.len() {
+                    tools.push(TinyVec::default());
+                }
+                tools[layer].push(tool.clone());
+                for wire in wires {
+                    qbit_steps[wire] = layer + 1;
+                }
+                for bit in bits {
+                    bit_steps[bit] = layer + 1;
+                }
+            }
+        }
+
+        Algorithm {
+            size: self.size,
+            ket: self.ket,
+            reg: self.reg,
+            tools,
+            snapshots,
+            qregs: self.qregs,
+            pos: self.pos,
+            sub_pos: 0,
+            steps: self.steps,
+        }
+    }
+
+    /// Returns all gates, measurements and resets in the order of execution, including already executed steps.
+    pub fn gates(&self) -> Vec<Gate> {
+        self.tools.iter()
+            .enumerate()
+            .flat_map(|(step, tools)| tools.iter().filter_map(move |tool| Gate::from_tool(step, tool)))
+            .collect()
+    }
+
+    /// Returns the depth, the gate counts and other summary metrics of all steps.
+    pub fn stats(&self) -> CircuitStats {
+        CircuitStats::new(self.size, self.reg.bits().len(), &self.gates())
+    }
+
+    /// Returns the probabilities of the outcomes of the first measurement or reset in the next step, given the state
+    /// after the preceding tools of that step, or none if the step does not contain one.
+    pub fn outcome_probabilities(&self) -> Result<Option<Vec<f64>>> {
+        let mut ket = initial_ket(self.ket.clone(), self.size)?;
+        let mut reg = self.reg.clone();
+        for tool in self.tools.get(self.pos).into_iter().flatten() {
+            if let Some(probabilities) = tool.outcome_probabilities(&ket) {
+                return Ok(Some(probabilities))
+            }
+            ket = tool.apply(ket, &mut reg);
+        }
+
+        Ok(None)
+    }
+
+    /// Completes the current step, the i-th measurement or reset of the step collapses onto `outcomes[i]`,
+    /// while those without a given outcome stay random.
+    /// Fails if the algorithm is finished or an outcome is out of range or impossible.
+    pub fn step_with_outcomes(&mut self, outcomes: &[usize]) -> Result<(QuantumRegister, ClassicalRegister)> {
+        let col = self.tools.get(self.pos)
+            .ok_or_else(|| QuantumError(
+                ErrorKind::Unsupported,
+                String::from("Expecting a step left, but the algorithm is finished")
+            ))?;
+        let mut ket = initial_ket(self.ket.clone(), self.size)?;
+        let mut reg = self.reg.clone();
+        let mut outcomes = outcomes.iter().peekable();
+        for tool in col {
+            ket = match tool.outcome_probabilities(&ket) {
+                Some(probabilities) if outcomes.peek().is_some() => {
+                    let outcome = *outcomes.next().unwrap();
+                    match probabilities.get(outcome) {
+                        Some(probability) if *probability > 1e-12 => tool.apply_outcome(ket, &mut reg, outcome),
+                        Some(_) => return Err(QuantumError(
+                            ErrorKind::InvalidArgument,
+                            format!("The outcome {} of {} is impossible", outcome, tool.name())
+                        )),
+                        None => return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                            "Expecting an outcome below {} for {} but got {}", probabilities.len(), tool.name(), outcome
+                        ))),
+                    }
+                },
+                _ => tool.apply(ket, &mut reg),
+            };
+        }
+        if outcomes.next().is_some() {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at most as many outcomes as measurements in the step")
+            ))
         }
+
+        self.ket = Some(ket.clone());
+        self.reg = reg.clone();
+        self.pos = self.pos + 1;
+        self.sub_pos = 0;
+
+        Ok((QuantumRegister(ket), ClassicalRegister(reg)))
     }
 
-    pub fn step(&mut self) -> Option<(QuantumRegister, ClassicalRegister)> {
-        let mut ket = self.ket.clone();
+    /// Runs the algorithm and returns the energy `<psi|H|psi>` of the final state, which is read from the state vector
+    /// instead of being estimated from measurements.
+    pub fn energy(self, hamiltonian: &Hamiltonian) -> Result<f64> {
+        let (quantum_register, _) = self.run()?;
+
+        hamiltonian.checked_expectation(&quantum_register)
+    }
+
+    /// Applies the next partial step, returns none if the algorithm is finished.
+    pub fn step(&mut self) -> Result<Option<(QuantumRegister, ClassicalRegister)>> {
+        let mut ket = initial_ket(self.ket.clone(), self.size)?;
         let mut reg = self.reg.clone();
         self.sub_pos += 1;
         if let Some(col) = self.tools.get(self.pos) {
@@ -150,8 +475,10 @@ impl Algorithm {
                             Controlled::ControlledControlledRotationSwap(o) => o.parameterized(theta).into(),
                             Controlled::ControlledControlledRotationU(o) => o.parameterized(theta).into(),
                         },
-                        Tool::Measurement(m) => Tool::Measurement(*m),
-                        Tool::Reset(r) => Tool::Reset(*r),
+                        // Measurements and resets only collapse the state once their step is completed,
+                        // classical operations and conditions only read and write the bits of completed steps
+                        Tool::Measurement(_) | Tool::PauliMeasurement(_) | Tool::Povm(_) | Tool::Reset(_) => Tool::None,
+                        Tool::Classical(_) | Tool::Conditional(_) => Tool::None,
                         Tool::Custom(c) => Tool::Custom(c.clone()),
                         Tool::None => Tool::None,
                     }
@@ -161,20 +488,20 @@ impl Algorithm {
             }
 
             if self.sub_pos == self.steps {
-                self.ket = ket.clone();
+                self.ket = Some(ket.clone());
                 self.reg = reg.clone();
                 self.pos = self.pos + 1;
                 self.sub_pos = 0;
             }
 
-            Some((QuantumRegister(ket), ClassicalRegister(reg)))
+            Ok(Some((QuantumRegister(ket), ClassicalRegister(reg))))
         } else {
-            None
+            Ok(None)
         }
     }
 
-    pub fn run(self) -> (QuantumRegister, ClassicalRegister) {
-        let mut ket = self.ket;
+    pub fn run(self) -> Result<(QuantumRegister, ClassicalRegister)> {
+        let mut ket = initial_ket(self.ket, self.size)?;
         let mut reg = self.reg;
         for step in self.tools {
             for tool in step {
@@ -182,7 +509,155 @@ impl Algorithm {
             }
         }
 
-        (QuantumRegister(ket), ClassicalRegister(reg))
+        Ok((QuantumRegister(ket), ClassicalRegister(reg)))
+    }
+
+    /// Applies the unitary of the algorithm to the basis state, fails on measurements, resets and classical instructions.
+    fn apply_unitary(&self, state: usize) -> Result<Ket> {
+        let mut ket = Ket::from_basis_state(self.size, state, seed())?;
+        let mut reg = self.reg.clone();
+        for step in self.tools.iter() {
+            for tool in step {
+                if let Tool::Measurement(_) | Tool::PauliMeasurement(_) | Tool::Povm(_) | Tool::Reset(_)
+                    | Tool::Classical(_) | Tool::Conditional(_) = tool {
+                    return Err(QuantumError(ErrorKind::NonUnitaryMatrix, format!("{} is not unitary", tool.name())))
+                }
+                ket = tool.apply(ket, &mut reg);
+            }
+        }
+
+        Ok(ket)
+    }
+
+    /// Returns the process fidelity `|Tr(U† V)|^2 / d^2` between the unitaries of both algorithms,
+    /// which is one if they are equal up to a global phase.
+    pub fn process_fidelity(&self, other: &Algorithm) -> Result<f64> {
+        if self.size != other.size {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting algorithms of the same size but got {} and {} qbits", self.size, other.size
+            )))
+        }
+        if self.size > MAX_PROCESS_SIZE {
+            return Err(QuantumError(ErrorKind::SizeLimitExceeded, format!(
+                "The process fidelity is limited to {} qbits, but the algorithms have {}", MAX_PROCESS_SIZE, self.size
+            )))
+        }
+        let dim = 0x1usize << self.size;
+        let mut trace = Complex::zero();
+        for state in 0..dim {
+            trace = trace + self.apply_unitary(state)?.inner_product(&other.apply_unitary(state)?)?;
+        }
+
+        Ok(trace.amplitude() / (dim as f64 * dim as f64))
+    }
+
+    /// Runs the algorithm once for every combination of measurement outcomes, each branch is forked at
+    /// a measurement or reset and carries its own state and classical register. Branches below `1e-12` are pruned.
+    pub fn run_branching(self) -> Result<Branches> {
+        let ket = initial_ket(self.ket, self.size)?;
+        let mut branches = Vec::from([Branch(1.0, Vec::new(), ket, self.reg)]);
+        for step in self.tools {
+            for tool in step {
+                branches = branches.into_iter().flat_map(|Branch(probability, outcomes, ket, mut reg)| {
+                    match tool.outcome_probabilities(&ket) {
+                        Some(probabilities) => probabilities.into_iter()
+                            .enumerate()
+                            .filter(|(_, p)| probability * p > 1e-12)
+                            .map(|(outcome, p)| {
+                                let mut reg = reg.clone();
+                                let ket = tool.apply_outcome(ket.clone(), &mut reg, outcome);
+                                let mut outcomes = outcomes.clone();
+                                outcomes.push(outcome);
+
+                                Branch(probability * p, outcomes, ket, reg)
+                            })
+                            .collect::<Vec<_>>(),
+                        None => {
+                            let ket = tool.apply(ket, &mut reg);
+                            Vec::from([Branch(probability, outcomes, ket, reg)])
+                        },
+                    }
+                }).collect();
+            }
+        }
+
+        Ok(Branches(branches))
+    }
+
+    /// Runs the algorithm and records a copy of the state and the classical register at every snapshot marker.
+    pub fn run_with_snapshots(self) -> Result<(QuantumRegister, ClassicalRegister, Snapshots)> {
+        let mut ket = initial_ket(self.ket, self.size)?;
+        let mut reg = self.reg;
+        let mut snapshots = BTreeMap::new();
+        let mut markers = self.snapshots.into_iter().peekable();
+        for (pos, step) in self.tools.into_iter().enumerate() {
+            while let Some((name, _)) = markers.next_if(|(_, marker)| *marker == pos) {
+                snapshots.insert(name, Snapshot(ket.clone(), reg.clone()));
+            }
+            for tool in step {
+                ket = tool.apply(ket, &mut reg);
+            }
+        }
+        for (name, _) in markers {
+            snapshots.insert(name, Snapshot(ket.clone(), reg.clone()));
+        }
+
+        Ok((QuantumRegister(ket), ClassicalRegister(reg), Snapshots(snapshots)))
+    }
+
+    /// Runs the algorithm on a matrix product state instead of the full state vector.
+    /// This allows the simulation of far more qbits, as long as their entanglement stays limited.
+    pub fn run_mps(self, options: MpsOptions) -> Result<(MpsRegister, ClassicalRegister)> {
+        let mut mps = match &self.ket {
+            Some(ket) => Mps::from_ket(ket, options.max_bond_dimension, options.truncation_threshold)?,
+            None => Mps::new(self.size, options.max_bond_dimension, options.truncation_threshold, seed())?,
+        };
+        let mut reg = self.reg;
+        for step in self.tools {
+            for tool in step {
+                mps.apply_tool(&tool, &mut reg)?;
+            }
+        }
+
+        Ok((MpsRegister(mps), ClassicalRegister(reg)))
+    }
+
+    /// Runs the algorithm on a sparse state vector, which only stores the nonzero amplitudes.
+    /// This is faster than [run](Algorithm::run) and allows more qbits, as long as only a few basis states are occupied.
+    pub fn run_sparse(self) -> Result<(SparseRegister, ClassicalRegister)> {
+        let mut ket = match &self.ket {
+            Some(ket) => SparseKet::from_ket(ket)?,
+            None => SparseKet::new(self.size, seed())?,
+        };
+        let mut reg = self.reg;
+        for step in self.tools {
+            for tool in step {
+                ket.apply_tool(&tool, &mut reg)?;
+            }
+        }
+
+        Ok((SparseRegister(ket), ClassicalRegister(reg)))
+    }
+
+    /// Runs the algorithm on a stabilizer tableau, which simulates hundreds of qbits efficiently.
+    /// Fails with the name of the first gate, which is not part of the clifford group, or if the algorithm
+    /// starts from another state than `|0...0>`, which is not known to be a stabilizer state.
+    pub fn run_stabilizer(self) -> Result<(StabilizerRegister, ClassicalRegister)> {
+        if self.ket.is_some() {
+            return Err(QuantumError(
+                ErrorKind::Unsupported,
+                String::from("Expecting the stabilizer simulation to start from |0...0> but an initial state was given")
+            ))
+        }
+        let mut tableau = Tableau::new(self.size, seed())?;
+        let mut reg = self.reg;
+        for step in self.tools {
+            for tool in step {
+                tableau.apply_tool(&tool, &mut reg)?;
+            }
+        }
+
+        Ok((StabilizerRegister(tableau), ClassicalRegister(reg)))
     }
 }
 
@@ -232,7 +707,14 @@ impl<'a> Bit<'a> {
     }
 }
 
-pub struct GateBuilder(pub(crate) Mutex<usize>, pub(crate) Mutex<usize>, pub(crate) Mutex<Vec<TinyVec<[Tool; 1]>>>);
+pub struct GateBuilder(
+    pub(crate) Mutex<usize>,
+    pub(crate) Mutex<usize>,
+    pub(crate) Mutex<Vec<TinyVec<[Tool; 1]>>>,
+    pub(crate) Mutex<Vec<(String, usize)>>,
+    pub(crate) Mutex<Vec<(String, usize, usize)>>,
+    pub(crate) Mutex<Vec<(String, usize, usize)>>,
+);
 impl GateBuilder {
     pub fn qbit(&self) -> QBit {
         let mut bit_id = self.0.lock();
@@ -249,8 +731,34 @@ impl GateBuilder {
 
         bit
     }
+
+    /// Allocates a named register of consecutive qbits, which keeps its name in the algorithm.
+    pub fn qreg(&self, name: &str, size: usize) -> Vec<QBit> {
+        let start = *self.0.lock();
+        self.4.lock().push((String::from(name), start, size));
+
+        (0..size).map(|_| self.qbit()).collect()
+    }
+
+    /// Allocates a named register of consecutive bits, which can be read as an integer from the results
+    /// with [creg](ClassicalRegister::creg).
+    pub fn creg(&self, name: &str, size: usize) -> Vec<Bit> {
+        let start = *self.1.lock();
+        self.5.lock().push((String::from(name), start, size));
+
+        (0..size).map(|_| self.bit()).collect()
+    }
+
+    /// Marks a snapshot, which records the state after all previously added steps,
+    /// if the algorithm is executed with [run_with_snapshots](Algorithm::run_with_snapshots).
+    pub fn snapshot(&self, name: &str) {
+        let pos = self.2.lock().len();
+        self.3.lock().push((String::from(name), pos));
+    }
 }
 
+// Every gate gets a `_same_step` variant, which adds it to the step of the previously added gate instead of
+// starting a new one. The gates of a step are still applied in the order they were added.
 macro_rules! impl_operator {
     ($name:ident, $cname:ident, $ccname:ident, $type:ty) => {
         impl_operator!(@operator, $name, $cname, $ccname, $type, 1);
@@ -284,6 +792,28 @@ macro_rules! impl_operator {
                 ).into()
             )
         }
+
+        paste! {
+            #[doc = impl_operator!(@doc, @same_step, @operator, $type)]
+            pub fn [<$name _same_step>](qbit: QBit) {
+                qbit.push(<$type>::new(qbit.idx()).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlled, $type)]
+            pub fn [<$cname _same_step>](c_qbit: QBit, t_qbit: QBit) {
+                c_qbit.push(C::<2, $type, _>::new(c_qbit.idx(), <$type>::new(t_qbit.idx())).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlledcontrolled, $type)]
+            pub fn [<$ccname _same_step>](c_qbit_0: QBit, c_qbit_1: QBit, t_qbit: QBit) {
+                c_qbit_0.push(
+                    C::<3, C<2, $type, _>, _>::new(
+                        c_qbit_0.idx(),
+                        C::<2, $type, _>::new(c_qbit_1.idx(), <$type>::new(t_qbit.idx()))
+                    ).into()
+                )
+            }
+        }
     };
 
     (@operator, $name:ident, $cname:ident, $ccname:ident, $type:ty, 2) => {
@@ -306,6 +836,28 @@ macro_rules! impl_operator {
                 ).into()
             )
         }
+
+        paste! {
+            #[doc = impl_operator!(@doc, @same_step, @operator, $type)]
+            pub fn [<$name _same_step>](qbit_0: QBit, qbit_1: QBit) {
+                qbit_0.push(<$type>::new(qbit_0.idx(), qbit_1.idx()).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlled, $type)]
+            pub fn [<$cname _same_step>](c_qbit: QBit, t_qbit_0: QBit, t_qbit_1: QBit) {
+                c_qbit.push(C::<3, $type, _>::new(c_qbit.idx(), <$type>::new(t_qbit_0.idx(), t_qbit_1.idx())).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlledcontrolled, $type)]
+            pub fn [<$ccname _same_step>](c_qbit_0: QBit, c_qbit_1: QBit, t_qbit_0: QBit, t_qbit_1: QBit) {
+                c_qbit_0.push(
+                    C::<4, C<3, $type, _>, _>::new(
+                        c_qbit_0.0,
+                        C::<3, $type, _>::new(c_qbit_1.idx(), <$type>::new(t_qbit_0.idx(), t_qbit_1.idx()))
+                    ).into()
+                )
+            }
+        }
     };
 
     (@rotation, $name:ident, $cname:ident, $ccname:ident, $type:ty, 1) => {
@@ -328,6 +880,28 @@ macro_rules! impl_operator {
                 ).into()
             )
         }
+
+        paste! {
+            #[doc = impl_operator!(@doc, @same_step, @operator, $type)]
+            pub fn [<$name _same_step>](theta: f64, qbit: QBit) {
+                qbit.push(<$type>::new(theta, qbit.idx()).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlled, $type)]
+            pub fn [<$cname _same_step>](theta: f64, c_qbit: QBit, t_qbit: QBit) {
+                c_qbit.push(C::<2, $type, _>::new(c_qbit.idx(), <$type>::new(theta, t_qbit.idx())).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlledcontrolled, $type)]
+            pub fn [<$ccname _same_step>](theta: f64, c_qbit_0: QBit, c_qbit_1: QBit, t_qbit: QBit) {
+                c_qbit_0.push(
+                    C::<3, C<2, $type, _>, _>::new(
+                        c_qbit_0.0,
+                        C::<2, $type, _>::new(c_qbit_1.idx(), <$type>::new(theta, t_qbit.idx()))
+                    ).into()
+                )
+            }
+        }
     };
 
     (@rotation, $name:ident, $cname:ident, $ccname:ident, $type:ty, 2) => {
@@ -350,6 +924,28 @@ macro_rules! impl_operator {
                 ).into()
             )
         }
+
+        paste! {
+            #[doc = impl_operator!(@doc, @same_step, @operator, $type)]
+            pub fn [<$name _same_step>](theta: f64, qbit_0: QBit, qbit_1: QBit) {
+                qbit_0.push(<$type>::new(theta, qbit_0.idx(), qbit_1.idx()).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlled, $type)]
+            pub fn [<$cname _same_step>](theta: f64, c_qbit: QBit, t_qbit_0: QBit, t_qbit_1: QBit) {
+                c_qbit.push(C::<3, $type, _>::new(c_qbit.idx(), <$type>::new(theta, t_qbit_0.idx(), t_qbit_1.idx())).into())
+            }
+
+            #[doc = impl_operator!(@doc, @same_step, @controlledcontrolled, $type)]
+            pub fn [<$ccname _same_step>](theta: f64, c_qbit_0: QBit, c_qbit_1: QBit, t_qbit_0: QBit, t_qbit_1: QBit) {
+                c_qbit_0.push(
+                    C::<4, C<3, $type, _>, _>::new(
+                        c_qbit_0.0,
+                        C::<3, $type, _>::new(c_qbit_1.idx(), <$type>::new(theta, t_qbit_0.idx(), t_qbit_1.idx()))
+                    ).into()
+                )
+            }
+        }
     };
 
     (@doc, @operator, $type:ty) => {
@@ -381,6 +977,10 @@ macro_rules! impl_operator {
             ")-Gate to the qbit"
         )
     };
+
+    (@doc, @same_step, @$kind:ident, $type:ty) => {
+        concat!(impl_operator!(@doc, @$kind, $type), " in the step of the previously added gate")
+    };
 }
 
 impl_operator!(hadamard, controlled_hadamard, controlled_controlled_hadamard, Hadamard);
@@ -411,6 +1011,13 @@ pub fn rotation_u(theta: f64, lambda: f64, phi: f64, qbit: QBit) {
     )
 }
 
+#[doc = impl_operator!(@doc, @same_step, @operator, RotationU)]
+pub fn rotation_u_same_step(theta: f64, lambda: f64, phi: f64, qbit: QBit) {
+    qbit.push(
+        RotationU::new(theta, lambda, phi, qbit.idx()).into()
+    )
+}
+
 #[doc = impl_operator!(@doc, @operator, RotationU)]
 pub fn controlled_rotation_u(theta: f64, lambda: f64, phi: f64, c_qbit: QBit, qbit: QBit) {
     qbit.push_col(
@@ -421,6 +1028,16 @@ pub fn controlled_rotation_u(theta: f64, lambda: f64, phi: f64, c_qbit: QBit, qb
     )
 }
 
+#[doc = impl_operator!(@doc, @same_step, @controlled, RotationU)]
+pub fn controlled_rotation_u_same_step(theta: f64, lambda: f64, phi: f64, c_qbit: QBit, qbit: QBit) {
+    qbit.push(
+        C::<2, RotationU, _>::new(
+            c_qbit.idx(),
+            RotationU::new(theta, lambda, phi, qbit.idx())
+        ).into()
+    )
+}
+
 #[doc = impl_operator!(@doc, @operator, RotationU)]
 pub fn controlled_controlled_rotation_u(theta: f64, lambda: f64, phi: f64, c_qbit_0: QBit, c_qbit_1: QBit, qbit: QBit) {
     qbit.push_col(
@@ -434,79 +1051,1222 @@ pub fn controlled_controlled_rotation_u(theta: f64, lambda: f64, phi: f64, c_qbi
     )
 }
 
+#[doc = impl_operator!(@doc, @same_step, @controlledcontrolled, RotationU)]
+pub fn controlled_controlled_rotation_u_same_step(theta: f64, lambda: f64, phi: f64, c_qbit_0: QBit, c_qbit_1: QBit, qbit: QBit) {
+    qbit.push(
+        C::<3, C<2, RotationU, _>, _>::new(
+            c_qbit_0.idx(),
+            C::<2, RotationU, _>::new(
+                c_qbit_1.idx(),
+                RotationU::new(theta, lambda, phi, qbit.idx())
+            )
+        ).into()
+    )
+}
+
 pub fn measurement_x(qbit: QBit, bit: Bit) {
     qbit.push_col(Measurement::new(qbit.idx(), Some(MeasurementBasis::X), Some(bit.idx())).into())
 }
 
+pub fn measurement_x_same_step(qbit: QBit, bit: Bit) {
+    qbit.push(Measurement::new(qbit.idx(), Some(MeasurementBasis::X), Some(bit.idx())).into())
+}
+
 pub fn measurement_y(qbit: QBit, bit: Bit) {
     qbit.push_col(Measurement::new(qbit.idx(), Some(MeasurementBasis::Y), Some(bit.idx())).into())
 }
 
+pub fn measurement_y_same_step(qbit: QBit, bit: Bit) {
+    qbit.push(Measurement::new(qbit.idx(), Some(MeasurementBasis::Y), Some(bit.idx())).into())
+}
+
 pub fn measurement_z(qbit: QBit, bit: Bit) {
     qbit.push_col(Measurement::new(qbit.idx(), Some(MeasurementBasis::Z), Some(bit.idx())).into())
 }
 
+pub fn measurement_z_same_step(qbit: QBit, bit: Bit) {
+    qbit.push(Measurement::new(qbit.idx(), Some(MeasurementBasis::Z), Some(bit.idx())).into())
+}
+
 pub fn reset(qbit: QBit) {
     qbit.push_col(Reset::new(qbit.idx(), false).into())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn reset_same_step(qbit: QBit) {
+    qbit.push(Reset::new(qbit.idx(), false).into())
+}
 
-    #[test]
-    fn test_bell() {
-        let mut algorithm = Algorithm::new(|gate_builder| {
-            let a = gate_builder.qbit();
-            let b = gate_builder.qbit();
-            let c_a = gate_builder.bit();
-            let c_b = gate_builder.bit();
+/// Measures the qbit and flips it, if it is not in the state, `true` resets it to `|1>`.
+pub fn reset_to(qbit: QBit, state: bool) {
+    qbit.push_col(Reset::new(qbit.idx(), state).into())
+}
 
-            hadamard(a);
-            controlled_pauli_x(a, b);
+/// Resets the qbit to the state like [reset_to](reset_to), but shares the step with the previously added gate.
+pub fn reset_to_same_step(qbit: QBit, state: bool) {
+    qbit.push(Reset::new(qbit.idx(), state).into())
+}
 
-            measurement_z(a, c_a);
-            measurement_z(b, c_b);
+fn pauli_measurement(qbits: &[QBit], paulis: &str, bit: Bit) -> Result<Tool> {
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
 
-            gate_builder
-        });
+    Ok(PauliMeasurement::new(&wires, paulis, Some(bit.idx()))?.into())
+}
 
-        let reg = algorithm.run().1;
-        assert_eq!(reg.state().get(0).unwrap(), reg.state().get(1).unwrap())
-    }
+/// Measures the product of `paulis[i]` on `qbits[i]` without destroying the eigenspace, e.g. `"ZZZ"` for a parity check.
+/// The paulis are `X`, `Y`, `Z` or `I`, the eigenvalue `-1` is written as `true` to the bit.
+pub fn measurement_pauli(qbits: &[QBit], paulis: &str, bit: Bit) -> Result<()> {
+    bit.push_col(pauli_measurement(qbits, paulis, bit)?);
 
-    fn bv_algorithm(hidden: Vec<bool>) {
-        let mut algorithm = Algorithm::new(|gate_builder| {
-            let qbits = (0..hidden.len()).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
-            let bits = (0..hidden.len()).map(|_| gate_builder.bit()).collect::<Vec<_>>();
-            let target = gate_builder.qbit();
+    Ok(())
+}
 
-            hadamard(target);
-            pauli_z(target);
+/// Measures the product of `paulis[i]` on `qbits[i]` like [measurement_pauli](measurement_pauli),
+/// but shares the step with the previously added gate.
+pub fn measurement_pauli_same_step(qbits: &[QBit], paulis: &str, bit: Bit) -> Result<()> {
+    bit.push(pauli_measurement(qbits, paulis, bit)?);
 
-            for qbit in qbits.iter() {
-                hadamard(*qbit);
-            }
+    Ok(())
+}
 
-            for idx in 0..qbits.len() {
-                if *hidden.get(idx).unwrap() {
-                    controlled_pauli_x(*qbits.get(idx).unwrap(), target);
-                }
-            }
+/// Returns the first qbit, whose builder receives the gates acting on all of the qbits.
+fn first_qbit<'a>(qbits: &[QBit<'a>]) -> Result<QBit<'a>> {
+    qbits.first().copied().ok_or_else(|| QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one qbit")))
+}
 
-            for qbit in qbits.iter() {
-                hadamard(*qbit);
-            }
-            hadamard(target);
+fn basis_measurement(qbits: &[QBit], unitary: &[Complex], bits: &[Bit]) -> Result<Tool> {
+    let wires = qbits.iter().rev().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    let creg_bits = bits.iter().map(|bit| bit.idx()).collect::<Vec<_>>();
 
-            for idx in 0..qbits.len() {
-                measurement_z(*qbits.get(idx).unwrap(), *bits.get(idx).unwrap());
-            }
+    Ok(Povm::from_basis(&wires, &DynamicSizedMatrix::with_qbits(Vec::from(unitary), qbits.len())?, &creg_bits)?.into())
+}
 
-            gate_builder
-        });
+/// Measures the qbits in the orthonormal basis given by the columns of the row major unitary, the outcome `i`
+/// belongs to the i-th column. Bit j of the matrix index belongs to `qbits[j]` and bit j of the outcome is written to `bits[j]`.
+pub fn measurement_basis(qbits: &[QBit], unitary: &[Complex], bits: &[Bit]) -> Result<()> {
+    first_qbit(qbits)?.push_col(basis_measurement(qbits, unitary, bits)?);
+
+    Ok(())
+}
+
+/// Measures the qbits in the basis given by the columns of the unitary like [measurement_basis](measurement_basis),
+/// but shares the step with the previously added gate.
+pub fn measurement_basis_same_step(qbits: &[QBit], unitary: &[Complex], bits: &[Bit]) -> Result<()> {
+    first_qbit(qbits)?.push(basis_measurement(qbits, unitary, bits)?);
+
+    Ok(())
+}
+
+fn povm_measurement(qbits: &[QBit], kraus: &[Vec<Complex>], bits: &[Bit]) -> Result<Tool> {
+    let wires = qbits.iter().rev().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    let creg_bits = bits.iter().map(|bit| bit.idx()).collect::<Vec<_>>();
+    let kraus = kraus.iter()
+        .map(|operator| DynamicSizedMatrix::with_qbits(operator.clone(), qbits.len()))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Povm::new(&wires, kraus, &creg_bits)?.into())
+}
+
+/// Measures the qbits with the row major kraus operators `K_i`, which have to satisfy `sum_i K_i† K_i = 1`.
+/// Bit j of the matrix index belongs to `qbits[j]` and bit j of the outcome `i` is written to `bits[j]`.
+pub fn measurement_povm(qbits: &[QBit], kraus: &[Vec<Complex>], bits: &[Bit]) -> Result<()> {
+    first_qbit(qbits)?.push_col(povm_measurement(qbits, kraus, bits)?);
+
+    Ok(())
+}
+
+/// Measures the qbits with the kraus operators like [measurement_povm](measurement_povm),
+/// but shares the step with the previously added gate.
+pub fn measurement_povm_same_step(qbits: &[QBit], kraus: &[Vec<Complex>], bits: &[Bit]) -> Result<()> {
+    first_qbit(qbits)?.push(povm_measurement(qbits, kraus, bits)?);
+
+    Ok(())
+}
+
+fn classical(operator: BitwiseOperator, bits: &[Bit], target: Bit) -> Result<Tool> {
+    let sources = bits.iter().map(|bit| bit.idx()).collect::<Vec<_>>();
+
+    Ok(ClassicalOperation::new(operator, &sources, target.idx())?.into())
+}
+
+/// Writes the xor of the bits to the target, e.g. the parity of syndrome bits. Fails if there are no bits.
+pub fn classical_xor(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push_col(classical(BitwiseOperator::Xor, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the xor of the bits to the target like [classical_xor](classical_xor),
+/// but shares the step with the previously added gate.
+pub fn classical_xor_same_step(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push(classical(BitwiseOperator::Xor, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the and of the bits to the target. Fails if there are no bits.
+pub fn classical_and(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push_col(classical(BitwiseOperator::And, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the and of the bits to the target like [classical_and](classical_and),
+/// but shares the step with the previously added gate.
+pub fn classical_and_same_step(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push(classical(BitwiseOperator::And, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the or of the bits to the target. Fails if there are no bits.
+pub fn classical_or(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push_col(classical(BitwiseOperator::Or, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the or of the bits to the target like [classical_or](classical_or),
+/// but shares the step with the previously added gate.
+pub fn classical_or_same_step(bits: &[Bit], target: Bit) -> Result<()> {
+    target.push(classical(BitwiseOperator::Or, bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the negation of the bit to the target, which may be the bit itself.
+pub fn classical_not(bit: Bit, target: Bit) {
+    target.push_col(classical(BitwiseOperator::Not, &[bit], target).unwrap())
+}
+
+/// Writes the negation of the bit to the target, but shares the step with the previously added gate.
+pub fn classical_not_same_step(bit: Bit, target: Bit) {
+    target.push(classical(BitwiseOperator::Not, &[bit], target).unwrap())
+}
+
+/// Applies the gates added by the closure only if the integer given by the bits compares to the value,
+/// `bits[i]` is bit i of the integer. Fails if there are no bits or the closure adds anything but gates.
+pub fn condition<F: FnOnce()>(bits: &[Bit], comparison: Comparison, value: usize, gates: F) -> Result<()> {
+    let indices = bits.iter().map(|bit| bit.idx()).collect::<Vec<_>>();
+    let condition = Condition::new(&indices, comparison, value)?;
+    let builder = bits[0].1;
+    let marker = {
+        let tools = builder.2.lock();
+        (tools.len(), tools.last().map_or(0, |col| col.len()))
+    };
+    gates();
+
+    condition_since(&mut builder.2.lock(), marker, &condition)
+}
+
+/// Prepares the normalized amplitudes on the qbits, which have to be in `|0...0>`.
+/// The amplitude index bit i belongs to `qbits[i]`, the circuit is synthesised from rotations and CNOTs.
+pub fn initialize(qbits: &[QBit], amplitudes: &[Complex]) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in prepare_state(&wires, amplitudes)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies `steps` repetitions of the product formula of the order, which approximates `e^{-iHt}` by rotations and
+/// CNOT ladders. Qbit i of the hamiltonian belongs to `qbits[i]`.
+pub fn trotter_evolution(qbits: &[QBit], hamiltonian: &Hamiltonian, time: f64, steps: usize, order: TrotterOrder) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in trotter_circuit(&wires, &hamiltonian.0, time, steps, order)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Maps qbit i of the pauli string to `wires[i]`.
+pub(crate) fn pauli_wires(wires: &[usize], string: &PauliString) -> Result<Vec<(usize, Pauli)>> {
+    string.paulis().iter()
+        .map(|(qbit, pauli)| match wires.get(*qbit) {
+            Some(wire) => Ok((*wire, *pauli)),
+            None => Err(QuantumError(ErrorKind::InvalidWire, format!(
+                "Expecting qbits below {} in the pauli string but was {}", wires.len(), qbit
+            ))),
+        })
+        .collect()
+}
+
+/// Applies the pauli string as `RotationPauliX`, `RotationPauliY` and `RotationPauliZ` gates with the angle,
+/// `theta = pi` applies the string exactly. Qbit i of the string belongs to `qbits[i]`.
+pub fn rotation_pauli_string(theta: f64, qbits: &[QBit], string: &PauliString) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in pauli_gates(&pauli_wires(&wires, string)?, theta) {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies `exp(-i theta/2 P)` for the pauli string by basis changes, a CNOT ladder and a `RotationZ`.
+/// Qbit i of the string belongs to `qbits[i]`.
+pub fn pauli_exponential(theta: f64, qbits: &[QBit], string: &PauliString) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    let mut tools = Vec::new();
+    pauli_rotation(&mut tools, &pauli_wires(&wires, string)?, theta);
+    for tool in tools {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies p rounds of QAOA to `|+...+>`, each made of the cost layer `e^{-i gamma C}` and the mixer layer `e^{-i beta sum X_j}`.
+/// Qbit i of the cost hamiltonian belongs to `qbits[i]`, p is the number of gammas, which has to match the betas.
+pub fn qaoa(qbits: &[QBit], cost: &Hamiltonian, gammas: &[f64], betas: &[f64]) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in qaoa_circuit(&wires, &cost.0, gammas, betas)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies the QAOA cost layer `e^{-i gamma C}`, qbit i of the cost hamiltonian belongs to `qbits[i]`.
+pub fn qaoa_cost(qbits: &[QBit], cost: &Hamiltonian, gamma: f64) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in qaoa_cost_layer(&wires, &cost.0, gamma)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies the QAOA mixer layer `e^{-i beta sum X_j}` to the qbits.
+pub fn qaoa_mixer(qbits: &[QBit], beta: f64) {
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for (qbit, tool) in qbits.iter().zip(qaoa_mixer_layer(&wires, beta)) {
+        qbit.push_col(tool);
+    }
+}
+
+/// Applies the hardware efficient ansatz, which alternates `RotationY` layers with controlled Z gates between neighbouring qbits
+/// and ends with a final rotation layer. Needs `qbits.len() * (layers + 1)` parameters.
+pub fn hardware_efficient(qbits: &[QBit], layers: usize, parameters: &[f64]) -> Result<()> {
+    let qbit = first_qbit(qbits)?;
+    let wires = qbits.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in hardware_efficient_ansatz(&wires, layers, parameters)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+/// Applies the UCC excitation `e^{theta (T - T^dagger)}` with `T = a^dagger_{v_0} ... a^dagger_{v_k} a_{o_k} ... a_{o_0}`
+/// under the Jordan-Wigner mapping, where `|1>` is occupied and the qbit order defines the fermionic order.
+/// A single excitation turns `|o>` into `cos(theta)|o> + sin(theta)|v>`.
+pub fn ucc_excitation(theta: f64, occupied: &[QBit], unoccupied: &[QBit]) -> Result<()> {
+    let qbit = first_qbit(occupied)?;
+    let occupied_wires = occupied.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    let unoccupied_wires = unoccupied.iter().map(|qbit| qbit.idx()).collect::<Vec<_>>();
+    for tool in excitation(&occupied_wires, &unoccupied_wires, theta)? {
+        qbit.push_col(tool);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex::tolerance;
+    use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_bell() {
+        let mut algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+            let c_b = gate_builder.bit();
+
+            hadamard(a);
+            controlled_pauli_x(a, b);
+
+            measurement_z(a, c_a);
+            measurement_z(b, c_b);
+
+            gate_builder
+        }).unwrap();
+
+        let reg = algorithm.run().unwrap().1;
+        assert_eq!(reg.state().get(0).unwrap(), reg.state().get(1).unwrap())
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_f32_ghz() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..3).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            controlled_pauli_x(qbits[0], qbits[1]);
+            controlled_pauli_x(qbits[1], qbits[2]);
+            // Rounding the amplitudes to single precision after every gate must not accumulate
+            for _ in 0..100 {
+                rotation_x(0.3, qbits[1]);
+                rotation_x(-0.3, qbits[1]);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let quantum_register = algorithm.run().unwrap().0;
+        assert_eq!(core::mem::size_of_val(&quantum_register.states()[0]), 2 * core::mem::size_of::<f32>());
+        assert_approx_eq!(f64, quantum_register.state(0).unwrap().re(), FRAC_1_SQRT_2, epsilon = 0.00001);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("000").unwrap(), 0.5, epsilon = 0.00001);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("111").unwrap(), 0.5, epsilon = 0.00001);
+    }
+
+    #[test]
+    fn test_mps_ghz_large() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..100).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..100).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            for idx in 1..qbits.len() {
+                controlled_pauli_x(qbits[idx - 1], qbits[idx]);
+            }
+            for idx in 0..qbits.len() {
+                measurement_z(qbits[idx], bits[idx]);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, classical_register) = algorithm.run_mps(MpsOptions::default()).unwrap();
+        let first = classical_register.state()[0];
+        for measured in classical_register.state().iter().take(100) {
+            assert_eq!(first, *measured);
+        }
+        assert!(quantum_register.truncation_error() < 0.00000003);
+    }
+
+    fn initial_state_algorithm() -> Algorithm {
+        Algorithm::new(|gate_builder| {
+            let qbits = (0..3).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+
+            controlled_pauli_x(qbits[0], qbits[1]);
+
+            gate_builder
+        }).unwrap().with_product_state(&[
+            (Complex::new(0.6, 0.0), Complex::new(0.8, 0.0)),
+            (Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)),
+            (Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_mps_initial_state() {
+        let (quantum_register, _) = initial_state_algorithm().run_mps(MpsOptions::default()).unwrap();
+        assert_approx_eq!(Complex, quantum_register.amplitude(&[false, false, true]).unwrap(), Complex::new(0.6, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, quantum_register.amplitude(&[true, true, true]).unwrap(), Complex::new(0.8, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, quantum_register.amplitude(&[true, false, true]).unwrap(), Complex::zero(), epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_marginals() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c = gate_builder.qbit();
+
+            rotation_y(FRAC_PI_2, a);
+            controlled_pauli_x(a, c);
+            pauli_x(b);
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, _) = algorithm.run().unwrap();
+        let marginal = quantum_register.marginal_probabilities(&[2, 1]).unwrap();
+        for (probability, expected) in marginal.iter().zip([0.0, 0.0, 0.5, 0.5]) {
+            assert_approx_eq!(f64, *probability, expected, epsilon = tolerance(0.000001));
+        }
+        let outcomes = quantum_register.top_outcomes(&[0, 1, 2], 1).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].0, 2);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("x1x").unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, quantum_register.pattern_probability("11*").unwrap(), 0.5, epsilon = tolerance(0.000001));
+        assert!(quantum_register.pattern_probability("11").is_err());
+        assert!(quantum_register.pattern_probability("1a1").is_err());
+        assert!(quantum_register.marginal_probabilities(&[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_state_comparison() {
+        let bell = |phase: bool| Algorithm::new(move |gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            hadamard(a);
+            controlled_pauli_x(a, b);
+            if phase {
+                pauli_z(a);
+                pauli_x(a);
+                pauli_z(a);
+                pauli_x(a);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let (a, _) = bell(false).run().unwrap();
+        let (b, _) = bell(true).run().unwrap();
+        assert_approx_eq!(Complex, a.inner_product(&b).unwrap(), Complex::new(-1.0, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, a.fidelity(&b).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, a.trace_distance(&b).unwrap(), 0.0, epsilon = 0.0001);
+        assert!(a.equals_up_to_global_phase(&b, 0.000001).unwrap());
+
+        let (c, _) = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let _ = gate_builder.qbit();
+
+            hadamard(a);
+
+            gate_builder
+        }).unwrap().run().unwrap();
+        assert_approx_eq!(f64, a.fidelity(&c).unwrap(), 0.25, epsilon = tolerance(0.000001));
+        assert!(!a.equals_up_to_global_phase(&c, 0.000001).unwrap());
+    }
+
+    #[test]
+    fn test_process_fidelity() {
+        let cnot = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            controlled_pauli_x(a, b);
+
+            gate_builder
+        }).unwrap();
+        let cz = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            hadamard(b);
+            controlled_pauli_z(a, b);
+            hadamard(b);
+
+            gate_builder
+        }).unwrap();
+        let identity = Algorithm::new(|gate_builder| {
+            let _ = gate_builder.qbit();
+            let _ = gate_builder.qbit();
+
+            gate_builder
+        }).unwrap();
+
+        assert_approx_eq!(f64, cnot.process_fidelity(&cz).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, cnot.process_fidelity(&identity).unwrap(), 0.25, epsilon = tolerance(0.000001));
+
+        let wide = Algorithm::new(|gate_builder| {
+            let qbits = (0..13).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+
+            gate_builder
+        }).unwrap();
+        assert_eq!(wide.process_fidelity(&wide).unwrap_err().kind(), ErrorKind::SizeLimitExceeded);
+    }
+
+    #[test]
+    fn test_bloch_vectors() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c = gate_builder.qbit();
+
+            hadamard(a);
+            phase(a);
+            pauli_x(b);
+            hadamard(c);
+            controlled_pauli_x(c, b);
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, _) = algorithm.run().unwrap();
+        let vectors = quantum_register.bloch_vectors();
+        for (vector, expected) in vectors.iter().zip([[0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]]) {
+            for (value, expected) in vector.iter().zip(expected) {
+                assert_approx_eq!(f64, *value, expected, epsilon = tolerance(0.000001));
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_states() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let _ = gate_builder.qbit();
+            let _ = gate_builder.qbit();
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, _) = algorithm.clone().with_basis_state(2).unwrap().run().unwrap();
+        assert_eq!(quantum_register.state(2).unwrap().re(), 1.0);
+        let (quantum_register, _) = algorithm.clone().with_product_state(&[
+            (Complex::new(0.0, 0.0), Complex::new(0.0, 1.0)),
+            (Complex::new(0.6, 0.0), Complex::new(0.8, 0.0)),
+        ]).unwrap().run().unwrap();
+        assert_approx_eq!(Complex, quantum_register.state(3).unwrap(), Complex::new(0.0, 0.8), epsilon = tolerance(0.000001));
+        assert!(algorithm.clone().with_basis_state(4).is_err());
+        assert!(algorithm.clone().with_amplitudes(&[Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)]).is_err());
+        assert!(algorithm.with_amplitudes(&[Complex::new(0.5, 0.0); 4]).is_ok());
+    }
+
+    #[test]
+    fn test_initialize() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            initialize(&[b, a], &[
+                Complex::new(0.0, 0.0), Complex::new(0.0, FRAC_1_SQRT_2),
+                Complex::new(-FRAC_1_SQRT_2, 0.0), Complex::new(0.0, 0.0),
+            ]).unwrap();
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, _) = algorithm.run().unwrap();
+        assert_approx_eq!(Complex, quantum_register.state(1).unwrap(), Complex::new(-FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, quantum_register.state(2).unwrap(), Complex::new(0.0, FRAC_1_SQRT_2), epsilon = tolerance(0.000001));
+
+        Algorithm::new(|gate_builder| {
+            gate_builder.qbit();
+            assert_eq!(initialize(&[], &[Complex::new(1.0, 0.0)]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+
+            gate_builder
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_trotter_evolution() {
+        let mut hamiltonian = Hamiltonian::new();
+        hamiltonian.add_term(0.5, &[0], "X").unwrap();
+        hamiltonian.add_term(-1.0, &[0, 1], "ZZ").unwrap();
+        hamiltonian.add_term(0.3, &[0, 1], "XY").unwrap();
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            trotter_evolution(&[b, a], &hamiltonian, 0.8, 40, TrotterOrder::Fourth).unwrap();
+
+            gate_builder
+        }).unwrap();
+        let (quantum_register, _) = algorithm.run().unwrap();
+
+        // The same hamiltonian with qbit 0 and 1 exchanged, as the builder maps qbit 0 to b
+        let mut exchanged = Hamiltonian::new();
+        exchanged.add_term(0.5, &[1], "X").unwrap();
+        exchanged.add_term(-1.0, &[1, 0], "ZZ").unwrap();
+        exchanged.add_term(0.3, &[1, 0], "XY").unwrap();
+        let exact = exchanged.evolve(&QuantumRegister(Ket::new(2).unwrap()), 0.8).unwrap();
+        assert!(quantum_register.equals_up_to_global_phase(&exact, 0.0001).unwrap());
+
+        Algorithm::new(|gate_builder| {
+            gate_builder.qbit();
+            let error = trotter_evolution(&[], &Hamiltonian::new(), 0.8, 1, TrotterOrder::First).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+
+            gate_builder
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_variational_ansatze() {
+        // A single edge is cut with certainty after one round with gamma = pi/2 and beta = -pi/8
+        let cost = Hamiltonian::max_cut(&[(0, 1, 1.0)]).unwrap();
+        let energy = Algorithm::new(|gate_builder| {
+            let qbits = [gate_builder.qbit(), gate_builder.qbit()];
+            qaoa(&qbits, &cost, &[FRAC_PI_2], &[-PI / 8.0]).unwrap();
+
+            gate_builder
+        }).unwrap().energy(&cost).unwrap();
+        assert_approx_eq!(f64, energy, -1.0, epsilon = tolerance(0.000001));
+
+        let fields = Hamiltonian::ising(&[], &[1.0, 0.5]).unwrap();
+        let energy = Algorithm::new(|gate_builder| {
+            let qbits = [gate_builder.qbit(), gate_builder.qbit()];
+            hardware_efficient(&qbits, 1, &[PI, 0.0, 0.0, 0.0]).unwrap();
+
+            gate_builder
+        }).unwrap().energy(&fields).unwrap();
+        assert_approx_eq!(f64, energy, -0.5, epsilon = tolerance(0.000001));
+
+        // The excitation moves the particle to b with probability sin^2(theta)
+        let energy = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            pauli_x(a);
+            ucc_excitation(0.3, &[a], &[b]).unwrap();
+
+            gate_builder
+        }).unwrap().energy(&Hamiltonian::ising(&[], &[1.0]).unwrap()).unwrap();
+        assert_approx_eq!(f64, energy, libm::cos(0.6), epsilon = tolerance(0.000001));
+
+        Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            assert_eq!(qaoa(&[], &cost, &[FRAC_PI_2], &[-PI / 8.0]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(qaoa_cost(&[], &cost, FRAC_PI_2).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(hardware_efficient(&[], 0, &[]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(ucc_excitation(0.3, &[], &[a]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+
+            gate_builder
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_pauli_string_gates() {
+        // exp(-i pi/4 X Z) = (1 - i X Z) / sqrt(2) and X0 Z1 applied exactly afterwards
+        let string = PauliString::new(&[0, 1], "XZ").unwrap();
+        let (quantum_register, _) = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            pauli_exponential(FRAC_PI_2, &[a, b], &string).unwrap();
+            rotation_pauli_string(PI, &[a, b], &string).unwrap();
+            assert!(pauli_exponential(FRAC_PI_2, &[a], &string).is_err());
+            assert_eq!(pauli_exponential(FRAC_PI_2, &[], &string).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(rotation_pauli_string(PI, &[], &string).unwrap_err().kind(), ErrorKind::InvalidArgument);
+
+            gate_builder
+        }).unwrap().run().unwrap();
+
+        assert_approx_eq!(Complex, quantum_register.state(0b01).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, quantum_register.state(0b00).unwrap(), Complex::new(0.0, -FRAC_1_SQRT_2), epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+
+            gate_builder.snapshot("start");
+            pauli_x(a);
+            gate_builder.snapshot("flipped");
+            controlled_pauli_x(a, b);
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, _, snapshots) = algorithm.run_with_snapshots().unwrap();
+        assert_eq!(quantum_register.state(3).unwrap().re(), 1.0);
+        assert_eq!(snapshots.names(), Vec::from([String::from("flipped"), String::from("start")]));
+        assert_eq!(snapshots.get("start").unwrap().quantum_register().state(0).unwrap().re(), 1.0);
+        let flipped = snapshots.get("flipped").unwrap();
+        assert_eq!(flipped.quantum_register().state(1).unwrap().re(), 1.0);
+
+        let algorithm = Algorithm::from_snapshot(&flipped, |gate_builder| {
+            let a = gate_builder.qbit();
+            let _ = gate_builder.qbit();
+
+            pauli_x(a);
+
+            gate_builder
+        }).unwrap();
+        let (quantum_register, _) = algorithm.run().unwrap();
+        assert_eq!(quantum_register.state(0).unwrap().re(), 1.0);
+
+        let algorithm = Algorithm::from_snapshot(&flipped, |gate_builder| {
+            let _ = gate_builder.qbit();
+
+            gate_builder
+        });
+        assert!(algorithm.is_err());
+    }
+
+    #[test]
+    fn test_sparse_oracle_wide() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..34).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..34).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            hadamard(qbits[1]);
+            controlled_controlled_pauli_x(qbits[0], qbits[1], qbits[33]);
+            for idx in 2..33 {
+                controlled_pauli_x(qbits[33], qbits[idx]);
+            }
+            for idx in 0..qbits.len() {
+                measurement_z(qbits[idx], bits[idx]);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, classical_register) = algorithm.run_sparse().unwrap();
+        assert_eq!(quantum_register.count(), 1);
+        let state = classical_register.state();
+        for idx in 2..34 {
+            assert_eq!(state[idx], state[0] && state[1]);
+        }
+    }
+
+    #[test]
+    fn test_sparse_initial_state() {
+        let (quantum_register, _) = initial_state_algorithm().run_sparse().unwrap();
+        assert_eq!(quantum_register.indices(), Vec::from([0b100, 0b111]));
+        assert_approx_eq!(Complex, quantum_register.state(0b100), Complex::new(0.6, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, quantum_register.state(0b111), Complex::new(0.8, 0.0), epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_stabilizer_ghz_large() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..200).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..200).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            for idx in 1..qbits.len() {
+                controlled_pauli_x(qbits[idx - 1], qbits[idx]);
+            }
+            for idx in 0..qbits.len() {
+                measurement_z(qbits[idx], bits[idx]);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let (_, classical_register) = algorithm.run_stabilizer().unwrap();
+        let first = classical_register.state()[0];
+        for measured in classical_register.state().iter().take(200) {
+            assert_eq!(first, *measured);
+        }
+    }
+
+    #[test]
+    fn test_stabilizer_initial_state() {
+        let error = initial_state_algorithm().run_stabilizer().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_pauli_measurement() {
+        let parity_checks = || Algorithm::new(|gate_builder| {
+            let qbits = (0..3).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..3).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            controlled_pauli_x(qbits[0], qbits[1]);
+            controlled_pauli_x(qbits[1], qbits[2]);
+            pauli_x(qbits[2]);
+            measurement_pauli(&qbits[..2], "ZZ", bits[0]).unwrap();
+            measurement_pauli(&qbits[1..], "ZZ", bits[1]).unwrap();
+            measurement_pauli(&qbits, "XXX", bits[2]).unwrap();
+
+            gate_builder
+        }).unwrap();
+
+        let expected = [false, true, false];
+        let (quantum_register, classical_register) = parity_checks().run().unwrap();
+        assert_eq!(&classical_register.state()[..3], &expected);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("011").unwrap(), 0.5, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, quantum_register.pattern_probability("100").unwrap(), 0.5, epsilon = tolerance(0.000001));
+        let (_, classical_register) = parity_checks().run_stabilizer().unwrap();
+        assert_eq!(&classical_register.state()[..3], &expected);
+        let (_, classical_register) = parity_checks().run_sparse().unwrap();
+        assert_eq!(&classical_register.state()[..3], &expected);
+    }
+
+    #[test]
+    fn test_measurement_basis_and_povm() {
+        let (h, one, zero) = (Complex::new(FRAC_1_SQRT_2, 0.0), Complex::new(1.0, 0.0), Complex::zero());
+        let measurements = || Algorithm::new(|gate_builder| {
+            let qbits = (0..3).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..4).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            hadamard(qbits[0]);
+            pauli_x(qbits[2]);
+            measurement_basis(&qbits[..1], &[h, h, h, -1.0 * h], &bits[..1]).unwrap();
+            // Projects onto the basis states of qbits 1 and 2, outcome 2 means qbit 2 is in |1>
+            let projectors = (0..4)
+                .map(|i| (0..16).map(|idx| if idx == 5 * i { one } else { zero }).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            measurement_povm(&qbits[1..], &projectors, &bits[1..3]).unwrap();
+
+            gate_builder
+        }).unwrap();
+
+        let (quantum_register, classical_register) = measurements().run().unwrap();
+        assert_eq!(&classical_register.state()[..3], &[false, false, true]);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("10x").unwrap(), 1.0, epsilon = tolerance(0.000001));
+        let (_, classical_register) = measurements().run_sparse().unwrap();
+        assert_eq!(&classical_register.state()[..3], &[false, false, true]);
+
+        let invalid = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let bit = gate_builder.bit();
+            assert!(measurement_basis(&[a], &[one, one, one, one], &[bit]).is_err());
+            assert!(measurement_povm(&[a], &[Vec::from([one, zero, zero, one]), Vec::from([one, zero, zero, one])], &[bit]).is_err());
+            // Two qbit matrices and lengths, which are no power of four, do not fit one qbit
+            let two_qbit_identity = (0..16).map(|idx| if idx % 5 == 0 { one } else { zero }).collect::<Vec<_>>();
+            assert_eq!(measurement_basis(&[a], &two_qbit_identity, &[bit]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(measurement_basis(&[a], &[one, zero, zero, one, zero, zero, zero, zero], &[bit]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(measurement_povm(&[a], &[Vec::from([one, zero, zero, one, zero, zero])], &[bit]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(measurement_basis(&[], &[one], &[bit]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+            assert_eq!(measurement_povm_same_step(&[], &[Vec::from([one])], &[bit]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+
+            gate_builder
+        }).unwrap();
+        invalid.run().unwrap();
+    }
+
+    #[test]
+    fn test_step_with_outcomes() {
+        let mut algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+            let c_b = gate_builder.bit();
+
+            hadamard(a);
+            controlled_pauli_x(a, b);
+            measurement_z(a, c_a);
+            measurement_z(b, c_b);
+
+            gate_builder
+        }).unwrap();
+
+        assert!(algorithm.outcome_probabilities().unwrap().is_none());
+        algorithm.step().unwrap();
+        algorithm.step().unwrap();
+        let probabilities = algorithm.outcome_probabilities().unwrap().unwrap();
+        assert_approx_eq!(f64, probabilities[0], 0.5, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, probabilities[1], 0.5, epsilon = tolerance(0.000001));
+        assert!(algorithm.step_with_outcomes(&[2]).is_err());
+        assert!(algorithm.step_with_outcomes(&[1, 0]).is_err());
+        algorithm.step_with_outcomes(&[1]).unwrap();
+
+        let probabilities = algorithm.outcome_probabilities().unwrap().unwrap();
+        assert_approx_eq!(f64, probabilities[1], 1.0, epsilon = tolerance(0.000001));
+        assert!(algorithm.step_with_outcomes(&[0]).is_err());
+        let (quantum_register, classical_register) = algorithm.step_with_outcomes(&[1]).unwrap();
+        assert_eq!(&classical_register.state()[..2], &[true, true]);
+        assert_approx_eq!(f64, quantum_register.pattern_probability("11").unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert!(algorithm.step_with_outcomes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_run_branching() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..3).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..3).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+
+            rotation_y(FRAC_PI_2 / 2.0, qbits[0]);
+            hadamard(qbits[1]);
+            pauli_x(qbits[2]);
+            for idx in 0..3 {
+                measurement_z(qbits[idx], bits[idx]);
+            }
+
+            gate_builder
+        }).unwrap();
+
+        let branches = algorithm.run_branching().unwrap();
+        assert_eq!(branches.len(), 4);
+        assert_eq!(branches.distribution().len(), 4);
+        let total: f64 = branches.branches().iter().map(|branch| branch.probability()).sum();
+        assert_approx_eq!(f64, total, 1.0, epsilon = tolerance(0.000001));
+        for branch in branches.branches() {
+            let outcomes = branch.outcomes();
+            let state = branch.classical_register().state()[..3].to_vec();
+            assert_eq!(outcomes.len(), 3);
+            assert_eq!(state, outcomes.iter().map(|outcome| *outcome == 1).collect::<Vec<_>>());
+            let p = if outcomes[0] == 1 { 0.5 - FRAC_1_SQRT_2 / 2.0 } else { 0.5 + FRAC_1_SQRT_2 / 2.0 };
+            assert_approx_eq!(f64, branch.probability(), p / 2.0, epsilon = tolerance(0.000001));
+        }
+    }
+
+    #[test]
+    fn test_reset_to() {
+        let reset_circuit = || Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+
+            hadamard(a);
+            controlled_pauli_x(a, b);
+            reset_to(a, true);
+            measurement_z(a, c_a);
+
+            gate_builder
+        }).unwrap();
+
+        for _ in 0..20 {
+            let (quantum_register, classical_register) = reset_circuit().run().unwrap();
+            assert_eq!(classical_register.state()[0], true);
+            assert_approx_eq!(f64, quantum_register.probability(0).unwrap(), 1.0, epsilon = tolerance(0.000001));
+            let probability = quantum_register.probability(1).unwrap();
+            assert!(probability < 0.000001 || probability > 0.999999);
+        }
+
+        let branches = reset_circuit().run_branching().unwrap();
+        assert_eq!(branches.len(), 2);
+        for branch in branches.branches() {
+            assert_approx_eq!(f64, branch.probability(), 0.5, epsilon = tolerance(0.000001));
+            assert_eq!(branch.outcomes()[1], 1);
+        }
+    }
+
+    #[test]
+    fn test_stabilizer_non_clifford() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+
+            hadamard(a);
+            phase_root(a);
+
+            gate_builder
+        }).unwrap();
+
+        let error = algorithm.run_stabilizer().err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+        assert!(error.message().contains("PhaseRoot"));
+    }
+
+    #[test]
+    fn test_circuit_stats() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c = gate_builder.qbit();
+            let c_c = gate_builder.bit();
+
+            hadamard(a);
+            phase_root(b);
+            controlled_pauli_x(a, b);
+            controlled_controlled_pauli_x(a, b, c);
+            phase_root_dagger(c);
+            rotation_x(0.5, c);
+            measurement_z(c, c_c);
+
+            gate_builder
+        }).unwrap();
+
+        let gates = algorithm.gates();
+        assert_eq!(gates.len(), 7);
+        assert_eq!(gates[3].name(), "ControlledControlledPauliX");
+        assert_eq!(gates[3].step(), 3);
+        assert_eq!(gates[3].controls(), Vec::from([0, 1]));
+        assert_eq!(gates[3].targets(), Vec::from([2]));
+        assert_eq!(gates[5].parameters(), Vec::from([0.5]));
+        assert_eq!(gates[6].bits(), Vec::from([0]));
+        assert!(!gates[6].is_unitary());
+
+        // The hadamard and the first phase root share a layer
+        let stats = algorithm.stats();
+        assert_eq!(stats.depth(), 6);
+        assert_eq!(stats.width(), 3);
+        assert_eq!(stats.gate_count(), 7);
+        assert_eq!(stats.counts().get("PhaseRoot"), Some(&1));
+        assert_eq!(stats.two_qbit_count(), 1);
+        assert_eq!(stats.t_count(), 2);
+        assert_eq!(stats.qbit_usage(), Vec::from([3, 3, 4]));
+    }
+
+    #[test]
+    fn test_layering() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c = gate_builder.qbit();
+            let d = gate_builder.qbit();
+            let c_c = gate_builder.bit();
+
+            hadamard(a);
+            hadamard_same_step(b);
+            controlled_pauli_x(a, c);
+            pauli_x(b);
+            gate_builder.snapshot("entangled");
+            pauli_z(d);
+            measurement_z(c, c_c);
+
+            gate_builder
+        }).unwrap();
+        assert_eq!(algorithm.tools.len(), 5);
+
+        // The pauli x joins the cnot, the pauli z may not move in front of the snapshot
+        let layered = algorithm.into_layered();
+        let steps = layered.tools.iter().map(|step| step.iter().map(|tool| tool.name()).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(steps, Vec::from([
+            Vec::from(["Hadamard", "Hadamard"]),
+            Vec::from(["ControlledPauliX", "PauliX"]),
+            Vec::from(["PauliZ", "Measurement"]),
+        ]));
+        assert_eq!(layered.snapshots, Vec::from([(String::from("entangled"), 2)]));
+        assert_eq!(layered.stats().depth(), 3);
+    }
+
+    #[test]
+    fn test_named_registers() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let data = gate_builder.qreg("data", 3);
+            let ancilla = gate_builder.qreg("ancilla", 2);
+            let syndrome = gate_builder.creg("syndrome", 2);
+
+            pauli_x(data[0]);
+            controlled_pauli_x(data[0], ancilla[0]);
+            controlled_pauli_x(data[0], ancilla[1]);
+            measurement_z(ancilla[0], syndrome[0]);
+            measurement_z(ancilla[1], syndrome[1]);
+
+            gate_builder
+        }).unwrap();
+        assert_eq!(algorithm.qregs, Vec::from([(String::from("data"), 0, 3), (String::from("ancilla"), 3, 2)]));
+
+        let (_, classical_register) = algorithm.run().unwrap();
+        assert_eq!(classical_register.cregs(), Vec::from([String::from("syndrome")]));
+        assert_eq!(classical_register.creg("syndrome").unwrap(), 3);
+        assert_eq!(classical_register.creg("data").err().unwrap().kind(), ErrorKind::InvalidArgument);
+
+        let duplicate = Algorithm::new(|gate_builder| {
+            gate_builder.qreg("data", 1);
+            gate_builder.qreg("data", 1);
+
+            gate_builder
+        });
+        assert_eq!(duplicate.err().unwrap().kind(), ErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn test_classical_instructions() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let data = gate_builder.qreg("data", 3);
+            let syndrome = gate_builder.creg("syndrome", 2);
+            let parity = gate_builder.bit();
+            let result = gate_builder.creg("result", 2);
+
+            pauli_x(data[0]);
+            measurement_z(data[0], syndrome[0]);
+            measurement_z(data[0], syndrome[0]);
+            measurement_z(data[1], syndrome[1]);
+            classical_xor(&syndrome, parity).unwrap();
+            condition(&syndrome, Comparison::Equal, 1, || pauli_x(data[2])).unwrap();
+            condition(&[parity], Comparison::Less, 1, || pauli_x(data[1])).unwrap();
+            measurement_z(data[2], result[0]);
+            classical_not(result[0], result[1]);
+
+            gate_builder
+        }).unwrap();
+
+        let (_, classical_register) = algorithm.clone().run().unwrap();
+        assert_eq!(classical_register.state().len(), 5);
+        assert_eq!(classical_register.creg("syndrome").unwrap(), 1);
+        assert_eq!(classical_register.value(2, 1).unwrap(), 1);
+        assert_eq!(classical_register.creg("result").unwrap(), 1);
+        assert!(classical_register.value(4, 2).is_err());
+
+        let (_, classical_register) = algorithm.clone().run_sparse().unwrap();
+        assert_eq!(classical_register.creg("result").unwrap(), 1);
+        let (_, classical_register) = algorithm.run_stabilizer().unwrap();
+        assert_eq!(classical_register.creg("result").unwrap(), 1);
+
+        Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+
+            let measured = condition(&[c_a], Comparison::Equal, 0, || measurement_z(a, c_a));
+            assert_eq!(measured.err().unwrap().kind(), ErrorKind::Unsupported);
+            assert!(condition(&[], Comparison::Equal, 0, || pauli_x(a)).is_err());
+
+            gate_builder
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_algorithms() {
+        let duplicate = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+
+            controlled_pauli_x(a, a);
+
+            gate_builder
+        });
+        assert_eq!(duplicate.err().unwrap().kind(), ErrorKind::DuplicateWire);
+
+        let empty = Algorithm::new(|gate_builder| gate_builder);
+        assert_eq!(empty.err().unwrap().kind(), ErrorKind::InvalidArgument);
+
+        let wide = Algorithm::new(|gate_builder| {
+            let qbits = (0..70).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+
+            hadamard(qbits[69]);
+
+            gate_builder
+        }).unwrap();
+        assert!(wide.clone().into_stepper(0).is_err());
+        assert_eq!(wide.clone().run().err().unwrap().kind(), ErrorKind::SizeLimitExceeded);
+        assert!(wide.run_mps(MpsOptions::default()).is_ok());
+
+        // Below the size limit, but too large to be allocated
+        let large = Algorithm::new(|gate_builder| {
+            let qbits = (0..50).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+
+            hadamard(qbits[49]);
+
+            gate_builder
+        }).unwrap();
+        assert_eq!(large.run().err().unwrap().kind(), ErrorKind::SizeLimitExceeded);
+
+        let (quantum_register, _) = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+
+            hadamard(a);
+
+            gate_builder
+        }).unwrap().run().unwrap();
+        assert_eq!(quantum_register.probability(1).err().unwrap().kind(), ErrorKind::InvalidWire);
+        assert!(quantum_register.bloch_vector(1).is_err());
+    }
+
+    fn bv_algorithm(hidden: Vec<bool>) {
+        let mut algorithm = Algorithm::new(|gate_builder| {
+            let qbits = (0..hidden.len()).map(|_| gate_builder.qbit()).collect::<Vec<_>>();
+            let bits = (0..hidden.len()).map(|_| gate_builder.bit()).collect::<Vec<_>>();
+            let target = gate_builder.qbit();
+
+            hadamard(target);
+            pauli_z(target);
+
+            for qbit in qbits.iter() {
+                hadamard(*qbit);
+            }
+
+            for idx in 0..qbits.len() {
+                if *hidden.get(idx).unwrap() {
+                    controlled_pauli_x(*qbits.get(idx).unwrap(), target);
+                }
+            }
+
+            for qbit in qbits.iter() {
+                hadamard(*qbit);
+            }
+            hadamard(target);
+
+            for idx in 0..qbits.len() {
+                measurement_z(*qbits.get(idx).unwrap(), *bits.get(idx).unwrap());
+            }
+
+            gate_builder
+        }).unwrap();
 
-        let (_quantum_register, classical_register) = algorithm.run();
+        let (_quantum_register, classical_register) = algorithm.run().unwrap();
 
         for (hidden, measured) in hidden.iter().zip(classical_register.state().iter()) {
             assert_eq!(*hidden, *measured);
@@ -1,17 +1,31 @@
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::stringify;
 use core::concat;
 use alloc::format;
 use tinyvec::TinyVec;
 use paste::paste;
+use spin::Mutex;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
-use crate::api::{ClassicalRegister, QuantumRegister};
-use crate::runtime::ket::Ket;
+use crate::api::{Branches, CircuitStats, ClassicalRegister, Comparison, ComplexArray, MpsOptions, MpsRegister, QuantumRegister, Snapshot, Snapshots, SparseRegister, StabilizerRegister, TrotterOrder};
+use crate::api::derive::pauli_wires;
+use crate::api::hamiltonian::Hamiltonian;
+use crate::runtime::pauli::PauliString;
+use crate::runtime::classical::{BitwiseOperator, ClassicalOperation, Condition};
 use crate::runtime::non_unitary::measurement::Measurement;
 use crate::runtime::non_unitary::measurement::MeasurementBasis;
+use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+use crate::runtime::non_unitary::povm::Povm;
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
 use crate::runtime::non_unitary::reset::Reset;
-use crate::runtime::register::Register;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError};
+use crate::toolbox::conditional::condition_since;
+use crate::toolbox::state_preparation::prepare_state;
+use crate::toolbox::pauli::{pauli_gates, pauli_rotation};
+use crate::toolbox::trotter::trotter_circuit;
+use crate::toolbox::variational::{excitation, hardware_efficient_ansatz, qaoa_circuit, qaoa_cost_layer, qaoa_mixer_layer};
 use crate::toolbox::operator::c::C;
 use crate::toolbox::operator::hadamard::Hadamard;
 use crate::toolbox::operator::pauli_x::PauliX;
@@ -52,6 +66,82 @@ impl AlgorithmResult {
     }
 }
 
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct MpsAlgorithmResult(MpsRegister, ClassicalRegister);
+
+#[wasm_bindgen]
+impl MpsAlgorithmResult {
+    #[wasm_bindgen(js_name = quantumRegister)]
+    pub fn quantum_register(&self) -> MpsRegister {
+        self.0.clone()
+    }
+    #[wasm_bindgen(js_name = classicalRegister)]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        self.1.clone()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct SnapshotAlgorithmResult(QuantumRegister, ClassicalRegister, Snapshots);
+
+#[wasm_bindgen]
+impl SnapshotAlgorithmResult {
+    #[wasm_bindgen(js_name = quantumRegister)]
+    pub fn quantum_register(&self) -> QuantumRegister {
+        self.0.clone()
+    }
+    #[wasm_bindgen(js_name = classicalRegister)]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        self.1.clone()
+    }
+    pub fn snapshots(&self) -> Snapshots {
+        self.2.clone()
+    }
+    pub fn log(&self) {
+        log(&*format!("{}\n{}", &self.0, &self.1));
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct SparseAlgorithmResult(SparseRegister, ClassicalRegister);
+
+#[wasm_bindgen]
+impl SparseAlgorithmResult {
+    #[wasm_bindgen(js_name = quantumRegister)]
+    pub fn quantum_register(&self) -> SparseRegister {
+        self.0.clone()
+    }
+    #[wasm_bindgen(js_name = classicalRegister)]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        self.1.clone()
+    }
+    pub fn log(&self) {
+        log(&*format!("{}\n{}", &self.0, &self.1));
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct StabilizerAlgorithmResult(StabilizerRegister, ClassicalRegister);
+
+#[wasm_bindgen]
+impl StabilizerAlgorithmResult {
+    #[wasm_bindgen(js_name = quantumRegister)]
+    pub fn quantum_register(&self) -> StabilizerRegister {
+        self.0.clone()
+    }
+    #[wasm_bindgen(js_name = classicalRegister)]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        self.1.clone()
+    }
+    pub fn log(&self) {
+        log(&*format!("{}\n{}", &self.0, &self.1));
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct Algorithm(super::derive::Algorithm);
@@ -66,47 +156,192 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "Bit")]
     pub type BitType;
+
+    #[wasm_bindgen(typescript_type = "Gate[]")]
+    pub type GateArray;
 }
 
 #[wasm_bindgen]
 impl Algorithm {
-    pub fn run(self) -> AlgorithmResult {
-        let (quantum_register, classical_register) = self.0.run();
+    pub fn run(self) -> Result<AlgorithmResult, JsValue> {
+        let (quantum_register, classical_register) = self.0.run()?;
+
+        Ok(AlgorithmResult(quantum_register, classical_register))
+    }
 
-        AlgorithmResult(quantum_register, classical_register)
+    /// Runs the algorithm and returns the energy `<psi|H|psi>` of the final state, read from the state vector
+    pub fn energy(self, hamiltonian: &Hamiltonian) -> Result<f64, JsValue> {
+        Ok(self.0.energy(hamiltonian)?)
     }
 
-    pub fn step(&mut self) -> Option<AlgorithmResult> {
-        if let Some((quantum_register, classical_register)) = self.0.step() {
-            Some(AlgorithmResult(quantum_register, classical_register))
+    #[wasm_bindgen(js_name = runWithSnapshots)]
+    pub fn run_with_snapshots(self) -> Result<SnapshotAlgorithmResult, JsValue> {
+        let (quantum_register, classical_register, snapshots) = self.0.run_with_snapshots()?;
+
+        Ok(SnapshotAlgorithmResult(quantum_register, classical_register, snapshots))
+    }
+
+    /// Runs the algorithm once for every combination of measurement outcomes
+    #[wasm_bindgen(js_name = runBranching)]
+    pub fn run_branching(self) -> Result<Branches, JsValue> {
+        Ok(self.0.run_branching()?)
+    }
+
+    #[wasm_bindgen(js_name = runMps)]
+    pub fn run_mps(self, max_bond_dimension: usize, truncation_threshold: f64) -> Result<MpsAlgorithmResult, JsValue> {
+        let (quantum_register, classical_register) = self.0.run_mps(MpsOptions {
+            max_bond_dimension,
+            truncation_threshold,
+        })?;
+
+        Ok(MpsAlgorithmResult(quantum_register, classical_register))
+    }
+
+    #[wasm_bindgen(js_name = runSparse)]
+    pub fn run_sparse(self) -> Result<SparseAlgorithmResult, JsValue> {
+        let (quantum_register, classical_register) = self.0.run_sparse()?;
+
+        Ok(SparseAlgorithmResult(quantum_register, classical_register))
+    }
+
+    #[wasm_bindgen(js_name = runStabilizer)]
+    pub fn run_stabilizer(self) -> Result<StabilizerAlgorithmResult, JsValue> {
+        let (quantum_register, classical_register) = self.0.run_stabilizer()?;
+
+        Ok(StabilizerAlgorithmResult(quantum_register, classical_register))
+    }
+
+    pub fn step(&mut self) -> Result<Option<AlgorithmResult>, JsValue> {
+        if let Some((quantum_register, classical_register)) = self.0.step()? {
+            Ok(Some(AlgorithmResult(quantum_register, classical_register)))
         } else {
-            None
+            Ok(None)
         }
     }
 
+    /// Returns the outcome probabilities of the first measurement or reset in the next step
+    #[wasm_bindgen(js_name = outcomeProbabilities)]
+    pub fn outcome_probabilities(&self) -> Result<Option<js_sys::Float64Array>, JsValue> {
+        Ok(self.0.outcome_probabilities()?.map(|probabilities| js_sys::Float64Array::from(probabilities.as_slice())))
+    }
+
+    /// Completes the current step, the i-th measurement or reset of the step collapses onto `outcomes[i]`
+    #[wasm_bindgen(js_name = stepWithOutcomes)]
+    pub fn step_with_outcomes(&mut self, outcomes: Vec<usize>) -> Result<AlgorithmResult, JsValue> {
+        let (quantum_register, classical_register) = self.0.step_with_outcomes(&outcomes)?;
+
+        Ok(AlgorithmResult(quantum_register, classical_register))
+    }
+
     #[wasm_bindgen(js_name = intoStepper)]
-    pub fn into_stepper(self, steps: usize) -> Algorithm {
-        Algorithm(self.0.into_stepper(steps))
+    pub fn into_stepper(self, steps: usize) -> Result<Algorithm, JsValue> {
+        Ok(Algorithm(self.0.into_stepper(steps)?))
+    }
+
+    /// Packs the remaining gates as soon as possible into shared steps, without moving them across snapshots
+    #[wasm_bindgen(js_name = intoLayered)]
+    pub fn into_layered(self) -> Algorithm {
+        Algorithm(self.0.into_layered())
+    }
+
+    /// Returns the process fidelity between the unitaries of both algorithms
+    #[wasm_bindgen(js_name = processFidelity)]
+    pub fn process_fidelity(&self, other: &Algorithm) -> Result<f64, JsValue> {
+        Ok(self.0.process_fidelity(&other.0)?)
+    }
+
+    /// Returns all gates, measurements and resets in the order of execution
+    pub fn gates(&self) -> GateArray {
+        self.0.gates().into_iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+
+    /// Returns the depth, the gate counts and other summary metrics
+    pub fn stats(&self) -> CircuitStats {
+        self.0.stats()
+    }
+
+    /// Returns the remaining steps as versioned json, a started algorithm begins with its current state
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        to_json(&self.0)
+    }
+
+    /// Restores an algorithm from the json written by `toJson` of an algorithm or a gate builder
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Algorithm, JsValue> {
+        let value = js_sys::JSON::parse(json)?;
+
+        Ok(Algorithm(serde_wasm_bindgen::from_value(value)?))
+    }
+
+    /// Starts the algorithm in the basis state, where qbit i is given by bit i of the state
+    #[wasm_bindgen(js_name = withBasisState)]
+    pub fn with_basis_state(self, state: usize) -> Result<Algorithm, JsValue> {
+        Ok(Algorithm(self.0.with_basis_state(state)?))
+    }
+
+    /// Starts the algorithm in the state given by a normalized vector of amplitudes
+    #[wasm_bindgen(js_name = withAmplitudes)]
+    pub fn with_amplitudes(self, amplitudes: ComplexArray) -> Result<Algorithm, JsValue> {
+        let amplitudes: Vec<Complex> = serde_wasm_bindgen::from_value(amplitudes.into())?;
+
+        Ok(Algorithm(self.0.with_amplitudes(&amplitudes)?))
+    }
+
+    /// Starts the algorithm in a product state, given as `[alpha_0, beta_0, alpha_1, beta_1, ...]`
+    #[wasm_bindgen(js_name = withProductState)]
+    pub fn with_product_state(self, qbits: ComplexArray) -> Result<Algorithm, JsValue> {
+        let qbits: Vec<Complex> = serde_wasm_bindgen::from_value(qbits.into())?;
+        if qbits.len() % 2 != 0 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting an alpha and a beta for every qbit but got {} amplitudes", qbits.len())
+            ).into())
+        }
+        let qbits: Vec<(Complex, Complex)> = qbits.chunks(2)
+            .map(|qbit| (qbit[0], qbit[1]))
+            .collect();
+
+        Ok(Algorithm(self.0.with_product_state(&qbits)?))
     }
 }
 
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, JsValue> {
+    let value = serde_wasm_bindgen::to_value(value)?;
+
+    Ok(js_sys::JSON::stringify(&value)?.into())
+}
+
 #[wasm_bindgen]
 pub struct QBit(usize, *mut GateBuilder);
 
+#[wasm_bindgen]
+impl QBit {
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 impl QBit {
     fn idx(&self) -> usize {
         self.0
     }
 
     fn push_col(&self, tool: Tool) {
-        (unsafe { &mut *self.1 }).2.push(TinyVec::from([tool; 1]));
+        let mut tools = (unsafe { &*self.1 }).2.lock();
+        tools.push(TinyVec::from([tool; 1]));
     }
 
     fn push(&self, tool: Tool) {
-        if let Some(col) = (unsafe { &mut *self.1 }).2.last_mut() {
+        let mut tools = (unsafe { &*self.1 }).2.lock();
+        if let Some(col) = tools.last_mut() {
             col.push(tool);
         } else {
-            self.push_col(tool);
+            tools.push(TinyVec::from([tool; 1]));
         }
     }
 }
@@ -114,26 +349,43 @@ impl QBit {
 #[wasm_bindgen]
 pub struct Bit(usize, *mut GateBuilder);
 
+#[wasm_bindgen]
+impl Bit {
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 impl Bit {
     fn idx(&self) -> usize {
         self.0
     }
 
     fn push_col(&self, tool: Tool) {
-        (unsafe { &mut *self.1 }).2.push(TinyVec::from([tool; 1]));
+        let mut tools = (unsafe { &*self.1 }).2.lock();
+        tools.push(TinyVec::from([tool; 1]));
     }
 
     fn push(&self, tool: Tool) {
-        if let Some(col) = (unsafe { &mut *self.1 }).2.last_mut() {
+        let mut tools = (unsafe { &*self.1 }).2.lock();
+        if let Some(col) = tools.last_mut() {
             col.push(tool);
         } else {
-            self.push_col(tool);
+            tools.push(TinyVec::from([tool; 1]));
         }
     }
 }
 
 #[wasm_bindgen]
-pub struct GateBuilder(usize, usize, Vec<TinyVec<[Tool; 1]>>);
+pub struct GateBuilder(
+    usize,
+    usize,
+    Mutex<Vec<TinyVec<[Tool; 1]>>>,
+    Vec<(String, usize)>,
+    Vec<(String, usize, usize)>,
+    Vec<(String, usize, usize)>,
+);
 
 #[wasm_bindgen]
 impl GateBuilder {
@@ -142,22 +394,191 @@ impl GateBuilder {
         #[cfg(feature="console_error_panic_hook")]
         console_error_panic_hook::set_once();
 
-        GateBuilder(0, 0, Vec::new())
+        GateBuilder(0, 0, Mutex::new(Vec::new()), Vec::new(), Vec::new(), Vec::new())
     }
 
+    /// Creates the algorithm, fails if a gate uses a qbit or bit outside of the registers or the same qbit twice
     #[wasm_bindgen(js_name = intoAlgorithm)]
-    pub fn into_algorithm(self) -> Algorithm {
-        let ket = Ket::new(self.0).unwrap();
-        let reg = Register::new(self.1);
-        let tools = self.2;
-        Algorithm(super::derive::Algorithm {
-            ket,
-            reg,
-            tools,
-            pos: 0,
-            sub_pos: 0,
-            steps: 1,
-        })
+    pub fn into_algorithm(self) -> Result<Algorithm, JsValue> {
+        Ok(Algorithm(
+            super::derive::Algorithm::from_parts(self.0, self.1, self.2.into_inner(), self.3)?.with_registers(self.4, self.5)?
+        ))
+    }
+
+    /// Creates an algorithm, which starts from the state and the classical register of the snapshot
+    #[wasm_bindgen(js_name = intoAlgorithmFromSnapshot)]
+    pub fn into_algorithm_from_snapshot(self, snapshot: &Snapshot) -> Result<Algorithm, JsValue> {
+        let mut algorithm = self.into_algorithm()?;
+        algorithm.0.with_snapshot(snapshot)?;
+
+        Ok(algorithm)
+    }
+
+    /// Returns the gates as the versioned json of the algorithm, fails on the same errors as `intoAlgorithm`
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        let algorithm = super::derive::Algorithm::from_parts(self.0, self.1, self.2.lock().clone(), self.3.clone())?
+            .with_registers(self.4.clone(), self.5.clone())?;
+
+        to_json(&algorithm)
+    }
+
+    /// Applies the gates added by the callback only if the integer given by the bits compares to the value,
+    /// `bits[i]` is bit i of the integer. Fails if there are no bits or the callback adds anything but gates
+    pub fn condition(&self, bits: Vec<usize>, comparison: Comparison, value: usize, gates: &js_sys::Function) -> Result<(), JsValue> {
+        let condition = Condition::new(&bits, comparison, value)?;
+        // The callback adds its gates through the qbits, so the tools must not be locked while it runs
+        let marker = {
+            let tools = self.2.lock();
+            (tools.len(), tools.last().map_or(0, |col| col.len()))
+        };
+        gates.call0(&JsValue::NULL)?;
+
+        Ok(condition_since(&mut self.2.lock(), marker, &condition)?)
+    }
+
+    /// Measures the qbits of the wires in the basis given by the columns of the row major unitary.
+    /// Bit j of the matrix index belongs to `wires[j]` and bit j of the outcome is written to `bits[j]`
+    #[wasm_bindgen(js_name = measurementBasis)]
+    pub fn measurement_basis(&self, wires: Vec<usize>, unitary: ComplexArray, bits: Vec<usize>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        self.push_col(basis_povm(&wires, unitary, &bits)?.into());
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = measurementBasisSameStep)]
+    pub fn measurement_basis_same_step(&self, wires: Vec<usize>, unitary: ComplexArray, bits: Vec<usize>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        self.push(basis_povm(&wires, unitary, &bits)?.into());
+
+        Ok(())
+    }
+
+    /// Measures the qbits of the wires with the concatenated row major kraus operators.
+    /// Bit j of the matrix index belongs to `wires[j]` and bit j of the outcome is written to `bits[j]`
+    #[wasm_bindgen(js_name = measurementPovm)]
+    pub fn measurement_povm(&self, wires: Vec<usize>, kraus: ComplexArray, bits: Vec<usize>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        self.push_col(kraus_povm(&wires, kraus, &bits)?.into());
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = measurementPovmSameStep)]
+    pub fn measurement_povm_same_step(&self, wires: Vec<usize>, kraus: ComplexArray, bits: Vec<usize>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        self.push(kraus_povm(&wires, kraus, &bits)?.into());
+
+        Ok(())
+    }
+
+    /// Applies `steps` repetitions of the product formula of the order, which approximates `e^{-iHt}` on the qbits
+    /// of the wires. Qbit i of the hamiltonian belongs to `wires[i]`
+    #[wasm_bindgen(js_name = trotterEvolution)]
+    pub fn trotter_evolution(&self, wires: Vec<usize>, hamiltonian: &Hamiltonian, time: f64, steps: usize, order: TrotterOrder) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        for tool in trotter_circuit(&wires, &hamiltonian.0, time, steps, order)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies p rounds of QAOA to `|+...+>` on the qbits of the wires, each made of the cost layer `e^{-i gamma C}`
+    /// and the mixer layer `e^{-i beta sum X_j}`. Qbit i of the cost hamiltonian belongs to `wires[i]`
+    pub fn qaoa(&self, wires: Vec<usize>, cost: &Hamiltonian, gammas: Vec<f64>, betas: Vec<f64>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        for tool in qaoa_circuit(&wires, &cost.0, &gammas, &betas)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the QAOA cost layer `e^{-i gamma C}` on the qbits of the wires
+    #[wasm_bindgen(js_name = qaoaCost)]
+    pub fn qaoa_cost(&self, wires: Vec<usize>, cost: &Hamiltonian, gamma: f64) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        for tool in qaoa_cost_layer(&wires, &cost.0, gamma)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the QAOA mixer layer `e^{-i beta sum X_j}` on the qbits of the wires
+    #[wasm_bindgen(js_name = qaoaMixer)]
+    pub fn qaoa_mixer(&self, wires: Vec<usize>, beta: f64) {
+        for tool in qaoa_mixer_layer(&wires, beta) {
+            self.push_col(tool);
+        }
+    }
+
+    /// Applies the hardware efficient ansatz of `RotationY` layers and controlled Z chains on the qbits of the wires,
+    /// which needs `wires.length * (layers + 1)` parameters
+    #[wasm_bindgen(js_name = hardwareEfficient)]
+    pub fn hardware_efficient(&self, wires: Vec<usize>, layers: usize, parameters: Vec<f64>) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        for tool in hardware_efficient_ansatz(&wires, layers, &parameters)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the UCC excitation `e^{theta (T - T^dagger)}` from the occupied to the unoccupied wires under the
+    /// Jordan-Wigner mapping
+    #[wasm_bindgen(js_name = uccExcitation)]
+    pub fn ucc_excitation(&self, theta: f64, occupied: Vec<usize>, unoccupied: Vec<usize>) -> Result<(), JsValue> {
+        expect_wires(&occupied)?;
+        for tool in excitation(&occupied, &unoccupied, theta)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the pauli string as `RotationPauliX`, `RotationPauliY` and `RotationPauliZ` gates with the angle,
+    /// `theta = pi` applies the string exactly. Qbit i of the string belongs to `wires[i]`
+    #[wasm_bindgen(js_name = rotationPauliString)]
+    pub fn rotation_pauli_string(&self, theta: f64, wires: Vec<usize>, string: &PauliString) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        for tool in pauli_gates(&pauli_wires(&wires, string)?, theta) {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Applies `exp(-i theta/2 P)` for the pauli string on the qbits of the wires
+    #[wasm_bindgen(js_name = pauliExponential)]
+    pub fn pauli_exponential(&self, theta: f64, wires: Vec<usize>, string: &PauliString) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        let mut tools = Vec::new();
+        pauli_rotation(&mut tools, &pauli_wires(&wires, string)?, theta);
+        for tool in tools {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Prepares the normalized amplitudes on the qbits of the wires, which have to be in `|0...0>`.
+    /// The amplitude index bit i belongs to `wires[i]`
+    pub fn initialize(&self, wires: Vec<usize>, amplitudes: ComplexArray) -> Result<(), JsValue> {
+        expect_wires(&wires)?;
+        let amplitudes: Vec<Complex> = serde_wasm_bindgen::from_value(amplitudes.into())?;
+        for tool in prepare_state(&wires, &amplitudes)? {
+            self.push_col(tool);
+        }
+
+        Ok(())
+    }
+
+    /// Marks a snapshot, which records the state after all previously added steps
+    pub fn snapshot(&mut self, name: String) {
+        self.3.push((name, self.2.lock().len()));
     }
 
     pub fn qbit(&mut self) -> QBit {
@@ -185,6 +606,44 @@ impl GateBuilder {
             self.bit()
         }).map(JsValue::from).map(JsValue::unchecked_into).collect()
     }
+
+    /// Allocates a named register of consecutive qbits, which keeps its name in the algorithm
+    pub fn qreg(&mut self, name: String, size: usize) -> Vec<QBitType> {
+        self.4.push((name, self.0, size));
+
+        self.qbits(size)
+    }
+
+    /// Allocates a named register of consecutive bits, which can be read as an integer from the results
+    pub fn creg(&mut self, name: String, size: usize) -> Vec<BitType> {
+        self.5.push((name, self.1, size));
+
+        self.bits(size)
+    }
+}
+
+impl GateBuilder {
+    fn push_col(&self, tool: Tool) {
+        self.2.lock().push(TinyVec::from([tool; 1]));
+    }
+
+    fn push(&self, tool: Tool) {
+        let mut tools = self.2.lock();
+        if let Some(col) = tools.last_mut() {
+            col.push(tool);
+        } else {
+            tools.push(TinyVec::from([tool; 1]));
+        }
+    }
+}
+
+/// Fails without wires, like the Rust builder functions acting on a slice of qbits.
+fn expect_wires(wires: &[usize]) -> Result<(), JsValue> {
+    if wires.is_empty() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one qbit")).into())
+    }
+
+    Ok(())
 }
 
 macro_rules! impl_operator {
@@ -594,6 +1053,44 @@ pub fn measurement_z_same_step(qbit: &QBit, bit: &Bit) {
     qbit.push(Measurement::new(qbit.idx(), Some(MeasurementBasis::Z), Some(bit.idx())).into())
 }
 
+/// Measures the product of `paulis[i]` on `wires[i]` into the bit, the eigenvalue `-1` is written as `true`
+#[wasm_bindgen]
+pub fn measurement_pauli(wires: Vec<usize>, paulis: &str, bit: &Bit) -> Result<(), JsValue> {
+    bit.push_col(PauliMeasurement::new(&wires, paulis, Some(bit.idx()))?.into());
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn measurement_pauli_same_step(wires: Vec<usize>, paulis: &str, bit: &Bit) -> Result<(), JsValue> {
+    bit.push(PauliMeasurement::new(&wires, paulis, Some(bit.idx()))?.into());
+
+    Ok(())
+}
+
+/// Reverses the wires, which list the least significant bit of the matrix index first.
+fn basis_povm(wires: &[usize], unitary: ComplexArray, bits: &[usize]) -> Result<Povm, JsValue> {
+    let unitary: Vec<Complex> = serde_wasm_bindgen::from_value(unitary.into())?;
+    let wires = wires.iter().rev().copied().collect::<Vec<_>>();
+
+    Ok(Povm::from_basis(&wires, &DynamicSizedMatrix::with_qbits(unitary, wires.len())?, bits)?)
+}
+
+/// Splits the concatenated row major kraus operators, the wires list the least significant bit of the matrix index first.
+fn kraus_povm(wires: &[usize], kraus: ComplexArray, bits: &[usize]) -> Result<Povm, JsValue> {
+    let kraus: Vec<Complex> = serde_wasm_bindgen::from_value(kraus.into())?;
+    let len = 0x1usize.checked_shl(2 * wires.len() as u32).unwrap_or(0);
+    if wires.is_empty() || len == 0 || kraus.len() % len != 0 {
+        return Err(JsValue::from_str(&format!("Expecting a multiple of {} entries but got {}", len, kraus.len())))
+    }
+    let wires = wires.iter().rev().copied().collect::<Vec<_>>();
+    let kraus = kraus.chunks(len)
+        .map(|operator| DynamicSizedMatrix::with_qbits(Vec::from(operator), wires.len()))
+        .collect::<crate::error::Result<Vec<_>>>()?;
+
+    Ok(Povm::new(&wires, kraus, bits)?)
+}
+
 #[wasm_bindgen]
 pub fn reset(qbit: &QBit) {
     qbit.push_col(Reset::new(qbit.idx(), false).into())
@@ -604,6 +1101,77 @@ pub fn reset_same_step(qbit: &QBit) {
     qbit.push(Reset::new(qbit.idx(), false).into())
 }
 
+/// Measures the qbit and flips it, if it is not in the state, `true` resets it to `|1>`
+#[wasm_bindgen]
+pub fn reset_to(qbit: &QBit, state: bool) {
+    qbit.push_col(Reset::new(qbit.idx(), state).into())
+}
+
+#[wasm_bindgen]
+pub fn reset_to_same_step(qbit: &QBit, state: bool) {
+    qbit.push(Reset::new(qbit.idx(), state).into())
+}
+
+fn classical(operator: BitwiseOperator, bits: &[usize], target: &Bit) -> Result<Tool, JsValue> {
+    Ok(ClassicalOperation::new(operator, bits, target.idx())?.into())
+}
+
+/// Writes the xor of the bits to the target, using the builder of the target
+#[wasm_bindgen]
+pub fn classical_xor(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push_col(classical(BitwiseOperator::Xor, &bits, target)?);
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn classical_xor_same_step(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push(classical(BitwiseOperator::Xor, &bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the and of the bits to the target, using the builder of the target
+#[wasm_bindgen]
+pub fn classical_and(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push_col(classical(BitwiseOperator::And, &bits, target)?);
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn classical_and_same_step(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push(classical(BitwiseOperator::And, &bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the or of the bits to the target, using the builder of the target
+#[wasm_bindgen]
+pub fn classical_or(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push_col(classical(BitwiseOperator::Or, &bits, target)?);
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn classical_or_same_step(bits: Vec<usize>, target: &Bit) -> Result<(), JsValue> {
+    target.push(classical(BitwiseOperator::Or, &bits, target)?);
+
+    Ok(())
+}
+
+/// Writes the negation of the bit to the target, which may be the bit itself
+#[wasm_bindgen]
+pub fn classical_not(bit: &Bit, target: &Bit) {
+    target.push_col(classical(BitwiseOperator::Not, &[bit.idx()], target).unwrap())
+}
+
+#[wasm_bindgen]
+pub fn classical_not_same_step(bit: &Bit, target: &Bit) {
+    target.push(classical(BitwiseOperator::Not, &[bit.idx()], target).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
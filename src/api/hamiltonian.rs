@@ -0,0 +1,385 @@
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::{ceil, fabs};
+use tinyvec::TinyVec;
+use crate::api::derive::Algorithm;
+use crate::api::QuantumRegister;
+#[cfg(feature = "wasm-bindgen")]
+use crate::api::EdgeArray;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::ket::{seed, Ket};
+use crate::runtime::pauli::{PauliString, PauliSum};
+use crate::toolbox::trotter::{trotter_circuit, TrotterOrder};
+
+#[cfg(feature = "wasm-bindgen")]
+use wasm_bindgen::prelude::*;
+
+/// The maximum number of taylor terms per chunk of the exact evolution.
+const MAX_TAYLOR_TERMS: usize = 64;
+
+/// A hermitian operator given as a real weighted sum of pauli strings.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hamiltonian(pub(crate) Vec<(f64, PauliString)>);
+
+impl Hamiltonian {
+    fn checked_add_term(&mut self, coefficient: f64, qbits: &[usize], paulis: &str) -> Result<()> {
+        if !coefficient.is_finite() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting a finite coefficient but got {}", coefficient
+            )))
+        }
+        self.0.push((coefficient, PauliString::checked_new(qbits, paulis)?));
+
+        Ok(())
+    }
+
+    /// Creates `sum w/2 (Z_i Z_j - 1)` for the weighted edges `(i, j, w)`, whose negated energy is the expected cut weight.
+    fn checked_max_cut(edges: &[(usize, usize, f64)]) -> Result<Hamiltonian> {
+        let mut hamiltonian = Hamiltonian(Vec::new());
+        let mut offset = 0.0;
+        for (i, j, weight) in edges {
+            hamiltonian.checked_add_term(weight / 2.0, &[*i, *j], "ZZ")?;
+            offset -= weight / 2.0;
+        }
+        if offset != 0.0 {
+            hamiltonian.0.push((offset, PauliString::default()));
+        }
+
+        Ok(hamiltonian)
+    }
+
+    /// Creates `sum J Z_i Z_j + sum h_i Z_i` for the couplings `(i, j, J)` and the field `h_i` of qbit i.
+    fn checked_ising(couplings: &[(usize, usize, f64)], fields: &[f64]) -> Result<Hamiltonian> {
+        let mut hamiltonian = Hamiltonian(Vec::new());
+        for (i, j, coupling) in couplings {
+            hamiltonian.checked_add_term(*coupling, &[*i, *j], "ZZ")?;
+        }
+        for (qbit, field) in fields.iter().enumerate().filter(|(_, field)| **field != 0.0) {
+            hamiltonian.checked_add_term(*field, &[qbit], "Z")?;
+        }
+
+        Ok(hamiltonian)
+    }
+
+    /// Checks that the hamiltonian acts on the state.
+    fn validate(&self, state: &QuantumRegister) -> Result<()> {
+        if self.size() > state.0.size() {
+            return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                "Expecting qbits below {} in the hamiltonian but was {}", state.0.size(), self.size() - 1
+            )))
+        }
+
+        Ok(())
+    }
+
+    fn validate_time(time: f64) -> Result<()> {
+        if !time.is_finite() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!("Expecting a finite time but got {}", time)))
+        }
+
+        Ok(())
+    }
+
+    /// Returns the energy `<psi|H|psi>` of the state.
+    pub(crate) fn checked_expectation(&self, state: &QuantumRegister) -> Result<f64> {
+        self.validate(state)?;
+        let amplitudes = state.complex_states();
+
+        Ok(self.apply(&amplitudes).iter()
+            .zip(amplitudes.iter())
+            .map(|(h, amplitude)| (amplitude.conjugate() * *h).re())
+            .sum())
+    }
+
+    /// Returns `H|psi>` for the amplitudes of `|psi>`.
+    pub(crate) fn apply(&self, amplitudes: &[Complex]) -> Vec<Complex> {
+        let mut result = vec![Complex::zero(); amplitudes.len()];
+        for (coefficient, string) in self.0.iter() {
+            for (idx, amplitude) in amplitudes.iter().enumerate() {
+                let (state, phase) = string.apply(idx);
+                result[state] = result[state] + phase * *amplitude * *coefficient;
+            }
+        }
+
+        result
+    }
+
+    /// Evolves the state by the taylor series of `e^{-iHt}`, the time is split into chunks with `|H dt| <= 1`,
+    /// so that the series of each chunk converges quickly.
+    fn checked_evolve(&self, state: &QuantumRegister, time: f64) -> Result<QuantumRegister> {
+        self.validate(state)?;
+        Hamiltonian::validate_time(time)?;
+        let norm = self.0.iter().map(|(coefficient, _)| fabs(*coefficient)).sum::<f64>();
+        let chunks = (ceil(norm * fabs(time)) as usize).max(1);
+        let dt = time / chunks as f64;
+
+        let mut amplitudes = state.complex_states();
+        for _ in 0..chunks {
+            let mut term = amplitudes.clone();
+            for k in 1..=MAX_TAYLOR_TERMS {
+                let factor = Complex::new(0.0, -dt / k as f64);
+                term = self.apply(&term).into_iter().map(|amplitude| amplitude * factor).collect();
+                for (amplitude, summand) in amplitudes.iter_mut().zip(term.iter()) {
+                    *amplitude = *amplitude + *summand;
+                }
+                if term.iter().map(|amplitude| amplitude.amplitude()).sum::<f64>() < 1e-30 {
+                    break
+                }
+            }
+        }
+
+        Ok(QuantumRegister(Ket::from_amplitudes(&amplitudes, seed())?))
+    }
+
+    fn checked_trotter_error(&self, state: &QuantumRegister, time: f64, steps: usize, order: TrotterOrder) -> Result<f64> {
+        self.validate(state)?;
+        Hamiltonian::validate_time(time)?;
+        let wires = (0..state.0.size()).collect::<Vec<_>>();
+        let tools = trotter_circuit(&wires, &self.0, time, steps, order)?
+            .into_iter()
+            .map(|tool| TinyVec::from([tool; 1]))
+            .collect();
+        let (trotterized, _) = Algorithm::from_parts(state.0.size(), 0, tools, Vec::new())?
+            .with_amplitudes(&state.complex_states())?
+            .run()?;
+        let exact = self.checked_evolve(state, time)?;
+
+        Ok(libm::sqrt((1.0 - trotterized.0.inner_product(&exact.0)?.amplitude()).max(0.0)))
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl Hamiltonian {
+    /// Returns the number of qbits the hamiltonian acts on, i.e. the highest qbit of a term plus one
+    pub fn size(&self) -> usize {
+        self.0.iter()
+            .flat_map(|(_, string)| string.qbits())
+            .max()
+            .map_or(0, |qbit| qbit + 1)
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl Hamiltonian {
+    /// Creates a hamiltonian without terms
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Hamiltonian {
+        Hamiltonian(Vec::new())
+    }
+
+    /// Creates `sum w/2 (Z_i Z_j - 1)` for the weighted edges `[i, j, w]`, whose negated energy is the expected cut weight
+    #[wasm_bindgen(js_name = maxCut)]
+    pub fn max_cut(edges: EdgeArray) -> core::result::Result<Hamiltonian, JsValue> {
+        let edges: Vec<(usize, usize, f64)> = serde_wasm_bindgen::from_value(edges.into())?;
+
+        Ok(Hamiltonian::checked_max_cut(&edges)?)
+    }
+
+    /// Creates `sum J Z_i Z_j + sum h_i Z_i` for the couplings `[i, j, J]` and the field `h_i` of qbit i
+    pub fn ising(couplings: EdgeArray, fields: Vec<f64>) -> core::result::Result<Hamiltonian, JsValue> {
+        let couplings: Vec<(usize, usize, f64)> = serde_wasm_bindgen::from_value(couplings.into())?;
+
+        Ok(Hamiltonian::checked_ising(&couplings, &fields)?)
+    }
+
+    /// Adds the term `coefficient * paulis[0] ... paulis[k]`, where `paulis[i]` is one of `X`, `Y`, `Z` or `I` on `qbits[i]`
+    #[wasm_bindgen(js_name = addTerm)]
+    pub fn add_term(&mut self, coefficient: f64, qbits: Vec<usize>, paulis: &str) -> core::result::Result<(), JsValue> {
+        Ok(self.checked_add_term(coefficient, &qbits, paulis)?)
+    }
+
+    /// Returns the terms as a sum of pauli strings with real coefficients
+    #[wasm_bindgen(js_name = toPauliSum)]
+    pub fn to_pauli_sum(&self) -> PauliSum {
+        PauliSum::from(self)
+    }
+
+    /// Returns the energy `<psi|H|psi>` of the state
+    pub fn expectation(&self, state: &QuantumRegister) -> core::result::Result<f64, JsValue> {
+        Ok(self.checked_expectation(state)?)
+    }
+
+    /// Returns the state evolved exactly by `e^{-iHt}`, which serves as reference for small systems
+    pub fn evolve(&self, state: &QuantumRegister, time: f64) -> core::result::Result<QuantumRegister, JsValue> {
+        Ok(self.checked_evolve(state, time)?)
+    }
+
+    /// Returns the trace distance between the trotterized and the exact evolution of the state
+    #[wasm_bindgen(js_name = trotterError)]
+    pub fn trotter_error(&self, state: &QuantumRegister, time: f64, steps: usize, order: TrotterOrder) -> core::result::Result<f64, JsValue> {
+        Ok(self.checked_trotter_error(state, time, steps, order)?)
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl Hamiltonian {
+    /// Creates a hamiltonian without terms.
+    pub fn new() -> Hamiltonian {
+        Hamiltonian(Vec::new())
+    }
+
+    /// Creates `sum w/2 (Z_i Z_j - 1)` for the weighted edges `(i, j, w)`, whose negated energy is the expected cut weight.
+    pub fn max_cut(edges: &[(usize, usize, f64)]) -> Result<Hamiltonian> {
+        Hamiltonian::checked_max_cut(edges)
+    }
+
+    /// Creates `sum J Z_i Z_j + sum h_i Z_i` for the couplings `(i, j, J)` and the field `h_i` of qbit i.
+    pub fn ising(couplings: &[(usize, usize, f64)], fields: &[f64]) -> Result<Hamiltonian> {
+        Hamiltonian::checked_ising(couplings, fields)
+    }
+
+    /// Adds the term `coefficient * paulis[0] ... paulis[k]`, where `paulis[i]` is one of `X`, `Y`, `Z` or `I` on `qbits[i]`.
+    /// Fails if the coefficient is not finite, a pauli is unknown or a qbit occurs twice.
+    pub fn add_term(&mut self, coefficient: f64, qbits: &[usize], paulis: &str) -> Result<()> {
+        self.checked_add_term(coefficient, qbits, paulis)
+    }
+
+    /// Returns the terms as `(coefficient, pauli string)`
+    pub fn terms(&self) -> &[(f64, PauliString)] {
+        &self.0
+    }
+
+    /// Returns the terms as a sum of pauli strings with real coefficients.
+    pub fn to_pauli_sum(&self) -> PauliSum {
+        PauliSum::from(self)
+    }
+
+    /// Returns the energy `<psi|H|psi>` of the state.
+    /// Fails if the hamiltonian acts on more qbits than the state.
+    pub fn expectation(&self, state: &QuantumRegister) -> Result<f64> {
+        self.checked_expectation(state)
+    }
+
+    /// Returns the state evolved exactly by `e^{-iHt}`, which serves as reference for small systems.
+    /// Fails if the hamiltonian acts on more qbits than the state.
+    pub fn evolve(&self, state: &QuantumRegister, time: f64) -> Result<QuantumRegister> {
+        self.checked_evolve(state, time)
+    }
+
+    /// Returns the trace distance between the trotterized and the exact evolution of the state.
+    pub fn trotter_error(&self, state: &QuantumRegister, time: f64, steps: usize, order: TrotterOrder) -> Result<f64> {
+        self.checked_trotter_error(state, time, steps, order)
+    }
+}
+
+impl From<&Hamiltonian> for PauliSum {
+    fn from(hamiltonian: &Hamiltonian) -> Self {
+        PauliSum::from_terms(hamiltonian.0.iter().map(|(coefficient, string)| (Complex::new(*coefficient, 0.0), string.clone())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use crate::api::derive::{hadamard, rotation_x, Algorithm};
+    use crate::api::hamiltonian::Hamiltonian;
+    use crate::api::QuantumRegister;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::{seed, Ket};
+    use crate::runtime::pauli::PauliSum;
+    use crate::toolbox::trotter::TrotterOrder;
+
+    fn ising(size: usize) -> Hamiltonian {
+        let mut hamiltonian = Hamiltonian::new();
+        for qbit in 0..size {
+            hamiltonian.checked_add_term(0.7, &[qbit], "X").unwrap();
+            if qbit + 1 < size {
+                hamiltonian.checked_add_term(-1.0, &[qbit, qbit + 1], "ZZ").unwrap();
+            }
+        }
+
+        hamiltonian
+    }
+
+    #[test]
+    fn test_evolve() {
+        // e^{-iXt}|0> = cos(t)|0> - i sin(t)|1>
+        let mut hamiltonian = Hamiltonian::new();
+        hamiltonian.checked_add_term(1.0, &[0], "X").unwrap();
+        let state = QuantumRegister(Ket::new(1).unwrap());
+        let evolved = hamiltonian.checked_evolve(&state, 2.5).unwrap().complex_states();
+        assert_approx_eq!(Complex, evolved[0], Complex::new(libm::cos(2.5), 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, evolved[1], Complex::new(0.0, -libm::sin(2.5)), epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_commuting_terms_are_exact() {
+        let mut hamiltonian = Hamiltonian::new();
+        hamiltonian.checked_add_term(0.4, &[0, 1], "XX").unwrap();
+        hamiltonian.checked_add_term(-0.9, &[0, 1], "YY").unwrap();
+        hamiltonian.checked_add_term(0.6, &[2], "Z").unwrap();
+        let (state, _) = Algorithm::new(|gate_builder| {
+            let qbits = [gate_builder.qbit(), gate_builder.qbit(), gate_builder.qbit()];
+            hadamard(qbits[0]);
+            rotation_x(0.3, qbits[2]);
+
+            gate_builder
+        }).unwrap().run().unwrap();
+
+        let error = hamiltonian.checked_trotter_error(&state, 1.3, 1, TrotterOrder::First).unwrap();
+        assert!(error < 0.00001);
+    }
+
+    #[test]
+    fn test_trotter_error() {
+        let hamiltonian = ising(3);
+        let (state, _) = Algorithm::new(|gate_builder| {
+            let qbits = [gate_builder.qbit(), gate_builder.qbit(), gate_builder.qbit()];
+            hadamard(qbits[1]);
+
+            gate_builder
+        }).unwrap().run().unwrap();
+
+        let errors = [(1, TrotterOrder::First), (8, TrotterOrder::First), (8, TrotterOrder::Second), (8, TrotterOrder::Fourth)]
+            .iter()
+            .map(|(steps, order)| hamiltonian.checked_trotter_error(&state, 0.5, *steps, *order).unwrap())
+            .collect::<Vec<_>>();
+        for pair in errors.windows(2) {
+            assert!(pair[1] < pair[0], "Expecting decreasing errors but got {:?}", errors);
+        }
+        assert!(errors[3] < 0.01);
+    }
+
+    #[test]
+    fn test_expectation() {
+        // The triangle has a maximum cut of 2, which |001> attains
+        let hamiltonian = Hamiltonian::checked_max_cut(&[(0, 1, 1.0), (1, 2, 1.0), (0, 2, 1.0)]).unwrap();
+        let state = QuantumRegister(Ket::from_basis_state(3, 0b001, seed()).unwrap());
+        assert_approx_eq!(f64, hamiltonian.checked_expectation(&state).unwrap(), -2.0, epsilon = tolerance(0.000001));
+        let state = QuantumRegister(Ket::new(3).unwrap());
+        assert_approx_eq!(f64, hamiltonian.checked_expectation(&state).unwrap(), 0.0, epsilon = tolerance(0.000001));
+
+        let hamiltonian = Hamiltonian::checked_ising(&[(0, 1, -1.0)], &[0.5, 0.0, 0.25]).unwrap();
+        assert_eq!(hamiltonian.0.len(), 3);
+        assert_approx_eq!(f64, hamiltonian.checked_expectation(&state).unwrap(), -0.25, epsilon = tolerance(0.000001));
+        assert!(Hamiltonian::checked_ising(&[(1, 1, -1.0)], &[]).is_err());
+    }
+
+    #[test]
+    fn test_pauli_sum() {
+        // The transverse field does not commute with the coupling
+        let hamiltonian = ising(2);
+        let sum = PauliSum::from(&hamiltonian);
+        assert!(sum.is_hermitian());
+        assert_eq!(sum.0.len(), 3);
+        assert!(!sum.commutes(&PauliSum::from(&Hamiltonian::checked_ising(&[(0, 1, 1.0)], &[]).unwrap())));
+        assert!(sum.commutes(&sum));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut hamiltonian = Hamiltonian::new();
+        assert!(hamiltonian.checked_add_term(f64::NAN, &[0], "X").is_err());
+        hamiltonian.checked_add_term(1.0, &[2], "Z").unwrap();
+        assert_eq!(hamiltonian.size(), 3);
+
+        let state = QuantumRegister(Ket::new(2).unwrap());
+        assert!(hamiltonian.checked_evolve(&state, 1.0).is_err());
+        assert!(hamiltonian.checked_trotter_error(&state, 1.0, 1, TrotterOrder::First).is_err());
+    }
+}
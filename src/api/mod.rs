@@ -1,10 +1,30 @@
 use core::fmt::{Display, Formatter};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::api::svg::{bar_chart, city_plot, q_sphere, SvgOptions};
+use crate::runtime::density_matrix::{concurrence, entropy, purity, reduced_density_matrix, schmidt_decomposition, validate_qbits, Schmidt};
 use crate::runtime::ket::Ket;
+use crate::runtime::mps::Mps;
 use crate::runtime::register::Register;
+use crate::runtime::simulator::Simulator;
+use crate::runtime::sparse_ket::SparseKet;
+use crate::runtime::stabilizer::Tableau;
 use crate::complex::Complex;
+use crate::toolbox::Tool;
 
 pub mod derive;
+pub mod svg;
+pub mod hamiltonian;
+
+pub use crate::runtime::classical::Comparison;
+pub use crate::runtime::pauli::{PauliString, PauliSum};
+pub use crate::toolbox::trotter::TrotterOrder;
+#[cfg(feature = "serde")]
+pub mod schema;
 #[cfg(feature = "wasm-bindgen")]
 pub mod derive_js;
 
@@ -20,6 +40,70 @@ pub struct QuantumRegister(pub(crate) Ket);
 extern "C" {
     #[wasm_bindgen(typescript_type = "Complex[]")]
     pub type ComplexArray;
+
+    #[wasm_bindgen(typescript_type = "[number, number][]")]
+    pub type OutcomeArray;
+
+    #[wasm_bindgen(typescript_type = "Map<string, number>")]
+    pub type DistributionMap;
+
+    #[wasm_bindgen(typescript_type = "Map<string, number>")]
+    pub type CountMap;
+
+    #[wasm_bindgen(typescript_type = "[number, number, number][]")]
+    pub type EdgeArray;
+
+    #[wasm_bindgen(typescript_type = "PauliSum[]")]
+    pub type PauliSumArray;
+}
+
+impl QuantumRegister {
+    fn complex_states(&self) -> Vec<Complex> {
+        self.0.state().iter().map(|c| Complex::from(*c)).collect()
+    }
+
+    fn checked_marginal_probabilities(&self, qbits: &[usize]) -> Result<Vec<f64>> {
+        validate_qbits(&self.0, qbits)?;
+
+        Ok(self.0.marginal_probabilities(qbits))
+    }
+
+    fn checked_top_outcomes(&self, qbits: &[usize], k: usize) -> Result<Vec<(usize, f64)>> {
+        let mut outcomes = self.checked_marginal_probabilities(qbits)?
+            .into_iter()
+            .enumerate()
+            .filter(|(_, probability)| *probability > 0.0)
+            .collect::<Vec<_>>();
+        outcomes.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        outcomes.truncate(k);
+
+        Ok(outcomes)
+    }
+
+    fn compatible(&self, other: &QuantumRegister) -> Result<()> {
+        if self.0.size() != other.0.size() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting registers of the same size but got {} and {} qbits", self.0.size(), other.0.size()
+            )))
+        }
+
+        Ok(())
+    }
+
+    fn checked_equals_up_to_global_phase(&self, other: &QuantumRegister, epsilon: f64) -> Result<bool> {
+        self.compatible(other)?;
+        let (a, b) = (self.complex_states(), other.complex_states());
+        // The phase is taken from the largest amplitude, as it is the most precise one
+        let (idx, _) = a.iter()
+            .enumerate()
+            .fold((0, 0.0), |(idx, max), (i, v)| if v.amplitude() > max { (i, v.amplitude()) } else { (idx, max) });
+        if b[idx].abs() <= epsilon {
+            return Ok(a[idx].abs() <= epsilon)
+        }
+        let phase = a[idx] * b[idx].conjugate() * (1.0 / (a[idx].abs() * b[idx].abs()));
+
+        Ok(a.iter().zip(b.iter()).all(|(a, b)| (*a - phase * *b).abs() <= epsilon))
+    }
 }
 
 #[cfg(feature = "wasm-bindgen")]
@@ -34,7 +118,7 @@ impl QuantumRegister {
 
     /// Returns the states of this index
     pub fn state(&self, idx: usize) -> Option<Complex> {
-        self.0.state().get(idx).map(|c| *c)
+        self.0.state().get(idx).map(|c| Complex::from(*c))
     }
 
     /// Returns the probability of all states
@@ -48,21 +132,130 @@ impl QuantumRegister {
     }
 
     /// Returns the probability of a qbit
-    pub fn probability(&self, bit: usize) -> f64 {
-        self.0.probability(bit)
+    pub fn probability(&self, bit: usize) -> core::result::Result<f64, JsValue> {
+        Ok(self.0.probability(bit)?)
+    }
+
+    /// Returns the bloch vector `[<X>, <Y>, <Z>]` of a qbit
+    #[wasm_bindgen(js_name = blochVector)]
+    pub fn bloch_vector(&self, bit: usize) -> core::result::Result<js_sys::Float64Array, JsValue> {
+        Ok(js_sys::Float64Array::from(self.0.bloch_vector(bit)?.as_slice()))
+    }
+
+    /// Returns the bloch vectors of all qbits, three consecutive values per qbit
+    #[wasm_bindgen(js_name = blochVectors)]
+    pub fn bloch_vectors(&self) -> js_sys::Float64Array {
+        let vectors = (0..self.0.size()).filter_map(|bit| self.0.bloch_vector(bit).ok()).flatten().collect::<Vec<_>>();
+
+        js_sys::Float64Array::from(vectors.as_slice())
+    }
+
+    /// Returns the joint distribution of the qbits, where bit j of the outcome belongs to the j-th qbit
+    #[wasm_bindgen(js_name = marginalProbabilities)]
+    pub fn marginal_probabilities(&self, qbits: Vec<usize>) -> core::result::Result<js_sys::Float64Array, JsValue> {
+        Ok(js_sys::Float64Array::from(self.checked_marginal_probabilities(&qbits)?.as_slice()))
+    }
+
+    /// Returns the k most likely outcomes of the qbits as `[outcome, probability]` in descending order
+    #[wasm_bindgen(js_name = topOutcomes)]
+    pub fn top_outcomes(&self, qbits: Vec<usize>, k: usize) -> core::result::Result<OutcomeArray, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        Ok(serde_wasm_bindgen::to_value(&self.checked_top_outcomes(&qbits, k)?)?.unchecked_into())
+    }
+
+    /// Returns the probability of all states matching the pattern, which lists the qbits from the highest to
+    /// the lowest with `0`, `1` or one of the wildcards `x` and `*`
+    #[wasm_bindgen(js_name = patternProbability)]
+    pub fn pattern_probability(&self, pattern: &str) -> core::result::Result<f64, JsValue> {
+        Ok(self.0.pattern_probability(pattern)?)
+    }
+
+    /// Returns the inner product `<self|other>`
+    #[wasm_bindgen(js_name = innerProduct)]
+    pub fn inner_product(&self, other: &QuantumRegister) -> core::result::Result<Complex, JsValue> {
+        Ok(self.0.inner_product(&other.0)?)
+    }
+
+    /// Returns the fidelity `|<self|other>|^2` of both states
+    pub fn fidelity(&self, other: &QuantumRegister) -> core::result::Result<f64, JsValue> {
+        Ok(self.0.inner_product(&other.0)?.amplitude())
+    }
+
+    /// Returns the trace distance `sqrt(1 - |<self|other>|^2)` of both pure states
+    #[wasm_bindgen(js_name = traceDistance)]
+    pub fn trace_distance(&self, other: &QuantumRegister) -> core::result::Result<f64, JsValue> {
+        Ok(libm::sqrt((1.0 - self.0.inner_product(&other.0)?.amplitude()).max(0.0)))
+    }
+
+    /// Returns true, if all amplitudes are equal after removing the global phase, within epsilon
+    #[wasm_bindgen(js_name = equalsUpToGlobalPhase)]
+    pub fn equals_up_to_global_phase(&self, other: &QuantumRegister, epsilon: f64) -> core::result::Result<bool, JsValue> {
+        Ok(self.checked_equals_up_to_global_phase(other, epsilon)?)
+    }
+
+    /// Returns an svg bar chart of the probabilities of all states, coloured by their phase
+    #[wasm_bindgen(js_name = barChartSvg)]
+    pub fn bar_chart_svg(&self, options: SvgOptions) -> String {
+        bar_chart(&self.complex_states(), self.0.size(), &options)
+    }
+
+    /// Returns an svg of the states on a q-sphere
+    #[wasm_bindgen(js_name = qSphereSvg)]
+    pub fn q_sphere_svg(&self, options: SvgOptions) -> String {
+        q_sphere(&self.complex_states(), self.0.size(), &options)
+    }
+
+    /// Returns an svg city plot of the density matrix of the qbits
+    #[wasm_bindgen(js_name = cityPlotSvg)]
+    pub fn city_plot_svg(&self, qbits: Vec<usize>, options: SvgOptions) -> core::result::Result<String, JsValue> {
+        let density = reduced_density_matrix(&self.0, &qbits)?;
+
+        Ok(city_plot(&density, 0x1 << qbits.len(), &options))
+    }
+
+    /// Returns the density matrix of the qbits with all other qbits traced out, row major with `2^k x 2^k` entries
+    #[wasm_bindgen(js_name = reducedDensityMatrix)]
+    pub fn reduced_density_matrix(&self, qbits: Vec<usize>) -> core::result::Result<ComplexArray, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        let density = reduced_density_matrix(&self.0, &qbits)?;
+
+        Ok(serde_wasm_bindgen::to_value(&density)?.unchecked_into())
+    }
+
+    /// Returns the von neumann entropy of the qbits in bits
+    pub fn entropy(&self, qbits: Vec<usize>) -> core::result::Result<f64, JsValue> {
+        Ok(entropy(&self.0, &qbits)?)
+    }
+
+    /// Returns the purity `Tr(rho^2)` of the qbits
+    pub fn purity(&self, qbits: Vec<usize>) -> core::result::Result<f64, JsValue> {
+        Ok(purity(&self.0, &qbits)?)
+    }
+
+    /// Returns the concurrence of two qbits
+    pub fn concurrence(&self, a: usize, b: usize) -> core::result::Result<f64, JsValue> {
+        Ok(concurrence(&self.0, a, b)?)
+    }
+
+    /// Returns the schmidt decomposition across the qbits and all other qbits
+    #[wasm_bindgen(js_name = schmidtDecomposition)]
+    pub fn schmidt_decomposition(&self, qbits: Vec<usize>) -> core::result::Result<SchmidtDecomposition, JsValue> {
+        Ok(SchmidtDecomposition(schmidt_decomposition(&self.0, &qbits)?))
     }
 }
 
 #[cfg(not(feature = "wasm-bindgen"))]
 impl QuantumRegister {
     /// Returns the array of all states
-    pub fn states(&self) -> &Vec<Complex> {
+    pub fn states(&self) -> &Vec<crate::complex::Amplitude> {
         self.0.state()
     }
 
     /// Returns the states of this index
     pub fn state(&self, idx: usize) -> Option<Complex> {
-        self.0.state().get(idx).map(|c| *c)
+        self.0.state().get(idx).map(|c| Complex::from(*c))
     }
 
     /// Returns the probability of all states
@@ -76,21 +269,675 @@ impl QuantumRegister {
     }
 
     /// Returns the probability of a qbit
-    pub fn probability(&self, bit: usize) -> f64 {
+    pub fn probability(&self, bit: usize) -> Result<f64> {
         self.0.probability(bit)
     }
+
+    /// Returns the bloch vector `[<X>, <Y>, <Z>]` of a qbit
+    pub fn bloch_vector(&self, bit: usize) -> Result<[f64; 3]> {
+        self.0.bloch_vector(bit)
+    }
+
+    /// Returns the bloch vectors of all qbits
+    pub fn bloch_vectors(&self) -> Vec<[f64; 3]> {
+        (0..self.0.size()).filter_map(|bit| self.0.bloch_vector(bit).ok()).collect()
+    }
+
+    /// Returns the joint distribution of the qbits, where bit j of the outcome belongs to the j-th qbit
+    pub fn marginal_probabilities(&self, qbits: &[usize]) -> Result<Vec<f64>> {
+        self.checked_marginal_probabilities(qbits)
+    }
+
+    /// Returns the k most likely outcomes of the qbits as `(outcome, probability)` in descending order
+    pub fn top_outcomes(&self, qbits: &[usize], k: usize) -> Result<Vec<(usize, f64)>> {
+        self.checked_top_outcomes(qbits, k)
+    }
+
+    /// Returns the probability of all states matching the pattern, which lists the qbits from the highest to
+    /// the lowest with `0`, `1` or one of the wildcards `x` and `*`
+    pub fn pattern_probability(&self, pattern: &str) -> Result<f64> {
+        self.0.pattern_probability(pattern)
+    }
+
+    /// Returns the inner product `<self|other>`
+    pub fn inner_product(&self, other: &QuantumRegister) -> Result<Complex> {
+        self.0.inner_product(&other.0)
+    }
+
+    /// Returns the fidelity `|<self|other>|^2` of both states
+    pub fn fidelity(&self, other: &QuantumRegister) -> Result<f64> {
+        Ok(self.0.inner_product(&other.0)?.amplitude())
+    }
+
+    /// Returns the trace distance `sqrt(1 - |<self|other>|^2)` of both pure states
+    pub fn trace_distance(&self, other: &QuantumRegister) -> Result<f64> {
+        Ok(libm::sqrt((1.0 - self.0.inner_product(&other.0)?.amplitude()).max(0.0)))
+    }
+
+    /// Returns true, if all amplitudes are equal after removing the global phase, within epsilon
+    pub fn equals_up_to_global_phase(&self, other: &QuantumRegister, epsilon: f64) -> Result<bool> {
+        self.checked_equals_up_to_global_phase(other, epsilon)
+    }
+
+    /// Returns an svg bar chart of the probabilities of all states, coloured by their phase
+    pub fn bar_chart_svg(&self, options: &SvgOptions) -> String {
+        bar_chart(&self.complex_states(), self.0.size(), options)
+    }
+
+    /// Returns an svg of the states on a q-sphere
+    pub fn q_sphere_svg(&self, options: &SvgOptions) -> String {
+        q_sphere(&self.complex_states(), self.0.size(), options)
+    }
+
+    /// Returns an svg city plot of the density matrix of the qbits
+    pub fn city_plot_svg(&self, qbits: &[usize], options: &SvgOptions) -> Result<String> {
+        let density = reduced_density_matrix(&self.0, qbits)?;
+
+        Ok(city_plot(&density, 0x1 << qbits.len(), options))
+    }
+
+    /// Returns the density matrix of the qbits with all other qbits traced out, row major with `2^k x 2^k` entries
+    pub fn reduced_density_matrix(&self, qbits: &[usize]) -> Result<Vec<Complex>> {
+        reduced_density_matrix(&self.0, qbits)
+    }
+
+    /// Returns the von neumann entropy of the qbits in bits
+    pub fn entropy(&self, qbits: &[usize]) -> Result<f64> {
+        entropy(&self.0, qbits)
+    }
+
+    /// Returns the purity `Tr(rho^2)` of the qbits
+    pub fn purity(&self, qbits: &[usize]) -> Result<f64> {
+        purity(&self.0, qbits)
+    }
+
+    /// Returns the concurrence of two qbits
+    pub fn concurrence(&self, a: usize, b: usize) -> Result<f64> {
+        concurrence(&self.0, a, b)
+    }
+
+    /// Returns the schmidt decomposition across the qbits and all other qbits
+    pub fn schmidt_decomposition(&self, qbits: &[usize]) -> Result<SchmidtDecomposition> {
+        Ok(SchmidtDecomposition(schmidt_decomposition(&self.0, qbits)?))
+    }
+}
+
+/// The schmidt decomposition `sum_i c_i |a_i>|b_i>` of a state, where `a_i` are states of the chosen qbits
+/// and `b_i` states of the remaining qbits in ascending order.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct SchmidtDecomposition(pub(crate) Schmidt);
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl SchmidtDecomposition {
+    /// Returns the schmidt coefficients in descending order
+    pub fn coefficients(&self) -> Vec<f64> {
+        self.0.coefficients.clone()
+    }
+
+    /// Returns the state of the chosen qbits of a term
+    pub fn left(&self, idx: usize) -> core::result::Result<ComplexArray, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        let state = self.0.a.get(idx).ok_or_else(|| JsValue::from_str("Schmidt term does not exist"))?;
+
+        Ok(serde_wasm_bindgen::to_value(state)?.unchecked_into())
+    }
+
+    /// Returns the state of the remaining qbits of a term
+    pub fn right(&self, idx: usize) -> core::result::Result<ComplexArray, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        let state = self.0.b.get(idx).ok_or_else(|| JsValue::from_str("Schmidt term does not exist"))?;
+
+        Ok(serde_wasm_bindgen::to_value(state)?.unchecked_into())
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl SchmidtDecomposition {
+    /// Returns the schmidt coefficients in descending order
+    pub fn coefficients(&self) -> &Vec<f64> {
+        &self.0.coefficients
+    }
+
+    /// Returns the state of the chosen qbits of a term
+    pub fn left(&self, idx: usize) -> Option<&Vec<Complex>> {
+        self.0.a.get(idx)
+    }
+
+    /// Returns the state of the remaining qbits of a term
+    pub fn right(&self, idx: usize) -> Option<&Vec<Complex>> {
+        self.0.b.get(idx)
+    }
+}
+
+/// The options of a matrix product state simulation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MpsOptions {
+    /// The maximal dimension of the bonds between neighbouring qbits
+    pub max_bond_dimension: usize,
+    /// Singular values are discarded, as long as their accumulated weight stays below this threshold
+    pub truncation_threshold: f64,
+}
+
+impl Default for MpsOptions {
+    fn default() -> Self {
+        MpsOptions {
+            max_bond_dimension: 64,
+            truncation_threshold: 1e-12,
+        }
+    }
+}
+
+/// A copy of the state and the classical register, recorded at a snapshot marker.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Snapshot(pub(crate) Ket, pub(crate) Register);
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl Snapshot {
+    /// Returns the recorded state
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = quantumRegister))]
+    pub fn quantum_register(&self) -> QuantumRegister {
+        QuantumRegister(self.0.clone())
+    }
+
+    /// Returns the recorded classical register
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = classicalRegister))]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        ClassicalRegister(self.1.clone())
+    }
+}
+
+/// All snapshots recorded during a run, retrievable by the name of their marker.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Snapshots(pub(crate) BTreeMap<String, Snapshot>);
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl Snapshots {
+    /// Returns the snapshot with this name
+    pub fn get(&self, name: &str) -> Option<Snapshot> {
+        self.0.get(name).cloned()
+    }
+
+    /// Returns the names of all snapshots in alphabetical order
+    pub fn names(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+}
+
+/// One leaf of the branching simulation, reached with the outcomes of all measurements along its path.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Branch(pub(crate) f64, pub(crate) Vec<usize>, pub(crate) Ket, pub(crate) Register);
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl Branch {
+    /// Returns the probability of this branch
+    pub fn probability(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns the outcome of each measurement along the path in the order of execution
+    pub fn outcomes(&self) -> Vec<usize> {
+        self.1.clone()
+    }
+
+    /// Returns the state at the end of the branch
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = quantumRegister))]
+    pub fn quantum_register(&self) -> QuantumRegister {
+        QuantumRegister(self.2.clone())
+    }
+
+    /// Returns the classical register at the end of the branch
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = classicalRegister))]
+    pub fn classical_register(&self) -> ClassicalRegister {
+        ClassicalRegister(self.3.clone())
+    }
+}
+
+/// All branches with a nonzero probability, which result from enumerating every measurement outcome.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Branches(pub(crate) Vec<Branch>);
+
+impl Branches {
+    /// Sums the probabilities of all branches with the same classical register, printed with the highest bit first.
+    fn merged(&self) -> BTreeMap<String, f64> {
+        let mut distribution = BTreeMap::new();
+        for branch in self.0.iter() {
+            *distribution.entry(format!("{}", branch.3)).or_insert(0.0) += branch.0;
+        }
+
+        distribution
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl Branches {
+    /// Returns the number of branches
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the branch at the index
+    pub fn get(&self, idx: usize) -> Option<Branch> {
+        self.0.get(idx).cloned()
+    }
+
+    /// Returns the exact distribution of the classical register, printed with the highest bit first
+    pub fn distribution(&self) -> core::result::Result<DistributionMap, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        Ok(serde_wasm_bindgen::to_value(&self.merged())?.unchecked_into())
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl Branches {
+    /// Returns the number of branches
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the branch at the index
+    pub fn get(&self, idx: usize) -> Option<&Branch> {
+        self.0.get(idx)
+    }
+
+    /// Returns all branches in the order of their outcomes
+    pub fn branches(&self) -> &[Branch] {
+        &self.0
+    }
+
+    /// Returns the exact distribution of the classical register, printed with the highest bit first
+    pub fn distribution(&self) -> BTreeMap<String, f64> {
+        self.merged()
+    }
+}
+
+/// A read-only view of a gate, measurement or reset of an algorithm.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct Gate {
+    pub(crate) step: usize,
+    pub(crate) name: &'static str,
+    pub(crate) controls: Vec<usize>,
+    pub(crate) targets: Vec<usize>,
+    pub(crate) parameters: Vec<f64>,
+    pub(crate) bits: Vec<usize>,
+    pub(crate) classical_control: Option<usize>,
+    pub(crate) unitary: bool,
+}
+
+impl Gate {
+    /// Describes the tool, or returns none if the tool does nothing.
+    pub(crate) fn from_tool(step: usize, tool: &Tool) -> Option<Gate> {
+        if let Tool::None = tool {
+            return None
+        }
+        let mut targets = tool.wires();
+        let controls = targets.drain(..tool.controls()).collect();
+        let unitary = tool.unitary();
+
+        Some(Gate {
+            step,
+            name: tool.name(),
+            controls,
+            targets,
+            parameters: tool.parameters(),
+            bits: if unitary.is_some() { Vec::new() } else { tool.bits() },
+            classical_control: unitary.as_ref().and_then(|o| o.classical_control()),
+            unitary: unitary.is_some(),
+        })
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl Gate {
+    /// Returns the index of the step, which executes this gate
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the name of the gate, e.g. `ControlledPauliX` or `Measurement`
+    pub fn name(&self) -> String {
+        String::from(self.name)
+    }
+
+    /// Returns the control qbits, the outer control first
+    pub fn controls(&self) -> Vec<usize> {
+        self.controls.clone()
+    }
+
+    /// Returns the qbits the gate acts on without the controls
+    pub fn targets(&self) -> Vec<usize> {
+        self.targets.clone()
+    }
+
+    /// Returns the angles of a rotation, `theta`, `phi` and `lambda` for `RotationU`
+    pub fn parameters(&self) -> Vec<f64> {
+        self.parameters.clone()
+    }
+
+    /// Returns the bits a measurement writes to, or a classical operation or a condition reads
+    pub fn bits(&self) -> Vec<usize> {
+        self.bits.clone()
+    }
+
+    /// Returns the bit, which has to be set to apply the gate
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = classicalControl))]
+    pub fn classical_control(&self) -> Option<usize> {
+        self.classical_control
+    }
+
+    /// Returns false for measurements, resets, classical operations and conditional gates
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = isUnitary))]
+    pub fn is_unitary(&self) -> bool {
+        self.unitary
+    }
+}
+
+/// Summary metrics of an algorithm.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct CircuitStats {
+    pub(crate) depth: usize,
+    pub(crate) width: usize,
+    pub(crate) counts: BTreeMap<String, usize>,
+    pub(crate) two_qbit_count: usize,
+    pub(crate) t_count: usize,
+    pub(crate) qbit_usage: Vec<usize>,
+}
+
+impl CircuitStats {
+    /// Computes the metrics of the gates on the registers.
+    pub(crate) fn new(size: usize, bits: usize, gates: &[Gate]) -> CircuitStats {
+        let mut qbit_layers = vec![0; size];
+        let mut bit_layers = vec![0; bits];
+        let mut qbit_usage = qbit_layers.clone();
+        let mut counts = BTreeMap::new();
+        for gate in gates {
+            let wires = gate.controls.iter().chain(gate.targets.iter());
+            let bits = gate.bits.iter().chain(gate.classical_control.iter());
+            // A gate starts after the last gate on any of its qbits and bits
+            let layer = wires.clone().map(|wire| qbit_layers[*wire])
+                .chain(bits.clone().map(|bit| bit_layers[*bit]))
+                .max()
+                .unwrap_or(0) + 1;
+            for wire in wires {
+                qbit_layers[*wire] = layer;
+                qbit_usage[*wire] += 1;
+            }
+            for bit in bits {
+                bit_layers[*bit] = layer;
+            }
+            *counts.entry(String::from(gate.name)).or_insert(0) += 1;
+        }
+
+        CircuitStats {
+            depth: qbit_layers.iter().chain(bit_layers.iter()).copied().max().unwrap_or(0),
+            width: size,
+            counts,
+            two_qbit_count: gates.iter()
+                .filter(|gate| gate.unitary && gate.controls.len() + gate.targets.len() == 2)
+                .count(),
+            t_count: gates.iter()
+                .filter(|gate| gate.name == "PhaseRoot" || gate.name == "PhaseRootDagger")
+                .count(),
+            qbit_usage,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl CircuitStats {
+    /// Returns the number of layers, where gates in one layer share neither qbits nor bits
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of qbits
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of gates, measurements and resets
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = gateCount))]
+    pub fn gate_count(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns the number of unitary gates on exactly two qbits, including the controls
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = twoQbitCount))]
+    pub fn two_qbit_count(&self) -> usize {
+        self.two_qbit_count
+    }
+
+    /// Returns the number of `PhaseRoot` and `PhaseRootDagger` gates
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = tCount))]
+    pub fn t_count(&self) -> usize {
+        self.t_count
+    }
+
+    /// Returns the number of gates, measurements and resets on each qbit
+    #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = qbitUsage))]
+    pub fn qbit_usage(&self) -> Vec<usize> {
+        self.qbit_usage.clone()
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl CircuitStats {
+    /// Returns the number of gates by their name
+    pub fn counts(&self) -> core::result::Result<CountMap, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        Ok(serde_wasm_bindgen::to_value(&self.counts)?.unchecked_into())
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl CircuitStats {
+    /// Returns the number of gates by their name
+    pub fn counts(&self) -> &BTreeMap<String, usize> {
+        &self.counts
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct MpsRegister(pub(crate) Mps);
+
+impl MpsRegister {
+    fn checked_amplitude(&self, bits: &[bool]) -> Result<Complex> {
+        if bits.len() != self.0.size() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting the state of {} qbits but got {}", self.0.size(), bits.len()
+            )))
+        }
+
+        Ok(self.0.amplitude(bits))
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl MpsRegister {
+    /// Returns the amplitude of the basis state, given by the state of each qbit
+    pub fn amplitude(&self, bits: Vec<js_sys::Boolean>) -> core::result::Result<Complex, JsValue> {
+        let bits: Vec<bool> = bits.iter().map(|b| b.value_of()).collect();
+
+        Ok(self.checked_amplitude(bits.as_slice())?)
+    }
+
+    /// Returns the probabilities of each qbit
+    pub fn probabilities(&self) -> js_sys::Float64Array {
+        js_sys::Float64Array::from(self.0.probabilities().as_slice())
+    }
+
+    /// Returns the probability of a qbit
+    pub fn probability(&self, bit: usize) -> core::result::Result<f64, JsValue> {
+        Ok(self.0.probability(bit)?)
+    }
+
+    /// Returns the accumulated weight of all discarded singular values
+    #[wasm_bindgen(js_name = truncationError)]
+    pub fn truncation_error(&self) -> f64 {
+        self.0.truncation_error()
+    }
+
+    /// Returns the dimensions of the bonds between neighbouring qbits
+    #[wasm_bindgen(js_name = bondDimensions)]
+    pub fn bond_dimensions(&self) -> Vec<usize> {
+        self.0.bond_dimensions()
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl MpsRegister {
+    /// Returns the amplitude of the basis state, given by the state of each qbit
+    pub fn amplitude(&self, bits: &[bool]) -> Result<Complex> {
+        self.checked_amplitude(bits)
+    }
+
+    /// Returns the probabilities of each qbit
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.0.probabilities()
+    }
+
+    /// Returns the probability of a qbit
+    pub fn probability(&self, bit: usize) -> Result<f64> {
+        self.0.probability(bit)
+    }
+
+    /// Returns the accumulated weight of all discarded singular values
+    pub fn truncation_error(&self) -> f64 {
+        self.0.truncation_error()
+    }
+
+    /// Returns the dimensions of the bonds between neighbouring qbits
+    pub fn bond_dimensions(&self) -> Vec<usize> {
+        self.0.bond_dimensions()
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct SparseRegister(pub(crate) SparseKet);
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl SparseRegister {
+    /// Returns the basis states with a nonzero amplitude in ascending order
+    pub fn indices(&self) -> Vec<usize> {
+        self.0.indices()
+    }
+
+    /// Returns the amplitude of a basis state, which is zero if it is not stored
+    pub fn state(&self, idx: usize) -> Complex {
+        self.0.state(idx)
+    }
+
+    /// Returns the number of nonzero amplitudes
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the probabilities of each qbit
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.0.probabilities()
+    }
+
+    /// Returns the probability of a qbit
+    pub fn probability(&self, bit: usize) -> Result<f64> {
+        self.0.probability(bit)
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone)]
+pub struct StabilizerRegister(pub(crate) Tableau);
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl StabilizerRegister {
+    /// Returns the probabilities of each qbit
+    pub fn probabilities(&self) -> Vec<f64> {
+        (0..self.0.size()).filter_map(|bit| self.0.probability(bit).ok()).collect()
+    }
+
+    /// Returns the probability of a qbit, which is either 0, 0.5 or 1
+    pub fn probability(&self, bit: usize) -> Result<f64> {
+        self.0.probability(bit)
+    }
+
+    /// Returns the generators of the stabilizer group as signed pauli strings, starting with qbit 0
+    pub fn stabilizers(&self) -> Vec<String> {
+        self.0.stabilizers()
+    }
 }
 
 #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
 #[derive(Clone)]
 pub struct ClassicalRegister(pub(crate) Register);
 
+impl ClassicalRegister {
+    /// Returns the bits from start as an integer, bit i of the range becomes bit i of the value.
+    fn checked_value(&self, start: usize, len: usize) -> Result<usize> {
+        if len > usize::BITS as usize {
+            return Err(QuantumError(ErrorKind::SizeLimitExceeded, format!(
+                "Expecting at most {} bits for an integer but got {}", usize::BITS, len
+            )))
+        }
+        let bits = self.0.bits();
+        if start + len > bits.len() {
+            return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                "Expecting bits below {} but the range ends at {}", bits.len(), start + len
+            )))
+        }
+
+        Ok(bits[start..start + len].iter()
+            .enumerate()
+            .fold(0, |value, (i, bit)| if *bit { value | (0x1 << i) } else { value }))
+    }
+
+    fn checked_creg(&self, name: &str) -> Result<usize> {
+        let (_, start, size) = self.0.names().iter()
+            .find(|(creg, _, _)| creg == name)
+            .ok_or_else(|| QuantumError(ErrorKind::InvalidArgument, format!("Expecting a creg named {}", name)))?;
+
+        self.checked_value(*start, *size)
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl ClassicalRegister {
+    /// Returns the names of all named cregs in the order of their allocation
+    pub fn cregs(&self) -> Vec<String> {
+        self.0.names().iter().map(|(name, _, _)| name.clone()).collect()
+    }
+}
+
 #[cfg(feature = "wasm-bindgen")]
 #[wasm_bindgen]
 impl ClassicalRegister {
     pub fn state(&self) -> Vec<js_sys::Boolean> {
         self.0.bits().iter().map(|v| js_sys::Boolean::from(*v)).collect()
     }
+
+    /// Returns the named creg as an integer, its first bit is the least significant one
+    pub fn creg(&self, name: &str) -> core::result::Result<usize, JsValue> {
+        Ok(self.checked_creg(name)?)
+    }
+
+    /// Returns the bits from start as an integer, the bit at start is the least significant one
+    pub fn value(&self, start: usize, len: usize) -> core::result::Result<usize, JsValue> {
+        Ok(self.checked_value(start, len)?)
+    }
 }
 
 #[cfg(not(feature = "wasm-bindgen"))]
@@ -98,6 +945,17 @@ impl ClassicalRegister {
     pub fn state(&self) -> &Vec<bool> {
         self.0.bits()
     }
+
+    /// Returns the named creg as an integer, its first bit is the least significant one
+    pub fn creg(&self, name: &str) -> Result<usize> {
+        self.checked_creg(name)
+    }
+
+    /// Returns the bits from start as an integer, the bit at start is the least significant one.
+    /// Fails if the range exceeds the register or an integer.
+    pub fn value(&self, start: usize, len: usize) -> Result<usize> {
+        self.checked_value(start, len)
+    }
 }
 
 impl Display for QuantumRegister {
@@ -106,6 +964,28 @@ impl Display for QuantumRegister {
     }
 }
 
+impl Display for SparseRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for idx in self.0.indices() {
+            let s = format!("{:#0width$b}", idx, width = self.0.size() + 2);
+            let (_, s) = s.split_at(2);
+            writeln!(f, "|{}> = {}", s, self.0.state(idx))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for StabilizerRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for stabilizer in self.0.stabilizers() {
+            writeln!(f, "{}", stabilizer)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Display for ClassicalRegister {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
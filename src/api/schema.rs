@@ -0,0 +1,609 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tinyvec::TinyVec;
+use crate::api::{ClassicalRegister, QuantumRegister};
+use crate::api::derive::Algorithm;
+use crate::api::Comparison;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::classical::{BitwiseOperator, ClassicalOperation, Condition};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+use crate::runtime::dynamic_sized::unitary_operator::DynamicSizedUnitaryOperator;
+use crate::runtime::ket::{seed, Ket};
+use crate::runtime::non_unitary::measurement::{Measurement, MeasurementBasis};
+use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+use crate::runtime::non_unitary::povm::Povm;
+use crate::runtime::non_unitary::reset::Reset;
+use crate::runtime::register::Register;
+use crate::toolbox::conditional::Conditional;
+use crate::toolbox::named_gate::named_gate;
+use crate::toolbox::Tool;
+
+/// The version written to every serialized algorithm and register, it is increased on incompatible changes of the schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The largest deviation of `U† U` from the identity, which is accepted as unitary.
+const EPSILON: f64 = 1e-8;
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+enum BasisSchema {
+    X, Y, Z
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+enum OperatorSchema {
+    And, Or, Xor, Not
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+enum ComparisonSchema {
+    Equal, NotEqual, Less, LessEqual, Greater, GreaterEqual
+}
+
+/// A single gate of a step. The qbits of a named gate start with its controls, the first qbit of a matrix
+/// is the most significant bit of the row major matrix index.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum GateSchema {
+    Gate {
+        name: String,
+        qbits: Vec<usize>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        params: Vec<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        condition: Option<usize>,
+    },
+    Custom {
+        qbits: Vec<usize>,
+        matrix: Vec<Complex>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        condition: Option<usize>,
+    },
+    Measurement {
+        qbit: usize,
+        basis: BasisSchema,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bit: Option<usize>,
+    },
+    PauliMeasurement {
+        qbits: Vec<usize>,
+        paulis: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bit: Option<usize>,
+    },
+    Povm {
+        qbits: Vec<usize>,
+        kraus: Vec<Vec<Complex>>,
+        bits: Vec<usize>,
+    },
+    Reset {
+        qbit: usize,
+        state: bool,
+    },
+    Classical {
+        operator: OperatorSchema,
+        bits: Vec<usize>,
+        target: usize,
+    },
+    /// A gate, which is applied if the integer given by the bits, the first one least significant, compares to the value.
+    Conditional {
+        bits: Vec<usize>,
+        comparison: ComparisonSchema,
+        value: usize,
+        gate: Box<GateSchema>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct SnapshotSchema {
+    name: String,
+    step: usize,
+}
+
+/// A named range of consecutive qbits or bits.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct RegisterSchema {
+    name: String,
+    start: usize,
+    size: usize,
+}
+
+impl RegisterSchema {
+    fn from_registers(registers: &[(String, usize, usize)]) -> Vec<RegisterSchema> {
+        registers.iter()
+            .map(|(name, start, size)| RegisterSchema { name: name.clone(), start: *start, size: *size })
+            .collect()
+    }
+
+    fn into_registers(registers: Vec<RegisterSchema>) -> Vec<(String, usize, usize)> {
+        registers.into_iter().map(|register| (register.name, register.start, register.size)).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AlgorithmSchema {
+    version: u32,
+    qbits: usize,
+    bits: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    initial_state: Option<Vec<Complex>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    initial_bits: Option<Vec<bool>>,
+    steps: Vec<Vec<GateSchema>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    snapshots: Vec<SnapshotSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    qregs: Vec<RegisterSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cregs: Vec<RegisterSchema>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct QuantumRegisterSchema {
+    version: u32,
+    amplitudes: Vec<Complex>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct ClassicalRegisterSchema {
+    version: u32,
+    bits: Vec<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cregs: Vec<RegisterSchema>,
+}
+
+fn validate_version(version: u32) -> Result<()> {
+    if version != SCHEMA_VERSION {
+        return Err(QuantumError(
+            ErrorKind::Unsupported,
+            format!("Expecting schema version {} but got {}", SCHEMA_VERSION, version)
+        ))
+    }
+
+    Ok(())
+}
+
+fn row_major(matrix: &DynamicSizedMatrix) -> Vec<Complex> {
+    let width = matrix.width();
+
+    (0..width * width).map(|idx| matrix.get(idx / width, idx % width)).collect()
+}
+
+/// Creates the matrix of a gate on the qbits and checks that it is unitary.
+fn unitary_matrix(qbits: &[usize], matrix: Vec<Complex>) -> Result<DynamicSizedMatrix> {
+    let matrix = DynamicSizedMatrix::with_qbits(matrix, qbits.len())?;
+    let width = matrix.width();
+    for y in 0..width {
+        for x in 0..width {
+            let sum = (0..width)
+                .map(|i| matrix.get(i, y).conjugate() * matrix.get(i, x))
+                .fold(Complex::zero(), |sum, v| sum + v);
+            let expected = Complex::new(if x == y { 1.0 } else { 0.0 }, 0.0);
+            if (sum - expected).abs() > EPSILON {
+                return Err(QuantumError(
+                    ErrorKind::NonUnitaryMatrix,
+                    String::from("Expecting a unitary matrix, but U† U is not the identity")
+                ))
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+impl GateSchema {
+    /// Returns the schema of the tool, or none if the tool does nothing.
+    fn from_tool(tool: &Tool) -> Option<GateSchema> {
+        let basis = |basis: MeasurementBasis| match basis {
+            MeasurementBasis::X => BasisSchema::X,
+            MeasurementBasis::Y => BasisSchema::Y,
+            MeasurementBasis::Z => BasisSchema::Z,
+        };
+
+        match tool {
+            Tool::Measurement(o) => Some(GateSchema::Measurement {
+                qbit: o.wire(),
+                basis: basis(o.basis()),
+                bit: o.creg_bit(),
+            }),
+            Tool::PauliMeasurement(o) => Some(GateSchema::PauliMeasurement {
+                qbits: o.paulis().iter().map(|(wire, _)| *wire).collect(),
+                paulis: o.paulis().iter().map(|(_, pauli)| match pauli {
+                    MeasurementBasis::X => 'X',
+                    MeasurementBasis::Y => 'Y',
+                    MeasurementBasis::Z => 'Z',
+                }).collect(),
+                bit: o.creg_bit(),
+            }),
+            Tool::Povm(o) => Some(GateSchema::Povm {
+                qbits: Vec::from(o.wires()),
+                kraus: o.kraus().iter().map(row_major).collect(),
+                bits: Vec::from(o.creg_bits()),
+            }),
+            Tool::Reset(o) => Some(GateSchema::Reset {
+                qbit: o.wire(),
+                state: o.state(),
+            }),
+            Tool::Custom(o) => Some(GateSchema::Custom {
+                qbits: Vec::from(o.wires()),
+                matrix: row_major(o.matrix()),
+                condition: o.classical_control(),
+            }),
+            Tool::Classical(o) => Some(GateSchema::Classical {
+                operator: match o.operator() {
+                    BitwiseOperator::And => OperatorSchema::And,
+                    BitwiseOperator::Or => OperatorSchema::Or,
+                    BitwiseOperator::Xor => OperatorSchema::Xor,
+                    BitwiseOperator::Not => OperatorSchema::Not,
+                },
+                bits: Vec::from(o.sources()),
+                target: o.target(),
+            }),
+            Tool::Conditional(o) => Some(GateSchema::Conditional {
+                bits: Vec::from(o.condition().bits()),
+                comparison: match o.condition().comparison() {
+                    Comparison::Equal => ComparisonSchema::Equal,
+                    Comparison::NotEqual => ComparisonSchema::NotEqual,
+                    Comparison::Less => ComparisonSchema::Less,
+                    Comparison::LessEqual => ComparisonSchema::LessEqual,
+                    Comparison::Greater => ComparisonSchema::Greater,
+                    Comparison::GreaterEqual => ComparisonSchema::GreaterEqual,
+                },
+                value: o.condition().value(),
+                gate: Box::new(GateSchema::from_tool(o.tool())?),
+            }),
+            Tool::None => None,
+            tool => Some(GateSchema::Gate {
+                name: String::from(tool.name()),
+                qbits: tool.wires(),
+                params: tool.parameters(),
+                condition: tool.unitary().and_then(|o| o.classical_control()),
+            }),
+        }
+    }
+
+    fn into_tool(self) -> Result<Tool> {
+        match self {
+            GateSchema::Gate { name, qbits, params, condition } => named_gate(&name, &qbits, &params, condition),
+            GateSchema::Custom { qbits, matrix, condition } => {
+                let matrix = unitary_matrix(&qbits, matrix)?;
+
+                Ok(Tool::Custom(DynamicSizedUnitaryOperator::new(matrix, qbits, condition)?))
+            }
+            GateSchema::Measurement { qbit, basis, bit } => {
+                let basis = match basis {
+                    BasisSchema::X => MeasurementBasis::X,
+                    BasisSchema::Y => MeasurementBasis::Y,
+                    BasisSchema::Z => MeasurementBasis::Z,
+                };
+
+                Ok(Measurement::new(qbit, Some(basis), bit).into())
+            }
+            GateSchema::PauliMeasurement { qbits, paulis, bit } => Ok(PauliMeasurement::new(&qbits, &paulis, bit)?.into()),
+            GateSchema::Povm { qbits, kraus, bits } => {
+                let kraus = kraus.into_iter()
+                    .map(|operator| DynamicSizedMatrix::with_qbits(operator, qbits.len()))
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Povm::new(&qbits, kraus, &bits)?.into())
+            }
+            GateSchema::Reset { qbit, state } => Ok(Reset::new(qbit, state).into()),
+            GateSchema::Classical { operator, bits, target } => {
+                let operator = match operator {
+                    OperatorSchema::And => BitwiseOperator::And,
+                    OperatorSchema::Or => BitwiseOperator::Or,
+                    OperatorSchema::Xor => BitwiseOperator::Xor,
+                    OperatorSchema::Not => BitwiseOperator::Not,
+                };
+
+                Ok(ClassicalOperation::new(operator, &bits, target)?.into())
+            }
+            GateSchema::Conditional { bits, comparison, value, gate } => {
+                let comparison = match comparison {
+                    ComparisonSchema::Equal => Comparison::Equal,
+                    ComparisonSchema::NotEqual => Comparison::NotEqual,
+                    ComparisonSchema::Less => Comparison::Less,
+                    ComparisonSchema::LessEqual => Comparison::LessEqual,
+                    ComparisonSchema::Greater => Comparison::Greater,
+                    ComparisonSchema::GreaterEqual => Comparison::GreaterEqual,
+                };
+
+                Ok(Conditional::new(Condition::new(&bits, comparison, value)?, gate.into_tool()?)?.into())
+            }
+        }
+    }
+}
+
+impl AlgorithmSchema {
+    /// Describes the remaining steps of the algorithm, a started algorithm begins with its current state.
+    fn from_algorithm(algorithm: &Algorithm) -> AlgorithmSchema {
+        let pos = algorithm.pos;
+        let bits = algorithm.reg.bits();
+
+        AlgorithmSchema {
+            version: SCHEMA_VERSION,
+            qbits: algorithm.size,
+            bits: bits.len(),
+            initial_state: algorithm.ket.as_ref()
+                .map(|ket| ket.state().iter().map(|amplitude| Complex::from(*amplitude)).collect()),
+            initial_bits: if bits.iter().any(|bit| *bit) { Some(bits.clone()) } else { None },
+            steps: algorithm.tools[pos..].iter()
+                .map(|step| step.iter().filter_map(GateSchema::from_tool).collect())
+                .collect(),
+            snapshots: algorithm.snapshots.iter()
+                .filter(|(_, step)| *step >= pos)
+                .map(|(name, step)| SnapshotSchema { name: name.clone(), step: step - pos })
+                .collect(),
+            qregs: RegisterSchema::from_registers(&algorithm.qregs),
+            cregs: RegisterSchema::from_registers(algorithm.reg.names()),
+        }
+    }
+
+    fn into_algorithm(self) -> Result<Algorithm> {
+        validate_version(self.version)?;
+        let tools = self.steps.into_iter()
+            .map(|step| step.into_iter().map(GateSchema::into_tool).collect::<Result<TinyVec<[Tool; 1]>>>())
+            .collect::<Result<Vec<_>>>()?;
+        let mut snapshots = Vec::with_capacity(self.snapshots.len());
+        for snapshot in self.snapshots {
+            if snapshot.step > tools.len() {
+                return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                    "Expecting snapshot {} at most at step {} but was {}", snapshot.name, tools.len(), snapshot.step
+                )))
+            }
+            snapshots.push((snapshot.name, snapshot.step));
+        }
+
+        let mut algorithm = Algorithm::from_parts(self.qbits, self.bits, tools, snapshots)?;
+        if let Some(amplitudes) = self.initial_state {
+            algorithm = algorithm.with_amplitudes(&amplitudes)?;
+        }
+        if let Some(bits) = self.initial_bits {
+            if bits.len() != self.bits {
+                return Err(QuantumError(
+                    ErrorKind::InvalidArgument,
+                    format!("Expecting {} initial bits but got {}", self.bits, bits.len())
+                ))
+            }
+            algorithm.reg = Register::from_bits(bits);
+        }
+
+        algorithm.with_registers(RegisterSchema::into_registers(self.qregs), RegisterSchema::into_registers(self.cregs))
+    }
+}
+
+impl Serialize for Algorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        AlgorithmSchema::from_algorithm(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Algorithm, D::Error> {
+        AlgorithmSchema::deserialize(deserializer)?
+            .into_algorithm()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for QuantumRegister {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        QuantumRegisterSchema {
+            version: SCHEMA_VERSION,
+            amplitudes: self.0.state().iter().map(|amplitude| Complex::from(*amplitude)).collect(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for QuantumRegister {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<QuantumRegister, D::Error> {
+        let schema = QuantumRegisterSchema::deserialize(deserializer)?;
+        validate_version(schema.version)
+            .and_then(|_| Ket::from_amplitudes(&schema.amplitudes, seed()))
+            .map(QuantumRegister)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for ClassicalRegister {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        ClassicalRegisterSchema {
+            version: SCHEMA_VERSION,
+            bits: self.0.bits().clone(),
+            cregs: RegisterSchema::from_registers(self.0.names()),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassicalRegister {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<ClassicalRegister, D::Error> {
+        let schema = ClassicalRegisterSchema::deserialize(deserializer)?;
+        validate_version(schema.version).map_err(serde::de::Error::custom)?;
+        let size = schema.bits.len();
+        let cregs = RegisterSchema::into_registers(schema.cregs);
+        if let Some((name, start, len)) = cregs.iter().find(|(_, start, len)| start + len > size) {
+            return Err(serde::de::Error::custom(format!(
+                "Expecting bits below {} in {} but the register ends at {}", size, name, start + len
+            )))
+        }
+
+        Ok(ClassicalRegister(Register::from_bits(schema.bits).with_names(cregs)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use crate::api::derive::*;
+    use crate::api::{ClassicalRegister, Comparison, QuantumRegister};
+    use crate::complex::{tolerance, Complex};
+
+    fn algorithm() -> Algorithm {
+        Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+            let c_b = gate_builder.bit();
+
+            hadamard(a);
+            rotation_u(0.5, 0.25, 0.125, b);
+            controlled_rotation_swap(1.5, a, b, c);
+            gate_builder.snapshot("entangled");
+            measurement_pauli(&[a, c], "XZ", c_a).unwrap();
+            reset_to(b, true);
+            measurement_x(c, c_b);
+
+            gate_builder
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_algorithm_round_trip() {
+        let json = serde_json::to_string(&algorithm()).unwrap();
+        assert!(json.starts_with("{\"version\":1,\"qbits\":3,\"bits\":2,\"steps\":[[{\"type\":\"gate\",\"name\":\"Hadamard\",\"qbits\":[0]}]"));
+        assert!(json.contains("{\"type\":\"gate\",\"name\":\"RotationU\",\"qbits\":[1],\"params\":[0.5,0.25,0.125]}"));
+        assert!(json.contains("{\"type\":\"gate\",\"name\":\"ControlledRotationSwap\",\"qbits\":[0,1,2],\"params\":[1.5]}"));
+        assert!(json.contains("{\"type\":\"pauliMeasurement\",\"qbits\":[0,2],\"paulis\":\"XZ\",\"bit\":0}"));
+        assert!(json.contains("{\"type\":\"reset\",\"qbit\":1,\"state\":true}"));
+        assert!(json.contains("\"snapshots\":[{\"name\":\"entangled\",\"step\":3}]"));
+
+        let algorithm: Algorithm = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&algorithm).unwrap(), json);
+        let (_, _, snapshots) = algorithm.run_with_snapshots().unwrap();
+        assert!(snapshots.get("entangled").is_some());
+    }
+
+    #[test]
+    fn test_started_algorithm() {
+        let mut algorithm = algorithm();
+        algorithm.step().unwrap();
+        let json = serde_json::to_string(&algorithm).unwrap();
+        assert!(json.contains("\"initialState\":["));
+        assert!(json.contains("\"snapshots\":[{\"name\":\"entangled\",\"step\":2}]"));
+
+        // The restored algorithm continues with the rotation on top of the hadamard
+        let mut restored: Algorithm = serde_json::from_str(&json).unwrap();
+        let (expected, _) = algorithm.step().unwrap().unwrap();
+        let (actual, _) = restored.step().unwrap().unwrap();
+        assert_approx_eq!(f64, actual.fidelity(&expected).unwrap(), 1.0, epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_custom_gate() {
+        let json = "{\"version\":1,\"qbits\":1,\"bits\":0,\"steps\":[[{\"type\":\"custom\",\"qbits\":[0],\"matrix\":[\
+            {\"re\":0.0,\"im\":0.0},{\"re\":1.0,\"im\":0.0},{\"re\":1.0,\"im\":0.0},{\"re\":0.0,\"im\":0.0}]}]]}";
+        let algorithm: Algorithm = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&algorithm).unwrap(), json);
+        let (quantum_register, _) = algorithm.run().unwrap();
+        assert_approx_eq!(f64, quantum_register.probability(0).unwrap(), 1.0, epsilon = tolerance(0.000001));
+
+        let json = json.replacen("0.0", "1.0", 1);
+        assert!(serde_json::from_str::<Algorithm>(&json).unwrap_err().to_string().contains("unitary"));
+    }
+
+    #[test]
+    fn test_povm_matrix_size() {
+        let entry = |re: f64| format!("{{\"re\":{:?},\"im\":0.0}}", re);
+        let povm = |kraus: &[f64]| format!(
+            "{{\"version\":1,\"qbits\":1,\"bits\":1,\"steps\":[[{{\"type\":\"povm\",\"qbits\":[0],\"kraus\":[[{}]],\"bits\":[0]}}]]}}",
+            kraus.iter().map(|re| entry(*re)).collect::<Vec<_>>().join(",")
+        );
+        assert!(serde_json::from_str::<Algorithm>(&povm(&[1.0, 0.0, 0.0, 1.0])).is_ok());
+        let error = serde_json::from_str::<Algorithm>(&povm(&[1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0])).unwrap_err();
+        assert!(error.to_string().contains("matrix of 1 qbits"));
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let json = "{\"version\":2,\"qbits\":1,\"bits\":0,\"steps\":[]}";
+        assert!(serde_json::from_str::<Algorithm>(json).unwrap_err().to_string().contains("version"));
+        let json = "{\"version\":1,\"qbits\":1,\"bits\":0,\"steps\":[[{\"type\":\"gate\",\"name\":\"Toffoli\",\"qbits\":[0]}]]}";
+        assert!(serde_json::from_str::<Algorithm>(json).unwrap_err().to_string().contains("Toffoli"));
+        let json = "{\"version\":1,\"qbits\":1,\"bits\":0,\"steps\":[[{\"type\":\"gate\",\"name\":\"PauliX\",\"qbits\":[1]}]]}";
+        assert!(serde_json::from_str::<Algorithm>(json).is_err());
+        let json = "{\"version\":1,\"qbits\":1,\"bits\":0,\"steps\":[],\"snapshots\":[{\"name\":\"end\",\"step\":1}]}";
+        assert!(serde_json::from_str::<Algorithm>(json).is_err());
+    }
+
+    #[test]
+    fn test_registers() {
+        let (quantum_register, classical_register) = algorithm().run().unwrap();
+        let json = serde_json::to_string(&quantum_register).unwrap();
+        assert!(json.starts_with("{\"version\":1,\"amplitudes\":[{\"re\":"));
+        let restored: QuantumRegister = serde_json::from_str(&json).unwrap();
+        assert_approx_eq!(f64, restored.fidelity(&quantum_register).unwrap(), 1.0, epsilon = tolerance(0.000001));
+
+        let json = serde_json::to_string(&classical_register).unwrap();
+        let restored: ClassicalRegister = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.state(), classical_register.state());
+
+        let amplitudes = [Complex::new(1.0, 0.0), Complex::new(1.0, 0.0)];
+        let json = String::from("{\"version\":1,\"amplitudes\":") + &serde_json::to_string(&amplitudes).unwrap() + "}";
+        assert!(serde_json::from_str::<QuantumRegister>(&json).is_err());
+        assert!(serde_json::from_str::<ClassicalRegister>("{\"version\":3,\"bits\":[]}").is_err());
+    }
+
+    #[test]
+    fn test_classical_instructions() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let a = gate_builder.qbit();
+            let b = gate_builder.qbit();
+            let c_a = gate_builder.bit();
+            let c_b = gate_builder.bit();
+
+            pauli_x(a);
+            measurement_z(a, c_a);
+            classical_not(c_a, c_b);
+            condition(&[c_a, c_b], Comparison::GreaterEqual, 1, || rotation_x(0.5, b)).unwrap();
+
+            gate_builder
+        }).unwrap();
+        let json = serde_json::to_string(&algorithm).unwrap();
+        assert!(json.contains("{\"type\":\"classical\",\"operator\":\"not\",\"bits\":[0],\"target\":1}"));
+        assert!(json.contains("{\"type\":\"conditional\",\"bits\":[0,1],\"comparison\":\"greaterEqual\",\"value\":1,\
+            \"gate\":{\"type\":\"gate\",\"name\":\"RotationX\",\"qbits\":[1],\"params\":[0.5]}}"));
+
+        let restored: Algorithm = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let json = "{\"version\":1,\"qbits\":1,\"bits\":1,\"steps\":[[{\"type\":\"conditional\",\"bits\":[0],\
+            \"comparison\":\"equal\",\"value\":1,\"gate\":{\"type\":\"reset\",\"qbit\":0,\"state\":true}}]]}";
+        assert!(serde_json::from_str::<Algorithm>(json).unwrap_err().to_string().contains("Reset"));
+    }
+
+    #[test]
+    fn test_named_registers() {
+        let algorithm = Algorithm::new(|gate_builder| {
+            let data = gate_builder.qreg("data", 2);
+            let result = gate_builder.creg("result", 2);
+
+            pauli_x(data[1]);
+            measurement_z(data[0], result[0]);
+            measurement_z(data[1], result[1]);
+
+            gate_builder
+        }).unwrap();
+        let json = serde_json::to_string(&algorithm).unwrap();
+        assert!(json.ends_with("\"qregs\":[{\"name\":\"data\",\"start\":0,\"size\":2}],\
+            \"cregs\":[{\"name\":\"result\",\"start\":0,\"size\":2}]}"));
+
+        let algorithm: Algorithm = serde_json::from_str(&json).unwrap();
+        let (_, classical_register) = algorithm.run().unwrap();
+        let json = serde_json::to_string(&classical_register).unwrap();
+        let restored: ClassicalRegister = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.creg("result").unwrap(), 2);
+
+        let json = "{\"version\":1,\"bits\":[true],\"cregs\":[{\"name\":\"result\",\"start\":0,\"size\":2}]}";
+        assert!(serde_json::from_str::<ClassicalRegister>(json).is_err());
+    }
+}
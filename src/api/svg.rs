@@ -0,0 +1,282 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use core::fmt::Write;
+use libm::{atan2, cos, sin, sqrt};
+use crate::complex::Complex;
+
+#[cfg(feature = "wasm-pack")]
+use tsify::Tsify;
+
+/// The options of the generated svg images.
+#[cfg_attr(feature = "wasm-pack", derive(Tsify))]
+#[cfg_attr(feature = "wasm-pack", tsify(from_wasm_abi, into_wasm_abi))]
+#[cfg_attr(feature = "wasm-pack", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "wasm-pack", serde(default))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The width of the image in pixels
+    pub width: f64,
+    /// The height of the image in pixels
+    pub height: f64,
+    /// The css colour of the background
+    pub background: String,
+    /// The css colour of axes, outlines and labels
+    pub foreground: String,
+    /// Colours each amplitude by its phase, otherwise `color` is used
+    pub phase_colors: bool,
+    /// The css colour of bars and points, if they are not coloured by their phase
+    pub color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            width: 640.0,
+            height: 360.0,
+            background: String::from("white"),
+            foreground: String::from("black"),
+            phase_colors: true,
+            color: String::from("#1f77b4"),
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Returns the fill colour of an amplitude, darker shades are used for the sides of the city plot.
+    fn fill(&self, amplitude: Complex, shade: usize) -> String {
+        if self.phase_colors {
+            let phase = atan2(amplitude.im(), amplitude.re());
+            let hue = (phase / PI * 180.0 + 360.0) % 360.0;
+
+            format!("hsl({:.0},70%,{}%)", hue, 55 - 10 * shade)
+        } else {
+            escape(&self.color)
+        }
+    }
+}
+
+/// Escapes the user supplied value, such that it can not leave the attribute it is written to.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn header(options: &SvgOptions) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" viewBox=\"0 0 {w:.0} {h:.0}\">\
+        <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        escape(&options.background),
+        w = options.width,
+        h = options.height,
+    )
+}
+
+fn label(state: usize, size: usize) -> String {
+    let s = format!("{:#0width$b}", state, width = size + 2);
+    let (_, s) = s.split_at(2);
+
+    format!("|{}&#x27E9;", s)
+}
+
+/// Draws the probability of every basis state as bar, coloured by the phase of its amplitude.
+pub(crate) fn bar_chart(states: &[Complex], size: usize, options: &SvgOptions) -> String {
+    let mut svg = header(options);
+    let (left, right, top, bottom) = (40.0, options.width - 10.0, 10.0, options.height - 30.0);
+    let max = states.iter().map(|state| state.amplitude()).fold(0.0, f64::max);
+    let width = (right - left) / states.len() as f64;
+    let font = (width * 0.8).min(12.0);
+
+    let _ = write!(
+        svg,
+        "<g stroke=\"{c}\"><line x1=\"{left}\" y1=\"{bottom:.2}\" x2=\"{right:.2}\" y2=\"{bottom:.2}\"/>\
+        <line x1=\"{left}\" y1=\"{top}\" x2=\"{left}\" y2=\"{bottom:.2}\"/></g>\
+        <text x=\"{x}\" y=\"{y}\" font-size=\"10\" text-anchor=\"end\" fill=\"{c}\">{max:.3}</text>",
+        c = escape(&options.foreground),
+        x = left - 4.0,
+        y = top + 8.0,
+    );
+    for (idx, state) in states.iter().enumerate() {
+        let x = left + idx as f64 * width;
+        if max > 0.0 && state.amplitude() > 0.0 {
+            let height = state.amplitude() / max * (bottom - top);
+            let _ = write!(
+                svg,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"/>",
+                x + width * 0.1, bottom - height, width * 0.8, height, options.fill(*state, 0),
+            );
+        }
+        if states.len() <= 32 {
+            let _ = write!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{:.1}\" text-anchor=\"middle\" fill=\"{}\">{}</text>",
+                x + width / 2.0, bottom + 16.0, font, escape(&options.foreground), label(idx, size),
+            );
+        }
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+/// Draws every basis state as point on a sphere, the latitude is given by the number of ones in the state.
+pub(crate) fn q_sphere(states: &[Complex], size: usize, options: &SvgOptions) -> String {
+    let mut svg = header(options);
+    let (cx, cy) = (options.width / 2.0, options.height / 2.0);
+    let radius = 0.4 * options.width.min(options.height);
+    let tilt: f64 = 0.35;
+
+    let _ = write!(
+        svg,
+        "<g fill=\"none\" stroke=\"{}\"><circle cx=\"{cx:.2}\" cy=\"{cy:.2}\" r=\"{radius:.2}\"/>\
+        <ellipse cx=\"{cx:.2}\" cy=\"{cy:.2}\" rx=\"{radius:.2}\" ry=\"{:.2}\" stroke-dasharray=\"4 4\"/></g>",
+        escape(&options.foreground), radius * sin(tilt),
+    );
+
+    // Points are placed evenly on the circle of their latitude, the back of the sphere is drawn first
+    let mut points = Vec::new();
+    for weight in 0..=size {
+        let members = (0..states.len()).filter(|state| state.count_ones() as usize == weight).collect::<Vec<_>>();
+        let theta = PI * weight as f64 / size as f64;
+        for (j, state) in members.iter().enumerate() {
+            let phi = 2.0 * PI * j as f64 / members.len() as f64;
+            let (x, y, z) = (sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            points.push((y, *state, cx + radius * x, cy - radius * (z * cos(tilt) + y * sin(tilt))));
+        }
+    }
+    points.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (_, state, x, y) in points {
+        let amplitude = states[state];
+        if amplitude.amplitude() <= 1e-10 {
+            continue
+        }
+        let fill = options.fill(amplitude, 0);
+        let _ = write!(
+            svg,
+            "<line x1=\"{cx:.2}\" y1=\"{cy:.2}\" x2=\"{x:.2}\" y2=\"{y:.2}\" stroke=\"{fill}\"/>\
+            <circle cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"{:.2}\" fill=\"{fill}\"/>",
+            3.0 + 0.05 * radius * sqrt(amplitude.amplitude()),
+        );
+        if size <= 5 {
+            let _ = write!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"11\" fill=\"{}\">{}</text>",
+                x + 8.0, y - 8.0, escape(&options.foreground), label(state, size),
+            );
+        }
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+/// Draws the magnitude of every entry of the row major `dim x dim` density matrix as isometric bar.
+pub(crate) fn city_plot(density: &[Complex], dim: usize, options: &SvgOptions) -> String {
+    let mut svg = header(options);
+    let (c30, s30) = (cos(PI / 6.0), sin(PI / 6.0));
+    let max_height = 0.4 * options.height;
+    let cell = ((options.width - 20.0) / (2.0 * dim as f64 * c30))
+        .min((options.height - 20.0 - max_height) / dim as f64);
+    let (cx, top) = (options.width / 2.0, 10.0 + max_height);
+    let corner = |x: usize, y: usize, h: f64| (
+        cx + (x as f64 - y as f64) * cell * c30,
+        top + (x + y) as f64 * cell * s30 - h,
+    );
+    let polygon = |svg: &mut String, points: [(f64, f64); 4], fill: &str, opacity: f64| {
+        let points = points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect::<Vec<_>>().join(" ");
+        let _ = write!(svg, "<polygon points=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>", points, fill, opacity);
+    };
+
+    let floor = [corner(0, 0, 0.0), corner(dim, 0, 0.0), corner(dim, dim, 0.0), corner(0, dim, 0.0)];
+    let _ = write!(
+        svg,
+        "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\"/>",
+        floor.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect::<Vec<_>>().join(" "),
+        escape(&options.foreground),
+    );
+
+    let max = density.iter().map(|v| v.abs()).fold(0.0, f64::max);
+    // Bars further back have a smaller sum of their coordinates and are drawn first
+    for diagonal in 0..2 * dim - 1 {
+        for y in 0..dim {
+            if diagonal < y || diagonal - y >= dim {
+                continue
+            }
+            let x = diagonal - y;
+            let value = density[y * dim + x];
+            if max <= 0.0 || value.abs() <= 1e-10 {
+                continue
+            }
+            let h = value.abs() / max * max_height;
+            let opacity = if options.phase_colors { 1.0 } else { 0.9 };
+            polygon(&mut svg, [corner(x, y + 1, 0.0), corner(x + 1, y + 1, 0.0), corner(x + 1, y + 1, h), corner(x, y + 1, h)], &options.fill(value, 1), opacity * 0.85);
+            polygon(&mut svg, [corner(x + 1, y, 0.0), corner(x + 1, y + 1, 0.0), corner(x + 1, y + 1, h), corner(x + 1, y, h)], &options.fill(value, 2), opacity * 0.7);
+            polygon(&mut svg, [corner(x, y, h), corner(x + 1, y, h), corner(x + 1, y + 1, h), corner(x, y + 1, h)], &options.fill(value, 0), opacity);
+        }
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use crate::api::svg::{bar_chart, city_plot, q_sphere, SvgOptions};
+    use crate::complex::Complex;
+
+    #[test]
+    fn test_svg_documents() {
+        let states = Vec::from([
+            Complex::new(FRAC_1_SQRT_2, 0.0), Complex::zero(), Complex::zero(), Complex::new(0.0, FRAC_1_SQRT_2),
+        ]);
+        let options = SvgOptions::default();
+
+        let svg = bar_chart(&states, 2, &options);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("hsl(90,70%,55%)"));
+
+        let svg = q_sphere(&states, 2, &options);
+        assert_eq!(svg.matches("<circle").count(), 3);
+
+        let density = Vec::from([
+            Complex::new(0.5, 0.0), Complex::new(0.0, -0.5),
+            Complex::new(0.0, 0.5), Complex::new(0.5, 0.0),
+        ]);
+        let svg = city_plot(&density, 2, &SvgOptions { phase_colors: false, ..SvgOptions::default() });
+        assert_eq!(svg.matches("<polygon").count(), 13);
+        assert!(svg.contains("#1f77b4"));
+    }
+
+    #[test]
+    fn test_escaped_colours() {
+        let states = Vec::from([Complex::new(1.0, 0.0), Complex::zero()]);
+        let options = SvgOptions {
+            background: String::from("red\"/><script>alert(1)</script>"),
+            foreground: String::from("x' onload='alert(1)"),
+            phase_colors: false,
+            color: String::from("blue&\""),
+            ..SvgOptions::default()
+        };
+
+        let svg = bar_chart(&states, 1, &options);
+        assert!(!svg.contains("<script>") && !svg.contains("' onload='"));
+        assert!(svg.contains("fill=\"red&quot;/&gt;&lt;script&gt;alert(1)&lt;/script&gt;\""));
+        assert!(svg.contains("blue&amp;&quot;"));
+    }
+}
@@ -1,5 +1,5 @@
 use core::fmt::{Display, Formatter};
-use core::ops::{Add, Mul};
+use core::ops::{Add, Mul, Neg, Sub};
 use libm::sqrt;
 
 #[cfg(feature = "wasm-pack")]
@@ -7,7 +7,7 @@ use tsify::Tsify;
 
 #[cfg_attr(feature = "wasm-pack", derive(Tsify))]
 #[cfg_attr(feature = "wasm-pack", tsify(from_wasm_abi, into_wasm_abi))]
-#[cfg_attr(feature = "wasm-pack", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Complex {
     re: f64,
@@ -26,6 +26,27 @@ impl const Add<Complex> for Complex {
     }
 }
 
+impl const Sub<Complex> for Complex {
+    type Output = Complex;
+
+    #[inline(always)]
+    fn sub(self, rhs: Complex) -> Self::Output {
+        Complex {
+            re: self.re() - rhs.re(),
+            im: self.im() - rhs.im(),
+        }
+    }
+}
+
+impl const Neg for Complex {
+    type Output = Complex;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re(), -self.im())
+    }
+}
+
 impl const Mul<Complex> for Complex {
     type Output = Complex;
 
@@ -144,6 +165,89 @@ impl const Default for Complex {
     }
 }
 
+/// A single precision complex number, used to store the state vector with the `f32` feature.
+#[cfg_attr(feature = "wasm-pack", derive(Tsify))]
+#[cfg_attr(feature = "wasm-pack", tsify(from_wasm_abi, into_wasm_abi))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Complex32 {
+    re: f32,
+    im: f32,
+}
+
+impl Complex32 {
+    pub const fn new(re: f32, im: f32) -> Complex32 {
+        Complex32 {
+            re,
+            im,
+        }
+    }
+
+    pub const fn zero() -> Complex32 {
+        Complex32::new(0.0, 0.0)
+    }
+
+    #[inline(always)]
+    pub const fn re(&self) -> f32 {
+        self.re
+    }
+
+    #[inline(always)]
+    pub const fn im(&self) -> f32 {
+        self.im
+    }
+
+    #[inline(always)]
+    pub fn abs(&self) -> f64 {
+        sqrt(self.amplitude())
+    }
+
+    #[inline(always)]
+    pub const fn amplitude(&self) -> f64 {
+        self.re() as f64 * self.re() as f64 + self.im() as f64 * self.im() as f64
+    }
+}
+
+impl From<Complex> for Complex32 {
+    #[inline(always)]
+    fn from(value: Complex) -> Self {
+        Complex32::new(value.re() as f32, value.im() as f32)
+    }
+}
+
+impl From<Complex32> for Complex {
+    #[inline(always)]
+    fn from(value: Complex32) -> Self {
+        Complex::new(value.re() as f64, value.im() as f64)
+    }
+}
+
+impl Display for Complex32 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} + {}i", self.re(), self.im())
+    }
+}
+
+impl const Default for Complex32 {
+    fn default() -> Self {
+        Complex32::zero()
+    }
+}
+
+/// The type each amplitude of the state vector is stored as.
+/// Calculations are always carried out in double precision, with the `f32` feature the amplitudes
+/// are stored as [Complex32](Complex32), which halves the memory required by the state vector.
+#[cfg(not(feature = "f32"))]
+pub type Amplitude = Complex;
+#[cfg(feature = "f32")]
+pub type Amplitude = Complex32;
+
+/// Returns the epsilon of a test, which is raised to the precision of the stored [Amplitude](Amplitude)s with the `f32` feature.
+#[cfg(test)]
+pub(crate) fn tolerance(epsilon: f64) -> f64 {
+    if cfg!(feature = "f32") { epsilon.max(0.00001) } else { epsilon }
+}
+
 #[cfg(test)]
 mod test {
     use crate::complex::Complex;
@@ -156,4 +260,15 @@ mod test {
             self.re().approx_eq(other.re(), margin) && self.im().approx_eq(other.im(), margin)
         }
     }
+
+    #[test]
+    fn test_complex32_conversion() {
+        use crate::complex::Complex32;
+        use float_cmp::assert_approx_eq;
+
+        let c = Complex::new(0.5, -0.25);
+        let c32 = Complex32::from(c);
+        assert_approx_eq!(f64, c.amplitude(), c32.amplitude(), epsilon = 0.0000001);
+        assert_approx_eq!(Complex, c, Complex::from(c32), epsilon = 0.0000001);
+    }
 }
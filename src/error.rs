@@ -3,11 +3,52 @@ use core::fmt;
 
 pub type Result<T> = core::result::Result<T, QuantumError>;
 
+/// The kind of a [QuantumError](QuantumError), which allows reacting to an error without parsing its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A qbit or bit index is outside of its register.
+    InvalidWire,
+    /// The same qbit is used more than once by a single gate.
+    DuplicateWire,
+    /// A matrix is not unitary, or a set of kraus operators is not complete.
+    NonUnitaryMatrix,
+    /// The register or the matrix is too large for the simulator.
+    SizeLimitExceeded,
+    /// The gate or the operation is not supported in the current context.
+    Unsupported,
+    /// Any other malformed input, like an empty register.
+    InvalidArgument,
+}
+
 #[derive(Debug, Clone)]
-pub struct QuantumError(pub(crate) String);
+pub struct QuantumError(pub(crate) ErrorKind, pub(crate) String);
+
+impl QuantumError {
+    #[inline(always)]
+    pub fn kind(&self) -> ErrorKind {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn message(&self) -> &str {
+        &self.1
+    }
+}
 
 impl fmt::Display for QuantumError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.1)
+    }
+}
+
+/// Converts the error into a javascript `Error` with the message, whose `kind` property names the [ErrorKind](ErrorKind).
+#[cfg(feature = "wasm-bindgen")]
+impl From<QuantumError> for wasm_bindgen::JsValue {
+    fn from(error: QuantumError) -> Self {
+        let js_error = js_sys::Error::new(&error.1);
+        let kind = wasm_bindgen::JsValue::from_str(&alloc::format!("{:?}", error.0));
+        js_sys::Reflect::set(&js_error, &wasm_bindgen::JsValue::from_str("kind"), &kind).unwrap();
+
+        js_error.into()
     }
 }
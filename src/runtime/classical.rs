@@ -0,0 +1,185 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::register::Register;
+
+#[cfg(feature = "wasm-bindgen")]
+use wasm_bindgen::prelude::*;
+
+/// The comparison of a classical condition between the bits and a constant.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Comparison {
+    fn compare(&self, left: usize, right: usize) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::Less => left < right,
+            Comparison::LessEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterEqual => left >= right,
+        }
+    }
+}
+
+/// Compares the integer given by the bits with a constant, `bits[i]` is bit i of the integer.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct Condition {
+    bits: Vec<usize>,
+    comparison: Comparison,
+    value: usize,
+}
+
+impl Condition {
+    /// Fails if there are no bits or more than an integer holds.
+    pub(crate) fn new(bits: &[usize], comparison: Comparison, value: usize) -> Result<Condition> {
+        if bits.is_empty() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one bit in a condition")))
+        }
+        if bits.len() > usize::BITS as usize {
+            return Err(QuantumError(ErrorKind::SizeLimitExceeded, format!(
+                "Expecting at most {} bits in a condition but got {}", usize::BITS, bits.len()
+            )))
+        }
+
+        Ok(Condition {
+            bits: Vec::from(bits),
+            comparison,
+            value,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn bits(&self) -> &[usize] {
+        &self.bits
+    }
+
+    #[inline(always)]
+    pub(crate) fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+
+    #[inline(always)]
+    pub(crate) fn value(&self) -> usize {
+        self.value
+    }
+
+    /// Returns true, if the condition holds for the current bits of the register.
+    pub(crate) fn holds(&self, register: &Register) -> bool {
+        let value = self.bits.iter()
+            .enumerate()
+            .fold(0, |value, (i, bit)| if register.bits()[*bit] { value | (0x1 << i) } else { value });
+
+        self.comparison.compare(value, self.value)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum BitwiseOperator {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+/// A bitwise operation on the classical register, which is executed as an instruction of the circuit.
+/// The operator is folded over all sources, the result overwrites the target.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct ClassicalOperation {
+    operator: BitwiseOperator,
+    sources: Vec<usize>,
+    target: usize,
+}
+
+impl ClassicalOperation {
+    /// Fails if there are no sources, or more than one for a negation.
+    pub(crate) fn new(operator: BitwiseOperator, sources: &[usize], target: usize) -> Result<ClassicalOperation> {
+        if sources.is_empty() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one source bit")))
+        }
+        if operator == BitwiseOperator::Not && sources.len() != 1 {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting a single bit to negate but got {}", sources.len()
+            )))
+        }
+
+        Ok(ClassicalOperation {
+            operator,
+            sources: Vec::from(sources),
+            target,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn operator(&self) -> BitwiseOperator {
+        self.operator
+    }
+
+    #[inline(always)]
+    pub(crate) fn sources(&self) -> &[usize] {
+        &self.sources
+    }
+
+    #[inline(always)]
+    pub(crate) fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Writes the result of the operation to the target bit.
+    pub(crate) fn apply(&self, register: &mut Register) {
+        let bits = register.bits();
+        let mut values = self.sources.iter().map(|source| bits[*source]);
+        let first = values.next().unwrap();
+        let value = match self.operator {
+            BitwiseOperator::And => values.fold(first, |a, b| a & b),
+            BitwiseOperator::Or => values.fold(first, |a, b| a | b),
+            BitwiseOperator::Xor => values.fold(first, |a, b| a ^ b),
+            BitwiseOperator::Not => !first,
+        };
+        register.set(self.target, value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::runtime::classical::{BitwiseOperator, ClassicalOperation, Comparison, Condition};
+    use crate::runtime::register::Register;
+
+    #[test]
+    fn test_operations() {
+        let mut register = Register::from_bits(Vec::from([true, false, true, false]));
+        ClassicalOperation::new(BitwiseOperator::Xor, &[0, 1, 2], 3).unwrap().apply(&mut register);
+        assert_eq!(register.bits(), &Vec::from([true, false, true, false]));
+        ClassicalOperation::new(BitwiseOperator::Or, &[0, 1], 3).unwrap().apply(&mut register);
+        assert_eq!(register.bits(), &Vec::from([true, false, true, true]));
+        ClassicalOperation::new(BitwiseOperator::And, &[1, 2], 0).unwrap().apply(&mut register);
+        ClassicalOperation::new(BitwiseOperator::Not, &[1], 1).unwrap().apply(&mut register);
+        assert_eq!(register.bits(), &Vec::from([false, true, true, true]));
+
+        assert!(ClassicalOperation::new(BitwiseOperator::Xor, &[], 0).is_err());
+        assert!(ClassicalOperation::new(BitwiseOperator::Not, &[0, 1], 2).is_err());
+    }
+
+    #[test]
+    fn test_conditions() {
+        // The bits 1 and 2 hold the value 0b10
+        let register = Register::from_bits(Vec::from([true, false, true]));
+        assert!(Condition::new(&[1, 2], Comparison::Equal, 2).unwrap().holds(&register));
+        assert!(Condition::new(&[1, 2], Comparison::Less, 3).unwrap().holds(&register));
+        assert!(Condition::new(&[1, 2], Comparison::GreaterEqual, 2).unwrap().holds(&register));
+        assert!(!Condition::new(&[1, 2], Comparison::Greater, 2).unwrap().holds(&register));
+        assert!(!Condition::new(&[0], Comparison::NotEqual, 1).unwrap().holds(&register));
+        assert!(Condition::new(&[], Comparison::Equal, 0).is_err());
+    }
+}
@@ -86,7 +86,7 @@ impl<
     fn apply(&self, ket: Ket, wires: &[usize; SIZE]) -> Ket {
         // Assert all qbits exist and are different
         for i in 0..SIZE {
-            assert!(ket.size() >= wires[i]);
+            assert!(ket.size() > wires[i]);
             for j in 0..SIZE {
                 if i != j {
                     assert_ne!(wires[i], wires[j]);
@@ -104,11 +104,12 @@ impl<
 
                         z.into_iter().zip(o)
                     }).for_each(|(z, o)| {
-                        let zt = self.matrix[0][0] * *z + self.matrix[0][1] * *o;
-                        let ot = self.matrix[1][0] * *z + self.matrix[1][1] * *o;
+                        let (zc, oc) = (Complex::from(*z), Complex::from(*o));
+                        let zt = self.matrix[0][0] * zc + self.matrix[0][1] * oc;
+                        let ot = self.matrix[1][0] * zc + self.matrix[1][1] * oc;
 
-                        *z = zt;
-                        *o = ot;
+                        *z = zt.into();
+                        *o = ot.into();
                     });
                 }
                 #[cfg(not(feature = "rayon"))]
@@ -118,11 +119,12 @@ impl<
 
                         z.into_iter().zip(o)
                     }).for_each(|(z, o)| {
-                        let zt = self.matrix[0][0] * *z + self.matrix[0][1] * *o;
-                        let ot = self.matrix[1][0] * *z + self.matrix[1][1] * *o;
+                        let (zc, oc) = (Complex::from(*z), Complex::from(*o));
+                        let zt = self.matrix[0][0] * zc + self.matrix[0][1] * oc;
+                        let ot = self.matrix[1][0] * zc + self.matrix[1][1] * oc;
 
-                        *z = zt;
-                        *o = ot;
+                        *z = zt.into();
+                        *o = ot.into();
                     });
                 }
 
@@ -142,6 +144,7 @@ impl<
                     }
                     let y: usize = y.into();
 
+                    let mut value = Complex::zero();
                     for x in 0..0x1 << SIZE {
                         let x_idx: IndexType = x.into();
                         for pos in 0..SIZE {
@@ -149,9 +152,10 @@ impl<
                         }
                         let ket_idx: usize = ket_idx.clone().into();
 
-                        let v = *old_vec.get(ket_idx).unwrap();
-                        *new_ket_value = *new_ket_value + self.matrix[y][x] * v;
+                        let v = Complex::from(*old_vec.get(ket_idx).unwrap());
+                        value = value + self.matrix[y][x] * v;
                     }
+                    *new_ket_value = value.into();
                 });
 
                 new_ket
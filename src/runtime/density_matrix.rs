@@ -0,0 +1,196 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::{log2, sqrt};
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::ket::Ket;
+use crate::util::svd::svd;
+
+/// Eigenvalues and schmidt coefficients below this are treated as zero.
+const EPSILON: f64 = 1e-12;
+
+/// The schmidt decomposition `sum_i c_i |a_i>|b_i>` of a state across a bipartition.
+#[derive(Clone, Debug)]
+pub(crate) struct Schmidt {
+    pub(crate) coefficients: Vec<f64>,
+    /// The states of the chosen qbits, index bit j belongs to the j-th chosen qbit.
+    pub(crate) a: Vec<Vec<Complex>>,
+    /// The states of the remaining qbits, index bit j belongs to the j-th remaining qbit in ascending order.
+    pub(crate) b: Vec<Vec<Complex>>,
+}
+
+pub(crate) fn validate_qbits(ket: &Ket, qbits: &[usize]) -> Result<()> {
+    if qbits.is_empty() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one qbit")))
+    }
+    for (i, qbit) in qbits.iter().enumerate() {
+        if *qbit >= ket.size() {
+            return Err(QuantumError(
+                ErrorKind::InvalidWire,
+                format!("Expecting qbits below {} but was {}", ket.size(), qbit)
+            ))
+        }
+        if qbits[..i].contains(qbit) {
+            return Err(QuantumError(
+                ErrorKind::DuplicateWire,
+                format!("Expecting distinct qbits but {} occurs twice", qbit)
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Reshapes the state into a `2^k x 2^(n-k)` matrix, with the chosen qbits as row and the rest as column index.
+fn bipartition(ket: &Ket, qbits: &[usize]) -> Result<(Vec<Complex>, usize, usize)> {
+    validate_qbits(ket, qbits)?;
+    let rest = (0..ket.size()).filter(|qbit| !qbits.contains(qbit)).collect::<Vec<_>>();
+    let (rows, cols) = (0x1 << qbits.len(), 0x1 << rest.len());
+    let gather = |state: usize, wires: &[usize]| wires.iter()
+        .enumerate()
+        .filter(|(_, wire)| state & (0x1 << *wire) > 0)
+        .fold(0, |idx, (bit, _)| idx | (0x1 << bit));
+
+    let mut matrix = vec![Complex::zero(); rows * cols];
+    for (state, amplitude) in ket.state().iter().enumerate() {
+        matrix[gather(state, qbits) * cols + gather(state, &rest)] = Complex::from(*amplitude);
+    }
+
+    Ok((matrix, rows, cols))
+}
+
+/// Traces out all but the chosen qbits, the result is row major with `2^k x 2^k` entries.
+pub(crate) fn reduced_density_matrix(ket: &Ket, qbits: &[usize]) -> Result<Vec<Complex>> {
+    let (matrix, rows, cols) = bipartition(ket, qbits)?;
+    let mut density = vec![Complex::zero(); rows * rows];
+    for y in 0..rows {
+        for x in 0..rows {
+            density[y * rows + x] = (0..cols)
+                .map(|c| matrix[y * cols + c] * matrix[x * cols + c].conjugate())
+                .fold(Complex::zero(), |sum, v| sum + v);
+        }
+    }
+
+    Ok(density)
+}
+
+pub(crate) fn schmidt_decomposition(ket: &Ket, qbits: &[usize]) -> Result<Schmidt> {
+    let (matrix, rows, cols) = bipartition(ket, qbits)?;
+    let decomposition = svd(&matrix, rows, cols);
+    let rank = decomposition.s.iter().take_while(|s| **s > EPSILON).count();
+    let column = |vectors: &[Complex], len: usize, i: usize, conjugate: bool| (0..len)
+        .map(|j| vectors[j * decomposition.rank + i])
+        .map(|v| if conjugate { v.conjugate() } else { v })
+        .collect::<Vec<_>>();
+
+    Ok(Schmidt {
+        coefficients: decomposition.s[..rank].to_vec(),
+        a: (0..rank).map(|i| column(&decomposition.u, rows, i, false)).collect(),
+        b: (0..rank).map(|i| column(&decomposition.v, cols, i, true)).collect(),
+    })
+}
+
+/// The von neumann entropy of the chosen qbits in bits, calculated from the schmidt coefficients.
+pub(crate) fn entropy(ket: &Ket, qbits: &[usize]) -> Result<f64> {
+    Ok(schmidt_decomposition(ket, qbits)?.coefficients.iter()
+        .map(|c| c * c)
+        .filter(|p| *p > EPSILON)
+        .map(|p| -p * log2(p))
+        .sum())
+}
+
+/// The purity `Tr(rho^2)` of the chosen qbits.
+pub(crate) fn purity(ket: &Ket, qbits: &[usize]) -> Result<f64> {
+    Ok(reduced_density_matrix(ket, qbits)?.iter().map(|v| v.amplitude()).sum())
+}
+
+/// The square root `U sqrt(S) U†` of a positive semidefinite hermitian matrix.
+fn sqrt_psd(matrix: &[Complex], dim: usize) -> Vec<Complex> {
+    let decomposition = svd(matrix, dim, dim);
+    let mut root = vec![Complex::zero(); dim * dim];
+    for y in 0..dim {
+        for x in 0..dim {
+            root[y * dim + x] = (0..decomposition.rank)
+                .map(|i| decomposition.u[y * decomposition.rank + i]
+                    * decomposition.u[x * decomposition.rank + i].conjugate()
+                    * sqrt(decomposition.s[i]))
+                .fold(Complex::zero(), |sum, v| sum + v);
+        }
+    }
+
+    root
+}
+
+/// The concurrence of two qbits after Wootters, the singular values of `sqrt(rho~) sqrt(rho)` are
+/// the square roots of the eigenvalues of `rho rho~` with the spin flipped `rho~ = (Y x Y) rho* (Y x Y)`.
+pub(crate) fn concurrence(ket: &Ket, a: usize, b: usize) -> Result<f64> {
+    let density = reduced_density_matrix(ket, &[a, b])?;
+    let root = sqrt_psd(&density, 4);
+    // Y x Y maps the basis state i onto 3 - i, with a sign for the states with odd parity
+    let sign = |i: usize| if i == 1 || i == 2 { -1.0 } else { 1.0 };
+    let flipped = (0..16)
+        .map(|idx| root[(3 - idx / 4) * 4 + 3 - idx % 4].conjugate() * (sign(idx / 4) * sign(idx % 4)))
+        .collect::<Vec<_>>();
+    let mut product = vec![Complex::zero(); 16];
+    for y in 0..4 {
+        for x in 0..4 {
+            product[y * 4 + x] = (0..4)
+                .map(|k| flipped[y * 4 + k] * root[k * 4 + x])
+                .fold(Complex::zero(), |sum, v| sum + v);
+        }
+    }
+    let lambda = svd(&product, 4, 4).s;
+
+    Ok((lambda[0] - lambda[1] - lambda[2] - lambda[3]).max(0.0))
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::density_matrix::{concurrence, entropy, purity, reduced_density_matrix, schmidt_decomposition};
+    use crate::runtime::ket::{seed, Ket};
+
+    fn bell_and_plus() -> Ket {
+        // (|00> + |11>) / sqrt(2) on the qbits 0 and 2, qbit 1 in |+>
+        let amplitudes = (0..8)
+            .map(|i| if i & 0x5 == 0x0 || i & 0x5 == 0x5 { Complex::new(0.5, 0.0) } else { Complex::zero() })
+            .collect::<Vec<_>>();
+
+        Ket::from_amplitudes(&amplitudes, seed()).unwrap()
+    }
+
+    #[test]
+    fn test_reduced_density_matrix() {
+        let ket = bell_and_plus();
+        let density = reduced_density_matrix(&ket, &[1]).unwrap();
+        for v in density {
+            assert_approx_eq!(Complex, v, Complex::new(0.5, 0.0), epsilon = tolerance(0.000001));
+        }
+        let density = reduced_density_matrix(&ket, &[2, 0]).unwrap();
+        assert_approx_eq!(Complex, density[0], Complex::new(0.5, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, density[15], Complex::new(0.5, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, density[3], Complex::new(0.5, 0.0), epsilon = tolerance(0.000001));
+        assert!(reduced_density_matrix(&ket, &[0, 0]).is_err());
+        assert!(reduced_density_matrix(&ket, &[3]).is_err());
+    }
+
+    #[test]
+    fn test_entanglement_measures() {
+        let ket = bell_and_plus();
+        assert_approx_eq!(f64, entropy(&ket, &[0]).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, entropy(&ket, &[1]).unwrap(), 0.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, purity(&ket, &[2]).unwrap(), 0.5, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, purity(&ket, &[0, 2]).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, concurrence(&ket, 0, 2).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        assert_approx_eq!(f64, concurrence(&ket, 0, 1).unwrap(), 0.0, epsilon = tolerance(0.000001));
+
+        let schmidt = schmidt_decomposition(&ket, &[0]).unwrap();
+        assert_eq!(schmidt.coefficients.len(), 2);
+        assert_approx_eq!(f64, schmidt.coefficients[0], FRAC_1_SQRT_2, epsilon = tolerance(0.000001));
+    }
+}
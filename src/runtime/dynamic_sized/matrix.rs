@@ -1,10 +1,9 @@
 use alloc::{format, vec};
 use alloc::vec::Vec;
-use core::mem::size_of;
-use core::ops::{BitXor, Mul, Not};
+use core::ops::{BitXor, Mul};
 
 use crate::complex::Complex;
-use crate::error::{QuantumError, Result};
+use crate::error::{ErrorKind, QuantumError, Result};
 use crate::runtime::ket::{IndexType, Ket};
 
 #[cfg(feature = "rayon")]
@@ -22,34 +21,41 @@ impl DynamicSizedMatrix {
         let len = matrix.len();
         if len <= 1 {
             return Err(QuantumError(
+                ErrorKind::InvalidArgument,
                 format!("Expecting matrix length to be larger than 1 but was {}", len)
             ))
         }
-        if len == 0 || (len & (len - 1)).not() == 0 {
+        if !len.is_power_of_two() || len.trailing_zeros() % 2 != 0 {
             return Err(QuantumError(
-                format!("Expecting matrix length to be power of two but was {}", len)
+                ErrorKind::InvalidArgument,
+                format!("Expecting matrix length to be a power of four but was {}", len)
             ))
         }
-        for i in 0..8 * size_of::<usize>() {
-            if (len & (0x1 << i)) > 0 {
-                return Ok(DynamicSizedMatrix {
-                    matrix,
-                    width: 0x1 << (i / 2),
-                    qbit_size: i / 2,
-                });
-            }
+        let qbit_size = len.trailing_zeros() as usize / 2;
+
+        Ok(DynamicSizedMatrix {
+            matrix,
+            width: 0x1 << qbit_size,
+            qbit_size,
+        })
+    }
+
+    /// Creates the matrix of an operator on the number of qbits, fails unless it has `4^qbits` entries.
+    pub(crate) fn with_qbits(matrix: Vec<Complex>, qbits: usize) -> Result<DynamicSizedMatrix> {
+        if qbits < 1 || matrix.len() != 0x1usize.checked_shl(2 * qbits as u32).unwrap_or(0) {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting a matrix of {} qbits but got {} entries", qbits, matrix.len()
+            )))
         }
 
-        Err(QuantumError(
-            format!("Expecting matrix length to be a square root of a power of two but was {}", len)
-        ))
+        DynamicSizedMatrix::new(matrix)
     }
 
     pub(crate) fn apply(&self, ket: Ket, wires: &[usize]) -> Ket {
         // Assert all qbits exist and are different
         assert_eq!(self.qbit_size, wires.len());
         for i in 0..wires.len() {
-            assert!(ket.size() >= wires[i]);
+            assert!(ket.size() > wires[i]);
             for j in 0..wires.len() {
                 if i != j {
                     assert_ne!(wires[i], wires[j]);
@@ -71,6 +77,7 @@ impl DynamicSizedMatrix {
             }
             let y: usize = y.into();
 
+            let mut value = Complex::zero();
             for x in 0..self.width {
                 let x_idx: IndexType = x.into();
                 for pos in 0..self.qbit_size {
@@ -78,10 +85,11 @@ impl DynamicSizedMatrix {
                 }
                 let ket_idx: usize = ket_idx.clone().into();
 
-                let v = *old_vec.get(ket_idx).unwrap();
+                let v = Complex::from(*old_vec.get(ket_idx).unwrap());
                 let matrix_value = *{ self.matrix.get(y * self.width + x).unwrap() };
-                *new_ket_value = *new_ket_value + matrix_value * v;
+                value = value + matrix_value * v;
             }
+            *new_ket_value = value.into();
         });
 
         new_ket
@@ -90,6 +98,17 @@ impl DynamicSizedMatrix {
     pub(crate) fn size(&self) -> usize {
         self.qbit_size
     }
+
+    #[inline(always)]
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the value in row `y` and column `x`.
+    #[inline(always)]
+    pub(crate) fn get(&self, y: usize, x: usize) -> Complex {
+        self.matrix[y * self.width + x]
+    }
 }
 
 impl Mul for DynamicSizedMatrix {
@@ -197,6 +216,18 @@ mod test {
     use crate::complex::Complex;
     use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
 
+    #[test]
+    fn test_invalid_length() {
+        assert!(DynamicSizedMatrix::new(Vec::from([Complex::zero(); 1])).is_err());
+        assert!(DynamicSizedMatrix::new(Vec::from([Complex::zero(); 2])).is_err());
+        assert!(DynamicSizedMatrix::new(Vec::from([Complex::zero(); 6])).is_err());
+        assert!(DynamicSizedMatrix::new(Vec::from([Complex::zero(); 8])).is_err());
+        assert_eq!(DynamicSizedMatrix::new(Vec::from([Complex::zero(); 16])).unwrap().size(), 2);
+        assert!(DynamicSizedMatrix::with_qbits(Vec::from([Complex::zero(); 16]), 1).is_err());
+        assert!(DynamicSizedMatrix::with_qbits(Vec::from([Complex::zero(); 1]), 0).is_err());
+        assert_eq!(DynamicSizedMatrix::with_qbits(Vec::from([Complex::zero(); 4]), 1).unwrap().size(), 1);
+    }
+
     #[test]
     fn test_multiply_x_x() {
         let a: DynamicSizedMatrix = DynamicSizedMatrix::new(Vec::from([
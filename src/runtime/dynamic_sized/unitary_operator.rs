@@ -1,6 +1,9 @@
 use alloc::vec::Vec;
 use alloc::format;
-use crate::error::{QuantumError, Result};
+use core::ops::Mul;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator;
 use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
 use crate::runtime::unitary::UnitaryOperator;
 use crate::runtime::ket::Ket;
@@ -21,7 +24,7 @@ impl DynamicSizedUnitaryOperator {
         classical_control: Option<usize>,
     ) -> Result<DynamicSizedUnitaryOperator> {
         if matrix.size() != wires.len() {
-            return Err(QuantumError(format!(
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
                     "Expecting matrix to be applied to {} qbits, but where {} qbits",
                     matrix.size(),
                     wires.len()
@@ -61,3 +64,26 @@ impl UnitaryOperator for DynamicSizedUnitaryOperator {
         }
     }
 }
+
+impl<
+    const SIZE: usize,
+    T: Mul<Complex, Output = Complex> + Copy + Sized + 'static
+> From<ConstSizedUnitaryOperator<SIZE, T>> for DynamicSizedUnitaryOperator
+    where
+        [(); 0x1 << SIZE]:,
+{
+    fn from(operator: ConstSizedUnitaryOperator<SIZE, T>) -> Self {
+        let mut matrix = Vec::with_capacity((0x1 << SIZE) * (0x1 << SIZE));
+        for row in operator.matrix().matrix().iter() {
+            for value in row.iter() {
+                matrix.push(*value * Complex::new(1.0, 0.0));
+            }
+        }
+
+        DynamicSizedUnitaryOperator {
+            matrix: DynamicSizedMatrix::new(matrix).unwrap(),
+            wires: Vec::from(operator.wires().as_slice()),
+            classical_control: operator.classical_control(),
+        }
+    }
+}
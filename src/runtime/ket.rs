@@ -1,12 +1,15 @@
-use crate::complex::Complex;
-use crate::error::{QuantumError, Result};
+use crate::complex::{Amplitude, Complex};
+use crate::error::{ErrorKind, QuantumError, Result};
 use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
+use core::mem::size_of;
 use core::ops::{Deref, DerefMut};
 use alloc::vec;
 use alloc::sync::Arc;
 use bit_array::BitArray;
+use libm::fabs;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
 use spin::Mutex;
@@ -52,50 +55,152 @@ impl DerefMut for IndexType {
     }
 }
 
+/// The tolerated deviation of the squared norm from one for user supplied states.
+const NORM_EPSILON: f64 = 1e-8;
+
+/// The largest number of qbits, whose state vector can still be allocated.
+pub(crate) const MAX_SIZE: usize = 8 * size_of::<usize>() - 1 - size_of::<Amplitude>().trailing_zeros() as usize;
+
+/// Checks that the qbit is one of the qbits of a register of the size.
+pub(crate) fn validate_qbit(size: usize, qbit: usize) -> Result<()> {
+    if qbit >= size {
+        return Err(QuantumError(ErrorKind::InvalidWire, format!("Expecting qbits below {} but was {}", size, qbit)))
+    }
+
+    Ok(())
+}
+
+/// Checks that the amplitudes describe a normalized state of whole qbits and returns the number of qbits.
+pub(crate) fn validate_amplitudes(amplitudes: &[Complex]) -> Result<usize> {
+    let len = amplitudes.len();
+    if len < 2 || len & (len - 1) != 0 {
+        return Err(QuantumError(
+            ErrorKind::InvalidArgument,
+            format!("Expecting the number of amplitudes to be a power of two larger than 1 but was {}", len)
+        ))
+    }
+    let norm: f64 = amplitudes.iter().map(|amplitude| amplitude.amplitude()).sum();
+    if fabs(norm - 1.0) > NORM_EPSILON {
+        return Err(QuantumError(
+            ErrorKind::InvalidArgument,
+            format!("Expecting the amplitudes to be normalized but the norm was {}", norm)
+        ))
+    }
+
+    Ok(len.trailing_zeros() as usize)
+}
+
 #[derive(Debug, Clone)]
 pub(crate)struct Ket {
     size: usize,
-    pub(crate) vec: Vec<Complex>,
+    pub(crate) vec: Vec<Amplitude>,
     pub(crate) seed: Arc<Mutex<SmallRng>>,
 }
 
+/// Creates a new random number generator, seeded from the environment if possible.
+pub(crate) fn seed() -> Arc<Mutex<SmallRng>> {
+    #[cfg(feature = "wasm-bindgen")]
+    {
+        Arc::new(Mutex::new(SmallRng::seed_from_u64(
+            (js_sys::Math::random() * u64::MAX as f64) as u64
+        )))
+    }
+    #[cfg(all(feature = "std", not(feature = "wasm-bindgen")))]
+    {
+        Arc::new(Mutex::new(
+            SmallRng::from_rng(rand::prelude::thread_rng()).unwrap()
+        ))
+    }
+    #[cfg(all(not(feature = "wasm-bindgen"), not(feature = "std")))]
+    {
+        Arc::new(Mutex::new(SmallRng::seed_from_u64(42)))
+    }
+}
+
 impl Ket {
     pub(crate) fn new(size: usize) -> Result<Ket> {
-        #[cfg(feature = "wasm-bindgen")]
-        {
-            Ket::new_with_seed(size, Arc::new(Mutex::new(SmallRng::seed_from_u64(
-                (js_sys::Math::random() * u64::MAX as f64) as u64
-            ))))
-        }
-        #[cfg(all(feature = "std", not(feature = "wasm-bindgen")))]
-        {
-            Ket::new_with_seed(size, Arc::new(Mutex::new(
-                SmallRng::from_rng(rand::prelude::thread_rng()).unwrap()
-            )))
-        }
-        #[cfg(all(not(feature = "wasm-bindgen"), not(feature = "std")))]
-        {
-            Ket::new_with_seed(size, Arc::new(Mutex::new(SmallRng::seed_from_u64(42))))
-        }
+        Ket::new_with_seed(size, seed())
     }
 
     pub(crate) fn new_with_seed(size: usize, seed: Arc<Mutex<SmallRng>>) -> Result<Ket> {
         if size < 1 {
-            return Err(QuantumError(format!("Size must be greater than 0, but was {}", size)))
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one qbit, but the register has none")
+            ))
+        }
+        if size > MAX_SIZE {
+            return Err(QuantumError(
+                ErrorKind::SizeLimitExceeded,
+                format!("Size must be at most {}, but was {}", MAX_SIZE, size)
+            ))
         }
+        let mut vec = Vec::new();
+        if vec.try_reserve_exact(0x1 << size).is_err() {
+            return Err(QuantumError(
+                ErrorKind::SizeLimitExceeded,
+                format!("Could not allocate the state vector of {} qbits", size)
+            ))
+        }
+        vec.resize(0x1 << size, Amplitude::default());
         let mut k = Ket {
             size,
-            vec: vec![Complex::zero(); 0x1 << size],
+            vec,
             seed
         };
-        *k.vec.get_mut(0).unwrap() = Complex::new(1.0, 0.0);
+        *k.vec.get_mut(0).unwrap() = Complex::new(1.0, 0.0).into();
 
         Ok(k)
     }
 
+    /// Creates a new Ket in the basis state, where qbit i is given by bit i of the state.
+    pub(crate) fn from_basis_state(size: usize, state: usize, seed: Arc<Mutex<SmallRng>>) -> Result<Ket> {
+        let mut k = Ket::new_with_seed(size, seed)?;
+        if state >= k.vec.len() {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting a basis state below {} but was {}", k.vec.len(), state)
+            ))
+        }
+        k.vec[0] = Amplitude::default();
+        k.vec[state] = Complex::new(1.0, 0.0).into();
+
+        Ok(k)
+    }
+
+    /// Creates a new Ket from a normalized vector of amplitudes.
+    pub(crate) fn from_amplitudes(amplitudes: &[Complex], seed: Arc<Mutex<SmallRng>>) -> Result<Ket> {
+        let size = validate_amplitudes(amplitudes)?;
+
+        Ok(Ket {
+            size,
+            vec: amplitudes.iter().map(|amplitude| (*amplitude).into()).collect(),
+            seed,
+        })
+    }
+
+    /// Creates a new Ket as product of single qbit states `alpha|0> + beta|1>`, starting with qbit 0.
+    pub(crate) fn from_product_state(qbits: &[(Complex, Complex)], seed: Arc<Mutex<SmallRng>>) -> Result<Ket> {
+        let mut amplitudes = Vec::from([Complex::new(1.0, 0.0)]);
+        for (wire, (alpha, beta)) in qbits.iter().enumerate() {
+            let norm = alpha.amplitude() + beta.amplitude();
+            if fabs(norm - 1.0) > NORM_EPSILON {
+                return Err(QuantumError(
+                    ErrorKind::InvalidArgument,
+                    format!("Expecting qbit {} to be normalized but the norm was {}", wire, norm)
+                ))
+            }
+            let low = amplitudes.iter().map(|amplitude| *amplitude * *alpha);
+            let high = amplitudes.iter().map(|amplitude| *amplitude * *beta);
+            amplitudes = low.chain(high).collect();
+        }
+
+        Ket::from_amplitudes(&amplitudes, seed)
+    }
+
     /// Create a new Ket with the same size and rng, but all zero.
     pub(crate) fn new_from(ket: &Ket) -> Ket {
-        let vec = vec![Complex::zero(); ket.vec.len()];
+        let vec = vec![Amplitude::default(); ket.vec.len()];
 
         Ket { size: ket.size, vec, seed: ket.seed.clone() }
     }
@@ -124,8 +229,8 @@ impl Ket {
         return probabilities
     }
 
-    pub(crate) fn probability(&self, bit: usize) -> f64 {
-        assert!(self.size > bit);
+    pub(crate) fn probability(&self, bit: usize) -> Result<f64> {
+        validate_qbit(self.size, bit)?;
         let mut probability = 0.0;
         let bit_m = 0x1 << bit;
         for state_id in 0..self.vec.len() {
@@ -134,10 +239,91 @@ impl Ket {
             }
         }
 
-        return probability
+        Ok(probability)
+    }
+
+    /// Returns the joint distribution of the bits, where bit j of the outcome belongs to `bits[j]`.
+    pub(crate) fn marginal_probabilities(&self, bits: &[usize]) -> Vec<f64> {
+        let mut probabilities = vec![0.0; 0x1 << bits.len()];
+        for (state_id, amplitude) in self.vec.iter().enumerate() {
+            let outcome = bits.iter()
+                .enumerate()
+                .filter(|(_, bit)| state_id & (0x1 << *bit) > 0)
+                .fold(0, |outcome, (j, _)| outcome | (0x1 << j));
+            probabilities[outcome] += amplitude.amplitude();
+        }
+
+        probabilities
+    }
+
+    /// Returns the probability of all states matching the pattern, which lists the bits from the highest to
+    /// the lowest with `0`, `1` or one of the wildcards `x` and `*`.
+    pub(crate) fn pattern_probability(&self, pattern: &str) -> Result<f64> {
+        let len = pattern.chars().count();
+        if len != self.size {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting a pattern of {} bits but got {}", self.size, len)
+            ))
+        }
+        let (mut mask, mut value) = (0usize, 0usize);
+        for (i, c) in pattern.chars().enumerate() {
+            let bit = 0x1 << (self.size - 1 - i);
+            match c {
+                '0' => mask |= bit,
+                '1' => {
+                    mask |= bit;
+                    value |= bit;
+                },
+                'x' | 'X' | '*' => {},
+                c => return Err(QuantumError(
+                    ErrorKind::InvalidArgument,
+                    format!("Expecting 0, 1, x or * in the pattern but got {}", c)
+                )),
+            }
+        }
+
+        Ok(self.vec.iter()
+            .enumerate()
+            .filter(|(state_id, _)| state_id & mask == value)
+            .map(|(_, amplitude)| amplitude.amplitude())
+            .sum())
+    }
+
+    /// Returns the bloch vector `(<X>, <Y>, <Z>)` of the reduced state of the bit.
+    pub(crate) fn bloch_vector(&self, bit: usize) -> Result<[f64; 3]> {
+        validate_qbit(self.size, bit)?;
+        let bit_m = 0x1 << bit;
+        // The off diagonal element rho_10 of the reduced density matrix and the probability of |1>
+        let mut coherence = Complex::zero();
+        let mut probability = 0.0;
+        for state_id in 0..self.vec.len() {
+            if state_id & bit_m == 0 {
+                let low = Complex::from(self.vec[state_id]);
+                let high = Complex::from(self.vec[state_id | bit_m]);
+                coherence = coherence + high * low.conjugate();
+                probability += high.amplitude();
+            }
+        }
+
+        Ok([2.0 * coherence.re(), 2.0 * coherence.im(), 1.0 - 2.0 * probability])
+    }
+
+    /// Returns the inner product `<self|other>`.
+    pub(crate) fn inner_product(&self, other: &Ket) -> Result<Complex> {
+        if self.size != other.size {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting registers of the same size but got {} and {} qbits", self.size, other.size
+            )))
+        }
+
+        Ok(self.vec.iter()
+            .zip(other.vec.iter())
+            .map(|(a, b)| Complex::from(*a).conjugate() * Complex::from(*b))
+            .fold(Complex::zero(), |sum, v| sum + v))
     }
 
-    pub(crate) fn state(&self) -> &Vec<Complex> {
+    pub(crate) fn state(&self) -> &Vec<Amplitude> {
         &self.vec
     }
 
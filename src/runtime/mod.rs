@@ -5,4 +5,11 @@ pub(crate) mod operator;
 pub(crate) mod unitary;
 pub(crate) mod ket;
 pub(crate) mod register;
+pub(crate) mod classical;
 pub(crate) mod matrix;
+pub(crate) mod simulator;
+pub(crate) mod mps;
+pub(crate) mod stabilizer;
+pub(crate) mod sparse_ket;
+pub(crate) mod density_matrix;
+pub(crate) mod pauli;
@@ -0,0 +1,464 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::sqrt;
+use rand::RngCore;
+use rand::rngs::SmallRng;
+use spin::Mutex;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+use crate::runtime::ket::{validate_qbit, Ket};
+use crate::runtime::simulator::{swap, validate_wires, Simulator};
+use crate::util::svd::svd;
+
+/// A single tensor of the chain with the indices `(left, physical, right)`.
+#[derive(Clone, Debug)]
+struct Site {
+    left: usize,
+    right: usize,
+    tensor: Vec<Complex>,
+}
+
+impl Site {
+    #[inline(always)]
+    fn get(&self, l: usize, p: usize, r: usize) -> Complex {
+        self.tensor[(l * 2 + p) * self.right + r]
+    }
+}
+
+/// A quantum state stored as matrix product state.
+///
+/// The qbits are permuted along the chain with swaps, such that every gate acts on neighbouring sites.
+/// The chain is kept in mixed canonical form, which makes the singular values of each split the
+/// schmidt coefficients of the state and the discarded weight the exact truncation error.
+#[derive(Clone, Debug)]
+pub(crate) struct Mps {
+    sites: Vec<Site>,
+    /// The site each qbit is located at
+    positions: Vec<usize>,
+    /// The qbit each site holds
+    qbits: Vec<usize>,
+    /// The site all other sites are canonical to
+    center: usize,
+    max_bond_dimension: usize,
+    truncation_threshold: f64,
+    truncation_error: f64,
+    seed: Arc<Mutex<SmallRng>>,
+}
+
+impl Mps {
+    pub(crate) fn new(
+        size: usize,
+        max_bond_dimension: usize,
+        truncation_threshold: f64,
+        seed: Arc<Mutex<SmallRng>>,
+    ) -> Result<Mps> {
+        if size < 1 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one qbit, but the register has none")
+            ))
+        }
+        if max_bond_dimension < 1 {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Bond dimension must be greater than 0, but was {}",
+                max_bond_dimension
+            )))
+        }
+
+        Ok(Mps {
+            sites: (0..size).map(|_| Site {
+                left: 1,
+                right: 1,
+                tensor: Vec::from([Complex::new(1.0, 0.0), Complex::zero()]),
+            }).collect(),
+            positions: (0..size).collect(),
+            qbits: (0..size).collect(),
+            center: 0,
+            max_bond_dimension,
+            truncation_threshold,
+            truncation_error: 0.0,
+            seed,
+        })
+    }
+
+    /// Creates a new Mps from the state vector by splitting it site by site, truncating like a gate would.
+    pub(crate) fn from_ket(ket: &Ket, max_bond_dimension: usize, truncation_threshold: f64) -> Result<Mps> {
+        let size = ket.size();
+        let mut mps = Mps::new(size, max_bond_dimension, truncation_threshold, ket.seed.clone())?;
+        // Qbit i is bit i of the ket, but the first site is the most significant bit of the tensor
+        let tensor = (0..ket.state().len())
+            .map(|idx| Complex::from(ket.state()[idx.reverse_bits() >> (usize::BITS as usize - size)]))
+            .collect();
+        mps.split_block(0, size, 1, 1, tensor);
+
+        Ok(mps)
+    }
+
+    #[inline(always)]
+    pub(crate) fn size(&self) -> usize {
+        self.sites.len()
+    }
+
+    /// The accumulated weight of all discarded singular values.
+    #[inline(always)]
+    pub(crate) fn truncation_error(&self) -> f64 {
+        self.truncation_error
+    }
+
+    /// The dimensions of the bonds between neighbouring sites.
+    pub(crate) fn bond_dimensions(&self) -> Vec<usize> {
+        self.sites.iter().skip(1).map(|site| site.left).collect()
+    }
+
+    /// Returns the amplitude of the basis state, the state of qbit `i` is `bits[i]`.
+    pub(crate) fn amplitude(&self, bits: &[bool]) -> Complex {
+        assert_eq!(bits.len(), self.size());
+        let mut vector = Vec::from([Complex::new(1.0, 0.0)]);
+        for (idx, site) in self.sites.iter().enumerate() {
+            let p = bits[self.qbits[idx]] as usize;
+            let mut next = vec![Complex::zero(); site.right];
+            for l in 0..site.left {
+                for r in 0..site.right {
+                    next[r] = next[r] + vector[l] * site.get(l, p, r);
+                }
+            }
+            vector = next;
+        }
+
+        vector[0] * (1.0 / sqrt(self.norm()))
+    }
+
+    /// Returns the probabilities of each qbit to be measured as `|1>`.
+    pub(crate) fn probabilities(&self) -> Vec<f64> {
+        (0..self.size()).filter_map(|wire| self.probability(wire).ok()).collect()
+    }
+
+    fn norm(&self) -> f64 {
+        self.contract(None)
+    }
+
+    /// Contracts the chain with its conjugate, optionally restricting a site to one physical state.
+    fn contract(&self, restriction: Option<(usize, usize)>) -> f64 {
+        let mut environment = Vec::from([Complex::new(1.0, 0.0)]);
+        for (idx, site) in self.sites.iter().enumerate() {
+            let physical = match restriction {
+                Some((restricted, p)) if restricted == idx => p..p + 1,
+                _ => 0..2,
+            };
+            let mut next = vec![Complex::zero(); site.right * site.right];
+            for p in physical {
+                let mut partial = vec![Complex::zero(); site.left * site.right];
+                for l in 0..site.left {
+                    for m in 0..site.left {
+                        let e = environment[l * site.left + m];
+                        for r in 0..site.right {
+                            partial[l * site.right + r] = partial[l * site.right + r] + e * site.get(m, p, r);
+                        }
+                    }
+                }
+                for l in 0..site.left {
+                    for r in 0..site.right {
+                        let a = site.get(l, p, r).conjugate();
+                        for s in 0..site.right {
+                            next[r * site.right + s] = next[r * site.right + s] + a * partial[l * site.right + s];
+                        }
+                    }
+                }
+            }
+            environment = next;
+        }
+
+        environment[0].re()
+    }
+
+    /// Moves the orthogonality center to the site, without truncation.
+    fn move_center(&mut self, target: usize) {
+        while self.center < target {
+            let site = &self.sites[self.center];
+            let (left, right) = (site.left, site.right);
+            let decomposition = svd(&site.tensor, left * 2, right);
+            let rank = decomposition.rank;
+            self.sites[self.center] = Site { left, right: rank, tensor: decomposition.u };
+
+            let next = &self.sites[self.center + 1];
+            let mut tensor = vec![Complex::zero(); rank * 2 * next.right];
+            for k in 0..rank {
+                for c in 0..right {
+                    let value = decomposition.s[k] * decomposition.v[c * rank + k].conjugate();
+                    for p in 0..2 {
+                        for r in 0..next.right {
+                            let idx = (k * 2 + p) * next.right + r;
+                            tensor[idx] = tensor[idx] + value * next.get(c, p, r);
+                        }
+                    }
+                }
+            }
+            self.sites[self.center + 1] = Site { left: rank, right: next.right, tensor };
+            self.center += 1;
+        }
+        while self.center > target {
+            let site = &self.sites[self.center];
+            let (left, right) = (site.left, site.right);
+            let decomposition = svd(&site.tensor, left, 2 * right);
+            let rank = decomposition.rank;
+            let mut tensor = vec![Complex::zero(); rank * 2 * right];
+            for k in 0..rank {
+                for c in 0..2 * right {
+                    tensor[k * 2 * right + c] = decomposition.v[c * rank + k].conjugate();
+                }
+            }
+            self.sites[self.center] = Site { left: rank, right, tensor };
+
+            let previous = &self.sites[self.center - 1];
+            let mut tensor = vec![Complex::zero(); previous.left * 2 * rank];
+            for a in 0..previous.left {
+                for p in 0..2 {
+                    for m in 0..left {
+                        let value = previous.get(a, p, m);
+                        for k in 0..rank {
+                            let idx = (a * 2 + p) * rank + k;
+                            tensor[idx] = tensor[idx] + value * decomposition.u[m * rank + k] * decomposition.s[k];
+                        }
+                    }
+                }
+            }
+            self.sites[self.center - 1] = Site { left: previous.left, right: rank, tensor };
+            self.center -= 1;
+        }
+    }
+
+    /// Swaps the qbits of the site and its right neighbour.
+    fn swap_sites(&mut self, site: usize) {
+        self.apply_block(site, &swap());
+        self.qbits.swap(site, site + 1);
+        self.positions[self.qbits[site]] = site;
+        self.positions[self.qbits[site + 1]] = site + 1;
+    }
+
+    /// Moves the qbit to the site by swapping it along the chain.
+    fn move_qbit(&mut self, wire: usize, target: usize) {
+        while self.positions[wire] > target {
+            self.swap_sites(self.positions[wire] - 1);
+        }
+        while self.positions[wire] < target {
+            self.swap_sites(self.positions[wire]);
+        }
+    }
+
+    /// Returns the number of singular values to keep and accumulates the discarded weight.
+    fn truncate(&mut self, singular_values: &[f64]) -> usize {
+        let total: f64 = singular_values.iter().map(|s| s * s).sum();
+        if total == 0.0 {
+            return 1;
+        }
+        let mut bond = singular_values.len().min(self.max_bond_dimension).max(1);
+        let mut discarded: f64 = singular_values[bond..].iter().map(|s| s * s).sum();
+        while bond > 1 {
+            let weight = singular_values[bond - 1] * singular_values[bond - 1];
+            if (discarded + weight) / total > self.truncation_threshold {
+                break;
+            }
+            discarded += weight;
+            bond -= 1;
+        }
+        self.truncation_error += discarded / total;
+
+        bond
+    }
+
+    /// Applies the matrix to the neighbouring sites starting at `start`, the first site being the
+    /// most significant bit of the matrix index.
+    fn apply_block(&mut self, start: usize, matrix: &DynamicSizedMatrix) {
+        let count = matrix.size();
+        let dimension = matrix.width();
+        if self.center < start {
+            self.move_center(start);
+        } else if self.center >= start + count {
+            self.move_center(start + count - 1);
+        }
+
+        // Contract the sites into one tensor (left, physical, right)
+        let left = self.sites[start].left;
+        let mut right = self.sites[start].right;
+        let mut tensor = self.sites[start].tensor.clone();
+        let mut physical = 2;
+        for site in &self.sites[start + 1..start + count] {
+            let mut next = vec![Complex::zero(); left * physical * 2 * site.right];
+            for l in 0..left {
+                for x in 0..physical {
+                    for m in 0..right {
+                        let value = tensor[(l * physical + x) * right + m];
+                        for p in 0..2 {
+                            for r in 0..site.right {
+                                let idx = ((l * physical + x) * 2 + p) * site.right + r;
+                                next[idx] = next[idx] + value * site.get(m, p, r);
+                            }
+                        }
+                    }
+                }
+            }
+            tensor = next;
+            physical *= 2;
+            right = site.right;
+        }
+
+        let mut applied = vec![Complex::zero(); tensor.len()];
+        for l in 0..left {
+            for y in 0..dimension {
+                for x in 0..dimension {
+                    let value = matrix.get(y, x);
+                    for r in 0..right {
+                        let idx = (l * dimension + y) * right + r;
+                        applied[idx] = applied[idx] + value * tensor[(l * dimension + x) * right + r];
+                    }
+                }
+            }
+        }
+
+        self.split_block(start, count, left, right, applied);
+    }
+
+    /// Splits the tensor `(left, physical, right)` of the block into its sites, moving the center to the
+    /// last site of the block.
+    fn split_block(&mut self, start: usize, count: usize, left: usize, right: usize, tensor: Vec<Complex>) {
+        let mut left = left;
+        let mut tensor = tensor;
+        let mut physical = 1 << count;
+        for offset in 0..count - 1 {
+            physical /= 2;
+            let (rows, cols) = (left * 2, physical * right);
+            let decomposition = svd(&tensor, rows, cols);
+            let rank = decomposition.rank;
+            let bond = self.truncate(&decomposition.s);
+            let total: f64 = decomposition.s.iter().map(|s| s * s).sum();
+            let kept: f64 = decomposition.s[..bond].iter().map(|s| s * s).sum();
+            let scale = if kept > 0.0 { sqrt(total / kept) } else { 1.0 };
+
+            let mut site = vec![Complex::zero(); rows * bond];
+            for r in 0..rows {
+                for k in 0..bond {
+                    site[r * bond + k] = decomposition.u[r * rank + k];
+                }
+            }
+            let mut remainder = vec![Complex::zero(); bond * cols];
+            for k in 0..bond {
+                for c in 0..cols {
+                    remainder[k * cols + c] = decomposition.s[k] * scale * decomposition.v[c * rank + k].conjugate();
+                }
+            }
+
+            self.sites[start + offset] = Site { left, right: bond, tensor: site };
+            left = bond;
+            tensor = remainder;
+        }
+        self.sites[start + count - 1] = Site { left, right, tensor };
+        self.center = start + count - 1;
+    }
+}
+
+impl Simulator for Mps {
+    fn apply_matrix(&mut self, matrix: &DynamicSizedMatrix, wires: &[usize]) -> Result<()> {
+        validate_wires(self.size(), matrix, wires)?;
+        let start = wires.iter()
+            .map(|wire| self.positions[*wire])
+            .min()
+            .unwrap()
+            .min(self.size() - wires.len());
+        for (offset, wire) in wires.iter().enumerate() {
+            self.move_qbit(*wire, start + offset);
+        }
+
+        self.apply_block(start, matrix);
+
+        Ok(())
+    }
+
+    fn probability(&self, wire: usize) -> Result<f64> {
+        validate_qbit(self.size(), wire)?;
+
+        Ok(self.contract(Some((self.positions[wire], 1))) / self.norm())
+    }
+
+    fn collapse(&mut self, wire: usize, state: bool, probability: f64) {
+        let position = self.positions[wire];
+        self.move_center(position);
+        let scale = if probability > 0.0 { 1.0 / sqrt(probability) } else { 1.0 };
+        let site = &mut self.sites[position];
+        for l in 0..site.left {
+            for p in 0..2 {
+                for r in 0..site.right {
+                    let idx = (l * 2 + p) * site.right + r;
+                    site.tensor[idx] = if (p == 1) == state {
+                        site.tensor[idx] * scale
+                    } else {
+                        Complex::zero()
+                    };
+                }
+            }
+        }
+    }
+
+    fn random(&self) -> f64 {
+        self.seed.lock().next_u32() as f64 / u32::MAX as f64
+    }
+
+    fn squared_norm(&self) -> f64 {
+        self.norm()
+    }
+
+    fn scale(&mut self, factor: f64) {
+        let site = &mut self.sites[self.center];
+        for value in site.tensor.iter_mut() {
+            *value = *value * factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use crate::error::ErrorKind;
+    use crate::runtime::ket::seed;
+    use crate::runtime::mps::Mps;
+    use crate::runtime::simulator::{controlled_pauli_x, hadamard, Simulator};
+
+    #[test]
+    fn test_mps_ghz_distant_qbits() {
+        let mut mps = Mps::new(6, 16, 0.0, seed()).unwrap();
+        mps.apply_matrix(&hadamard(), &[0]).unwrap();
+        mps.apply_matrix(&controlled_pauli_x(), &[0, 5]).unwrap();
+        mps.apply_matrix(&controlled_pauli_x(), &[5, 2]).unwrap();
+
+        let mut bits = Vec::from([false; 6]);
+        assert_approx_eq!(f64, mps.amplitude(&bits).re(), FRAC_1_SQRT_2, epsilon = 0.00000003);
+        bits[0] = true;
+        bits[2] = true;
+        bits[5] = true;
+        assert_approx_eq!(f64, mps.amplitude(&bits).re(), FRAC_1_SQRT_2, epsilon = 0.00000003);
+        assert_approx_eq!(f64, mps.probability(2).unwrap(), 0.5, epsilon = 0.00000003);
+        assert_approx_eq!(f64, mps.probability(3).unwrap(), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, mps.truncation_error(), 0.0, epsilon = 0.00000003);
+    }
+
+    #[test]
+    fn test_mps_truncation() {
+        let mut mps = Mps::new(2, 1, 0.0, seed()).unwrap();
+        mps.apply_matrix(&hadamard(), &[0]).unwrap();
+        mps.apply_matrix(&controlled_pauli_x(), &[0, 1]).unwrap();
+
+        assert_eq!(mps.bond_dimensions(), Vec::from([1]));
+        assert_approx_eq!(f64, mps.truncation_error(), 0.5, epsilon = 0.00000003);
+    }
+
+    #[test]
+    fn test_mps_empty_register() {
+        assert_eq!(Mps::new(0, 16, 0.0, seed()).err().unwrap().kind(), ErrorKind::InvalidArgument);
+        assert_eq!(Mps::new(1, 0, 0.0, seed()).err().unwrap().kind(), ErrorKind::InvalidArgument);
+    }
+}
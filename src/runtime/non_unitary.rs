@@ -1,15 +1,21 @@
 pub(crate) mod measurement;
+pub(crate) mod pauli_measurement;
+pub(crate) mod povm;
 pub(crate) mod reset;
 
 use crate::runtime::ket::Ket;
 use crate::runtime::operator::Operator;
 use crate::runtime::non_unitary::measurement::Measurement;
+use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+use crate::runtime::non_unitary::povm::Povm;
 use crate::runtime::non_unitary::reset::Reset;
 use crate::runtime::register::Register;
 use crate::runtime::unitary::UnitaryOperator;
 
 pub(crate) enum NonUnitaryOperators {
     Measurement(Measurement),
+    PauliMeasurement(PauliMeasurement),
+    Povm(Povm),
     Reset(Reset),
 }
 
@@ -17,6 +23,8 @@ impl UnitaryOperator for NonUnitaryOperators {
     fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
         match self {
             NonUnitaryOperators::Measurement(nu) => nu.apply(ket, register),
+            NonUnitaryOperators::PauliMeasurement(nu) => nu.apply(ket, register),
+            NonUnitaryOperators::Povm(nu) => nu.apply(ket, register),
             NonUnitaryOperators::Reset(nu) => nu.apply(ket, register),
         }
     }
@@ -38,4 +46,6 @@ macro_rules! impl_from_trait {
 }
 
 impl_from_trait!(Measurement, Measurement);
+impl_from_trait!(PauliMeasurement, PauliMeasurement);
+impl_from_trait!(Povm, Povm);
 impl_from_trait!(Reset, Reset);
@@ -1,4 +1,5 @@
 use core::f64::consts::SQRT_2;
+use alloc::vec::Vec;
 use libm::sqrt;
 use rand::RngCore;
 use crate::complex::Complex;
@@ -38,6 +39,21 @@ impl Measurement {
             creg_bit,
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn wire(&self) -> usize {
+        self.wire
+    }
+
+    #[inline(always)]
+    pub(crate) fn basis(&self) -> MeasurementBasis {
+        self.basis
+    }
+
+    #[inline(always)]
+    pub(crate) fn creg_bit(&self) -> Option<usize> {
+        self.creg_bit
+    }
 }
 
 static HADAMARD: ConstSizedMatrix<1, Complex> = ConstSizedMatrix::new(
@@ -52,6 +68,12 @@ static HADAMARD: ConstSizedMatrix<1, Complex> = ConstSizedMatrix::new(
         ],
     ]
 );
+static S_GATE: ConstSizedMatrix<1, Complex> = ConstSizedMatrix::new(
+    [
+        [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
+        [Complex::new(0.0, 0.0), Complex::new(0.0, 1.0)],
+    ]
+);
 static S_INV_GATE: ConstSizedMatrix<1, Complex> = ConstSizedMatrix::new(
     [
         [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
@@ -59,17 +81,28 @@ static S_INV_GATE: ConstSizedMatrix<1, Complex> = ConstSizedMatrix::new(
     ]
 );
 
-impl UnitaryOperator for Measurement {
-    fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
-        let transformed = match self.basis {
+impl Measurement {
+    fn rotate(&self, ket: Ket) -> Ket {
+        match self.basis {
             MeasurementBasis::X => HADAMARD.apply(ket, &[ self.wire ]),
             MeasurementBasis::Y => HADAMARD.apply(S_INV_GATE.apply(ket, &[ self.wire ]), &[ self.wire ]),
             MeasurementBasis::Z => ket,
-        };
+        }
+    }
+
+    /// Returns the probabilities of the outcomes `|0>` and `|1>` in the basis of the measurement.
+    pub(crate) fn probabilities(&self, ket: &Ket) -> Vec<f64> {
+        let probability = self.rotate(ket.clone()).marginal_probabilities(&[ self.wire ])[1];
+
+        Vec::from([1.0 - probability, probability])
+    }
 
-        let probability: f64 = transformed.probability(self.wire);
-        let random_weight: f64 = transformed.seed.lock().next_u32() as f64 / u32::MAX as f64;
-        let state = probability - random_weight > 0.0;
+    /// Collapses the state onto the outcome, which is chosen from the probability of `|1>`.
+    fn collapse<F: FnOnce(f64) -> bool>(&self, ket: Ket, register: &mut Register, choose: F) -> Ket {
+        let transformed = self.rotate(ket);
+
+        let probability: f64 = transformed.marginal_probabilities(&[ self.wire ])[1];
+        let state = choose(probability);
 
         if let Some(bit) = &self.creg_bit {
             register.set(*bit, state);
@@ -88,14 +121,14 @@ impl UnitaryOperator for Measurement {
                 ])
             }
         } else {
-            if probability == 0.0 {
+            if probability == 1.0 {
                 ConstSizedMatrix::new([
                     [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
                     [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)],
                 ])
             } else {
                 ConstSizedMatrix::new([
-                    [Complex::new(sqrt(1.0 / probability), 0.0), Complex::new(0.0, 0.0)],
+                    [Complex::new(sqrt(1.0 / (1.0 - probability)), 0.0), Complex::new(0.0, 0.0)],
                     [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)],
                 ])
             }
@@ -103,10 +136,23 @@ impl UnitaryOperator for Measurement {
 
         match self.basis {
             MeasurementBasis::X => HADAMARD.apply(transformed, &[ self.wire ]),
-            MeasurementBasis::Y => HADAMARD.apply(S_INV_GATE.apply(transformed, &[ self.wire ]), &[ self.wire ]),
+            MeasurementBasis::Y => S_GATE.apply(HADAMARD.apply(transformed, &[ self.wire ]), &[ self.wire ]),
             MeasurementBasis::Z => transformed,
         }
     }
+
+    /// Collapses the state onto the outcome `0` or `1` instead of a random one.
+    pub(crate) fn apply_outcome(&self, ket: Ket, register: &mut Register, outcome: usize) -> Ket {
+        self.collapse(ket, register, |_| outcome == 1)
+    }
+}
+
+impl UnitaryOperator for Measurement {
+    fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
+        let random_weight: f64 = ket.seed.lock().next_u32() as f64 / u32::MAX as f64;
+
+        self.collapse(ket, register, |probability| probability - random_weight > 0.0)
+    }
 }
 
 #[cfg(test)]
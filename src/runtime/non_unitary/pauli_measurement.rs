@@ -0,0 +1,195 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::sqrt;
+use rand::RngCore;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::ket::Ket;
+use crate::runtime::non_unitary::measurement::MeasurementBasis;
+use crate::runtime::register::Register;
+use crate::runtime::unitary::UnitaryOperator;
+
+/// A projective measurement of a product of pauli operators, e.g. `Z0 Z1 Z2`, which collapses the state onto
+/// the `+1` or `-1` eigenspace of the product. The eigenvalue `-1` is written as `true` to the classical bit.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate)struct PauliMeasurement {
+    paulis: Vec<(usize, MeasurementBasis)>,
+    creg_bit: Option<usize>,
+}
+
+impl PauliMeasurement {
+    /// Creates the measurement of `paulis[i]` on `wires[i]`, where each pauli is one of `X`, `Y`, `Z` or `I`.
+    pub(crate) fn new(wires: &[usize], paulis: &str, creg_bit: Option<usize>) -> Result<PauliMeasurement> {
+        let len = paulis.chars().count();
+        if len != wires.len() {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                format!("Expecting {} paulis for {} qbits but got {}", wires.len(), wires.len(), len)
+            ))
+        }
+        let mut measured = Vec::with_capacity(len);
+        for (i, (wire, pauli)) in wires.iter().zip(paulis.chars()).enumerate() {
+            if wires[..i].contains(wire) {
+                return Err(QuantumError(
+                    ErrorKind::DuplicateWire,
+                    format!("Expecting distinct qbits but {} occurs twice", wire)
+                ))
+            }
+            match pauli {
+                'X' | 'x' => measured.push((*wire, MeasurementBasis::X)),
+                'Y' | 'y' => measured.push((*wire, MeasurementBasis::Y)),
+                'Z' | 'z' => measured.push((*wire, MeasurementBasis::Z)),
+                'I' | 'i' => {},
+                pauli => return Err(QuantumError(
+                    ErrorKind::InvalidArgument,
+                    format!("Expecting X, Y, Z or I as pauli but got {}", pauli)
+                )),
+            }
+        }
+        if measured.is_empty() {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one pauli other than the identity")
+            ))
+        }
+
+        Ok(PauliMeasurement {
+            paulis: measured,
+            creg_bit,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn paulis(&self) -> &[(usize, MeasurementBasis)] {
+        &self.paulis
+    }
+
+    #[inline(always)]
+    pub(crate) fn creg_bit(&self) -> Option<usize> {
+        self.creg_bit
+    }
+
+    /// Returns the state `P|state>` of the basis state after applying the pauli product,
+    /// with `X|b> = |!b>`, `Z|b> = (-1)^b |b>` and `Y = iXZ`.
+    fn product(&self, state: usize) -> (usize, Complex) {
+        let mut target = state;
+        let mut phase = Complex::new(1.0, 0.0);
+        for (wire, pauli) in self.paulis.iter() {
+            let bit = state & (0x1 << wire) > 0;
+            match pauli {
+                MeasurementBasis::X => target ^= 0x1 << wire,
+                MeasurementBasis::Y => {
+                    target ^= 0x1 << wire;
+                    phase = phase * Complex::new(0.0, if bit { -1.0 } else { 1.0 });
+                },
+                MeasurementBasis::Z => if bit {
+                    phase = phase * -1.0;
+                },
+            }
+        }
+
+        (target, phase)
+    }
+}
+
+impl PauliMeasurement {
+    /// Returns the state `P|psi>` and the probability of the eigenvalue `-1`, which is `(1 - <P>) / 2`.
+    fn flip(&self, ket: &Ket) -> (Vec<Complex>, f64) {
+        let mut flipped = vec![Complex::zero(); ket.vec.len()];
+        for (state, amplitude) in ket.vec.iter().enumerate() {
+            let (target, phase) = self.product(state);
+            flipped[target] = phase * Complex::from(*amplitude);
+        }
+        let expectation = ket.vec.iter()
+            .zip(flipped.iter())
+            .map(|(a, b)| Complex::from(*a).conjugate() * *b)
+            .fold(Complex::zero(), |sum, v| sum + v)
+            .re();
+
+        (flipped, ((1.0 - expectation) / 2.0).max(0.0).min(1.0))
+    }
+
+    /// Returns the probabilities of the eigenvalues `+1` and `-1`.
+    pub(crate) fn probabilities(&self, ket: &Ket) -> Vec<f64> {
+        let (_, probability) = self.flip(ket);
+
+        Vec::from([1.0 - probability, probability])
+    }
+
+    /// Projects the state onto the eigenspace `s = +-1` with `(1 + s P) / 2`, which is chosen from the probability of `-1`.
+    fn collapse<F: FnOnce(f64) -> bool>(&self, ket: Ket, register: &mut Register, choose: F) -> Ket {
+        let (flipped, probability) = self.flip(&ket);
+        let state = choose(probability);
+
+        if let Some(bit) = &self.creg_bit {
+            register.set(*bit, state);
+        }
+
+        let (sign, probability) = if state { (-1.0, probability) } else { (1.0, 1.0 - probability) };
+        let norm = 1.0 / (2.0 * sqrt(probability));
+        let mut ket = ket;
+        for (amplitude, flipped) in ket.vec.iter_mut().zip(flipped) {
+            *amplitude = ((Complex::from(*amplitude) + flipped * sign) * norm).into();
+        }
+
+        ket
+    }
+
+    /// Collapses the state onto the eigenvalue `+1` for the outcome `0` or `-1` for `1` instead of a random one.
+    pub(crate) fn apply_outcome(&self, ket: Ket, register: &mut Register, outcome: usize) -> Ket {
+        self.collapse(ket, register, |_| outcome == 1)
+    }
+}
+
+impl UnitaryOperator for PauliMeasurement {
+    fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
+        let random_weight: f64 = ket.seed.lock().next_u32() as f64 / u32::MAX as f64;
+
+        self.collapse(ket, register, |probability| probability - random_weight > 0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::{seed, Ket};
+    use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+    use crate::runtime::register::Register;
+    use crate::runtime::unitary::UnitaryOperator;
+
+    #[test]
+    fn test_pauli_measurement() {
+        // (|000> + |011>) / sqrt(2) has the stabilizers Z0 Z1 and X0 X1 with the eigenvalue +1, Y0 Y1 with -1
+        let amplitudes = (0..8)
+            .map(|i| if i == 0 || i == 3 { Complex::new(FRAC_1_SQRT_2, 0.0) } else { Complex::zero() })
+            .collect::<Vec<_>>();
+        let ket = Ket::from_amplitudes(&amplitudes, seed()).unwrap();
+        let mut register = Register::new(3);
+
+        let ket = PauliMeasurement::new(&[0, 1], "ZZ", Some(0)).unwrap().apply(ket, &mut register);
+        let ket = PauliMeasurement::new(&[1, 0], "XX", Some(1)).unwrap().apply(ket, &mut register);
+        let ket = PauliMeasurement::new(&[1, 2, 0], "YIY", Some(2)).unwrap().apply(ket, &mut register);
+        assert_eq!(false, *register.get(0).unwrap());
+        assert_eq!(false, *register.get(1).unwrap());
+        assert_eq!(true, *register.get(2).unwrap());
+        for (amplitude, expected) in ket.state().iter().zip(amplitudes.iter()) {
+            assert_approx_eq!(Complex, Complex::from(*amplitude), *expected, epsilon = tolerance(0.000001));
+        }
+
+        // Measuring Y0 on |0> collapses onto (|0> +- i|1>) / sqrt(2)
+        let ket = PauliMeasurement::new(&[0], "Y", Some(0)).unwrap().apply(Ket::new(1).unwrap(), &mut register);
+        let sign = if *register.get(0).unwrap() { -1.0 } else { 1.0 };
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0]), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, Complex::from(ket.state()[1]), Complex::new(0.0, sign * FRAC_1_SQRT_2), epsilon = tolerance(0.000001));
+
+        assert!(PauliMeasurement::new(&[0, 0], "ZZ", None).is_err());
+        assert!(PauliMeasurement::new(&[0, 1], "Z", None).is_err());
+        assert!(PauliMeasurement::new(&[0], "Q", None).is_err());
+        assert!(PauliMeasurement::new(&[0], "I", None).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use libm::sqrt;
+use rand::RngCore;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+use crate::runtime::ket::Ket;
+use crate::runtime::register::Register;
+use crate::runtime::unitary::UnitaryOperator;
+
+/// The largest deviation of `sum_i K_i† K_i` from the identity, which is accepted as complete.
+const EPSILON: f64 = 1e-8;
+
+/// A general measurement given by its kraus operators `K_i`, outcome `i` occurs with the probability
+/// `<psi|K_i† K_i|psi>` and leaves the state `K_i|psi>` renormalized. Bit j of the outcome is written to `creg_bits[j]`.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate)struct Povm {
+    wires: Vec<usize>,
+    kraus: Vec<DynamicSizedMatrix>,
+    creg_bits: Vec<usize>,
+}
+
+impl Povm {
+    /// Creates the measurement of the kraus operators on the wires, the first wire is the most significant bit
+    /// of the matrix index. Fails if the operators are not complete or there are too few bits for the outcomes.
+    pub(crate) fn new(wires: &[usize], kraus: Vec<DynamicSizedMatrix>, creg_bits: &[usize]) -> Result<Povm> {
+        if wires.is_empty() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one qbit")))
+        }
+        for (i, wire) in wires.iter().enumerate() {
+            if wires[..i].contains(wire) {
+                return Err(QuantumError(
+                    ErrorKind::DuplicateWire,
+                    format!("Expecting distinct qbits but {} occurs twice", wire)
+                ))
+            }
+        }
+        if kraus.is_empty() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one kraus operator")))
+        }
+        for operator in kraus.iter() {
+            if operator.size() != wires.len() {
+                return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                    "Expecting kraus operators on {} qbits but got one on {}", wires.len(), operator.size()
+                )))
+            }
+        }
+        let required = (usize::BITS - (kraus.len() - 1).leading_zeros()) as usize;
+        if creg_bits.len() < required {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting {} bits for {} outcomes but got {}", required, kraus.len(), creg_bits.len()
+            )))
+        }
+
+        let width = kraus[0].width();
+        for y in 0..width {
+            for x in 0..width {
+                let sum = kraus.iter()
+                    .flat_map(|k| (0..width).map(move |i| k.get(i, y).conjugate() * k.get(i, x)))
+                    .fold(Complex::zero(), |sum, v| sum + v);
+                let expected = Complex::new(if x == y { 1.0 } else { 0.0 }, 0.0);
+                if (sum - expected).abs() > EPSILON {
+                    return Err(QuantumError(ErrorKind::NonUnitaryMatrix, String::from(
+                        "Expecting the kraus operators to be complete, but sum K† K is not the identity"
+                    )))
+                }
+            }
+        }
+
+        Ok(Povm {
+            wires: Vec::from(wires),
+            kraus,
+            creg_bits: Vec::from(creg_bits),
+        })
+    }
+
+    /// Creates the projective measurement in the orthonormal basis given by the columns of the unitary,
+    /// outcome `i` projects onto the i-th column.
+    pub(crate) fn from_basis(wires: &[usize], unitary: &DynamicSizedMatrix, creg_bits: &[usize]) -> Result<Povm> {
+        let width = unitary.width();
+        let projectors = (0..width).map(|i| {
+            let matrix = (0..width * width)
+                .map(|idx| unitary.get(idx / width, i) * unitary.get(idx % width, i).conjugate())
+                .collect::<Vec<_>>();
+            DynamicSizedMatrix::new(matrix)
+        }).collect::<Result<Vec<_>>>()?;
+
+        Povm::new(wires, projectors, creg_bits)
+    }
+
+    #[inline(always)]
+    pub(crate) fn wires(&self) -> &[usize] {
+        &self.wires
+    }
+
+    #[inline(always)]
+    pub(crate) fn kraus(&self) -> &[DynamicSizedMatrix] {
+        &self.kraus
+    }
+
+    #[inline(always)]
+    pub(crate) fn creg_bits(&self) -> &[usize] {
+        &self.creg_bits
+    }
+
+    /// Writes the bits of the outcome to the classical register.
+    pub(crate) fn write(&self, outcome: usize, register: &mut Register) {
+        for (j, bit) in self.creg_bits.iter().enumerate() {
+            register.set(*bit, outcome & (0x1 << j) > 0);
+        }
+    }
+}
+
+impl Povm {
+    /// Returns the state `K_i|psi>` of the outcome and its probability `<psi|K_i† K_i|psi>`.
+    fn branch(&self, ket: &Ket, outcome: usize) -> (Ket, f64) {
+        let branch = self.kraus[outcome].apply(ket.clone(), &self.wires);
+        let probability = branch.vec.iter().map(|amplitude| amplitude.amplitude()).sum();
+
+        (branch, probability)
+    }
+
+    /// Returns the probability of each outcome.
+    pub(crate) fn probabilities(&self, ket: &Ket) -> Vec<f64> {
+        (0..self.kraus.len()).map(|outcome| self.branch(ket, outcome).1).collect()
+    }
+
+    fn collapse(&self, outcome: usize, mut branch: Ket, probability: f64, register: &mut Register) -> Ket {
+        self.write(outcome, register);
+        let scale = 1.0 / sqrt(probability);
+        for amplitude in branch.vec.iter_mut() {
+            *amplitude = (Complex::from(*amplitude) * scale).into();
+        }
+
+        branch
+    }
+
+    /// Applies the kraus operator of the outcome instead of a random one.
+    pub(crate) fn apply_outcome(&self, ket: Ket, register: &mut Register, outcome: usize) -> Ket {
+        let (branch, probability) = self.branch(&ket, outcome);
+
+        self.collapse(outcome, branch, probability, register)
+    }
+}
+
+impl UnitaryOperator for Povm {
+    fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
+        let random_weight: f64 = ket.seed.lock().next_u32() as f64 / u32::MAX as f64;
+        let mut cumulative = 0.0;
+        let mut chosen = None;
+        for outcome in 0..self.kraus.len() {
+            let (branch, probability) = self.branch(&ket, outcome);
+            if probability <= 0.0 {
+                continue
+            }
+            cumulative += probability;
+            chosen = Some((outcome, branch, probability));
+            if random_weight < cumulative {
+                break
+            }
+        }
+
+        // Rounding may leave the random weight above the total, the last possible outcome is used then
+        let (outcome, branch, probability) = chosen.unwrap();
+
+        self.collapse(outcome, branch, probability, register)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::f64::consts::{FRAC_1_SQRT_2, PI};
+    use float_cmp::assert_approx_eq;
+    use libm::{cos, sin, sqrt};
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+    use crate::runtime::ket::Ket;
+    use crate::runtime::non_unitary::povm::Povm;
+    use crate::runtime::register::Register;
+    use crate::runtime::simulator::{hadamard, pauli_x};
+    use crate::runtime::unitary::UnitaryOperator;
+
+    #[test]
+    fn test_basis_measurement() {
+        // |+> is always measured as the first column of the hadamard and stays unchanged
+        let ket = hadamard().apply(Ket::new(2).unwrap(), &[1]);
+        let mut register = Register::new(1);
+        let ket = Povm::from_basis(&[1], &hadamard(), &[0]).unwrap().apply(ket, &mut register);
+        assert_eq!(false, *register.get(0).unwrap());
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0]), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, Complex::from(ket.state()[2]), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_povm() {
+        // The trine povm has three outcomes with the elements 2/3 |phi_i><phi_i|, |1> never yields outcome 0
+        let angles = [0.0, 2.0 * PI / 3.0, 4.0 * PI / 3.0];
+        let kraus = angles.iter().map(|angle| {
+            let (c, s) = (cos(angle / 2.0), sin(angle / 2.0));
+            let f = sqrt(2.0 / 3.0);
+            DynamicSizedMatrix::new(Vec::from([
+                Complex::new(f * c * c, 0.0), Complex::new(f * c * s, 0.0),
+                Complex::new(f * s * c, 0.0), Complex::new(f * s * s, 0.0),
+            ])).unwrap()
+        }).collect::<Vec<_>>();
+        let povm = Povm::new(&[0], kraus, &[0, 1]).unwrap();
+
+        for _ in 0..20 {
+            let mut register = Register::new(2);
+            let ket = povm.apply(pauli_x().apply(Ket::new(1).unwrap(), &[0]), &mut register);
+            assert!(*register.get(0).unwrap() || *register.get(1).unwrap());
+            let norm: f64 = ket.state().iter().map(|amplitude| amplitude.amplitude()).sum();
+            assert_approx_eq!(f64, norm, 1.0, epsilon = tolerance(0.000001));
+        }
+
+        assert!(Povm::new(&[0], Vec::from([hadamard()]), &[]).is_ok());
+        assert!(Povm::new(&[0], Vec::from([hadamard(), hadamard()]), &[0]).is_err());
+        assert!(Povm::new(&[0], Vec::from([hadamard(), pauli_x()]), &[]).is_err());
+        assert!(Povm::new(&[0, 1], Vec::from([hadamard()]), &[]).is_err());
+        assert!(Povm::new(&[0, 0], Vec::from([hadamard()]), &[]).is_err());
+    }
+}
@@ -1,4 +1,6 @@
+use alloc::vec::Vec;
 use libm::sqrt;
+use rand::RngCore;
 use crate::complex::Complex;
 use crate::runtime::const_sized::matrix::ConstSizedMatrix;
 use crate::runtime::ket::Ket;
@@ -19,22 +21,83 @@ impl Reset {
             state,
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn wire(&self) -> usize {
+        self.wire
+    }
+
+    #[inline(always)]
+    pub(crate) fn state(&self) -> bool {
+        self.state
+    }
+}
+
+impl Reset {
+    /// Returns the probabilities of measuring `|0>` and `|1>` before the qbit is reset.
+    pub(crate) fn probabilities(&self, ket: &Ket) -> Vec<f64> {
+        let probability = ket.marginal_probabilities(&[ self.wire ])[1];
+
+        Vec::from([1.0 - probability, probability])
+    }
+
+    /// Collapses the qbit onto the measured state and flips it, if it differs from the reset state.
+    fn collapse(&self, ket: Ket, state: bool) -> Ket {
+        let probability = ket.marginal_probabilities(&[ self.wire ])[1];
+        let weight = if state { probability } else { 1.0 - probability };
+        let scale = if weight > 0.0 { sqrt(1.0 / weight) } else { 1.0 };
+        let (zero, one) = (Complex::new(0.0, 0.0), Complex::new(scale, 0.0));
+
+        // Projects onto the measured state and moves it to the reset state in one step
+        match (state, self.state) {
+            (false, false) => ConstSizedMatrix::new([[one, zero], [zero, zero]]),
+            (false, true) => ConstSizedMatrix::new([[zero, zero], [one, zero]]),
+            (true, false) => ConstSizedMatrix::new([[zero, one], [zero, zero]]),
+            (true, true) => ConstSizedMatrix::new([[zero, zero], [zero, one]]),
+        }.apply(ket, &[ self.wire ])
+    }
+
+    /// Resets the qbit, given that it is measured in the state of the outcome.
+    pub(crate) fn apply_outcome(&self, ket: Ket, outcome: usize) -> Ket {
+        self.collapse(ket, outcome == 1)
+    }
 }
 
 impl UnitaryOperator for Reset {
+    /// Measures the qbit and flips it, if the outcome differs from the reset state.
     fn apply(&self, ket: Ket, _: &mut Register) -> Ket {
-        let probability = ket.probability(self.wire);
-
-        if self.state {
-            ConstSizedMatrix::new([
-                [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)],
-                [Complex::new(0.0, 0.0), Complex::new(sqrt(1.0 / probability), 0.0)],
-            ])
-        } else {
-            ConstSizedMatrix::new([
-                [Complex::new(sqrt(1.0 / probability), 0.0), Complex::new(0.0, 0.0)],
-                [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)],
-            ])
-        }.apply(ket, &[ self.wire ])
+        let probability = ket.marginal_probabilities(&[ self.wire ])[1];
+        let random_weight: f64 = ket.seed.lock().next_u32() as f64 / u32::MAX as f64;
+
+        self.collapse(ket, probability - random_weight > 0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::complex::tolerance;
+    use float_cmp::assert_approx_eq;
+    use crate::runtime::ket::Ket;
+    use crate::runtime::non_unitary::reset::Reset;
+    use crate::runtime::register::Register;
+    use crate::runtime::unitary::UnitaryOperator;
+    use crate::toolbox::operator::hadamard::Hadamard;
+    use crate::toolbox::operator::pauli_x::PauliX;
+
+    #[test]
+    fn test_reset() {
+        let mut register = Register::new(0);
+        for _ in 0..50 {
+            let ket = Hadamard::new(0).apply(Ket::new(2).unwrap(), &mut register);
+            let ket = PauliX::new(1).apply(ket, &mut register);
+            let ket = Reset::new(0, false).apply(ket, &mut register);
+            let ket = Reset::new(1, false).apply(ket, &mut register);
+            assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.000001));
+            assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.000001));
+
+            let ket = Hadamard::new(0).apply(ket, &mut register);
+            let ket = Reset::new(0, true).apply(ket, &mut register);
+            assert_approx_eq!(f64, ket.probability(0).unwrap(), 1.0, epsilon = tolerance(0.000001));
+        }
     }
 }
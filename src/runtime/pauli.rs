@@ -0,0 +1,527 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+
+#[cfg(feature = "wasm-bindgen")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "wasm-bindgen")]
+use crate::api::{ComplexArray, PauliSumArray};
+
+/// Coefficients below this are dropped from a sum.
+const EPSILON: f64 = 1e-12;
+
+/// The largest number of qbits of a dense matrix of a sum, which has `4^n` entries.
+const MAX_MATRIX_SIZE: usize = 12;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Pauli {
+    X,
+    Y,
+    Z,
+}
+
+impl Pauli {
+    /// Returns the phase and the pauli of the product, where `None` is the identity.
+    pub(crate) fn product(self, other: Pauli) -> (Complex, Option<Pauli>) {
+        match (self, other) {
+            (Pauli::X, Pauli::Y) => (Complex::new(0.0, 1.0), Some(Pauli::Z)),
+            (Pauli::Y, Pauli::Z) => (Complex::new(0.0, 1.0), Some(Pauli::X)),
+            (Pauli::Z, Pauli::X) => (Complex::new(0.0, 1.0), Some(Pauli::Y)),
+            (Pauli::Y, Pauli::X) => (Complex::new(0.0, -1.0), Some(Pauli::Z)),
+            (Pauli::Z, Pauli::Y) => (Complex::new(0.0, -1.0), Some(Pauli::X)),
+            (Pauli::X, Pauli::Z) => (Complex::new(0.0, -1.0), Some(Pauli::Y)),
+            _ => (Complex::new(1.0, 0.0), None),
+        }
+    }
+}
+
+/// A product of single qbit paulis, e.g. `X0 Z2`. Identities are not stored.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PauliString(pub(crate) Vec<(usize, Pauli)>);
+
+impl PauliString {
+    /// Creates the product of `paulis[i]` on `qbits[i]`, the paulis are `X`, `Y`, `Z` or `I` in either case.
+    pub(crate) fn checked_new(qbits: &[usize], paulis: &str) -> Result<PauliString> {
+        if qbits.len() != paulis.chars().count() {
+            return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+                "Expecting a pauli for each of the {} qbits but got {}", qbits.len(), paulis
+            )))
+        }
+        let mut string = Vec::with_capacity(qbits.len());
+        for (i, (qbit, pauli)) in qbits.iter().zip(paulis.chars()).enumerate() {
+            if qbits[..i].contains(qbit) {
+                return Err(QuantumError(
+                    ErrorKind::DuplicateWire,
+                    format!("Expecting distinct qbits but {} occurs twice", qbit)
+                ))
+            }
+            match pauli {
+                'X' | 'x' => string.push((*qbit, Pauli::X)),
+                'Y' | 'y' => string.push((*qbit, Pauli::Y)),
+                'Z' | 'z' => string.push((*qbit, Pauli::Z)),
+                'I' | 'i' => {},
+                pauli => return Err(QuantumError(
+                    ErrorKind::InvalidArgument,
+                    format!("Expecting one of X, Y, Z and I but got {}", pauli)
+                )),
+            }
+        }
+        string.sort_by_key(|(qbit, _)| *qbit);
+
+        Ok(PauliString(string))
+    }
+
+    #[inline(always)]
+    pub(crate) fn paulis(&self) -> &[(usize, Pauli)] {
+        &self.0
+    }
+
+    /// Returns true, if the paulis of both strings are equal on every qbit, where both are not the identity.
+    fn qbit_wise_commuting(&self, other: &PauliString) -> bool {
+        self.0.iter().all(|(qbit, pauli)| other.0.iter().all(|(other_qbit, other_pauli)| qbit != other_qbit || pauli == other_pauli))
+    }
+
+    /// Returns the phase and the pauli string of the product `self * other`.
+    pub(crate) fn multiply(&self, other: &PauliString) -> (Complex, PauliString) {
+        let mut phase = Complex::new(1.0, 0.0);
+        let mut string = Vec::with_capacity(self.0.len() + other.0.len());
+        let (mut left, mut right) = (self.0.iter().peekable(), other.0.iter().peekable());
+        loop {
+            match (left.peek(), right.peek()) {
+                (Some((a, pauli_a)), Some((b, pauli_b))) if a == b => {
+                    let (factor, pauli) = pauli_a.product(*pauli_b);
+                    phase = phase * factor;
+                    if let Some(pauli) = pauli {
+                        string.push((*a, pauli));
+                    }
+                    left.next();
+                    right.next();
+                },
+                (Some((a, _)), Some((b, _))) if a > b => string.push(*right.next().unwrap()),
+                (Some(_), _) => string.push(*left.next().unwrap()),
+                (None, Some(_)) => string.push(*right.next().unwrap()),
+                (None, None) => break,
+            }
+        }
+
+        (phase, PauliString(string))
+    }
+
+    /// Returns the basis state and the phase of `P|idx>`.
+    pub(crate) fn apply(&self, idx: usize) -> (usize, Complex) {
+        let mut phase = Complex::new(1.0, 0.0);
+        let mut state = idx;
+        for (qbit, pauli) in self.0.iter() {
+            let bit = idx & (0x1 << qbit) > 0;
+            phase = match (pauli, bit) {
+                (Pauli::X, _) | (Pauli::Z, false) => phase,
+                (Pauli::Z, true) => -phase,
+                (Pauli::Y, false) => phase * Complex::new(0.0, 1.0),
+                (Pauli::Y, true) => phase * Complex::new(0.0, -1.0),
+            };
+            if *pauli != Pauli::Z {
+                state ^= 0x1 << qbit;
+            }
+        }
+
+        (state, phase)
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl PauliString {
+    /// Creates the product of `paulis[i]` on `qbits[i]`, the paulis are `X`, `Y`, `Z` or `I`
+    #[wasm_bindgen(constructor)]
+    pub fn new(qbits: Vec<usize>, paulis: &str) -> core::result::Result<PauliString, JsValue> {
+        Ok(PauliString::checked_new(&qbits, paulis)?)
+    }
+
+    /// Returns the product `self * other` as a sum with a single term, which carries the phase
+    pub fn product(&self, other: &PauliString) -> PauliSum {
+        let (phase, string) = self.multiply(other);
+
+        PauliSum::from_terms([(phase, string)])
+    }
+
+    /// Returns true, if the paulis of both strings are equal on every qbit, where both are not the identity.
+    /// Qbit-wise commuting strings can be measured together
+    #[wasm_bindgen(js_name = qubitWiseCommutes)]
+    pub fn qubit_wise_commutes(&self, other: &PauliString) -> bool {
+        self.qbit_wise_commuting(other)
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl PauliString {
+    /// Creates the product of `paulis[i]` on `qbits[i]`, the paulis are `X`, `Y`, `Z` or `I`.
+    /// Fails on other characters or if a qbit occurs twice.
+    pub fn new(qbits: &[usize], paulis: &str) -> Result<PauliString> {
+        PauliString::checked_new(qbits, paulis)
+    }
+
+    /// Returns the phase and the pauli string of the product `self * other`, e.g. `X0 * Y0 = i Z0`.
+    pub fn product(&self, other: &PauliString) -> (Complex, PauliString) {
+        self.multiply(other)
+    }
+
+    /// Returns true, if the paulis of both strings are equal on every qbit, where both are not the identity.
+    /// Qbit-wise commuting strings can be measured together.
+    pub fn qubit_wise_commutes(&self, other: &PauliString) -> bool {
+        self.qbit_wise_commuting(other)
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl PauliString {
+    /// Returns the qbits with a pauli other than the identity in ascending order
+    pub fn qbits(&self) -> Vec<usize> {
+        self.0.iter().map(|(qbit, _)| *qbit).collect()
+    }
+
+    /// Returns the number of qbits with a pauli other than the identity
+    pub fn weight(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true, if both strings commute, i.e. they differ on an even number of qbits, where both are not the identity
+    pub fn commutes(&self, other: &PauliString) -> bool {
+        self.0.iter()
+            .filter(|(qbit, pauli)| other.0.iter().any(|(other_qbit, other_pauli)| qbit == other_qbit && pauli != other_pauli))
+            .count() % 2 == 0
+    }
+}
+
+impl Display for PauliString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "I")
+        }
+        for (i, (qbit, pauli)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:?}{}", pauli, qbit)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A complex weighted sum of pauli strings, where every string occurs once.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PauliSum(pub(crate) Vec<(Complex, PauliString)>);
+
+impl PauliSum {
+    /// Creates the sum of the terms, merges equal strings and drops vanishing coefficients.
+    pub(crate) fn from_terms<I: IntoIterator<Item = (Complex, PauliString)>>(terms: I) -> PauliSum {
+        let mut sum: Vec<(Complex, PauliString)> = Vec::new();
+        for (coefficient, string) in terms {
+            match sum.iter_mut().find(|(_, other)| *other == string) {
+                Some((total, _)) => *total = *total + coefficient,
+                None => sum.push((coefficient, string)),
+            }
+        }
+        sum.retain(|(coefficient, _)| coefficient.abs() > EPSILON);
+
+        PauliSum(sum)
+    }
+
+    pub(crate) fn multiply(&self, other: &PauliSum) -> PauliSum {
+        PauliSum::from_terms(self.0.iter().flat_map(|(a, string_a)| other.0.iter().map(move |(b, string_b)| {
+            let (phase, string) = string_a.multiply(string_b);
+            (*a * *b * phase, string)
+        })))
+    }
+
+    /// Returns the matrix of the sum on `size` qbits, where qbit 0 is the most significant bit of the row and column
+    /// like for the matrices of gates, so the matrix applied to the wires `[0, 1, ..., size - 1]` acts like the sum.
+    pub(crate) fn to_dynamic_matrix(&self, size: usize) -> Result<DynamicSizedMatrix> {
+        if size < 1 {
+            return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one qbit for a matrix")))
+        }
+        if size > MAX_MATRIX_SIZE {
+            return Err(QuantumError(ErrorKind::SizeLimitExceeded, format!(
+                "Expecting at most {} qbits for a matrix but got {}", MAX_MATRIX_SIZE, size
+            )))
+        }
+        if self.size() > size {
+            return Err(QuantumError(ErrorKind::InvalidWire, format!(
+                "Expecting qbits below {} in the sum but was {}", size, self.size() - 1
+            )))
+        }
+
+        let reverse = |idx: usize| (0..size).fold(0, |reversed, qbit| reversed | (((idx >> qbit) & 0x1) << (size - 1 - qbit)));
+        let width = 0x1 << size;
+        let mut matrix = vec![Complex::zero(); width * width];
+        for x in 0..width {
+            for (coefficient, string) in self.0.iter() {
+                let (state, phase) = string.apply(reverse(x));
+                let y = reverse(state);
+                matrix[y * width + x] = matrix[y * width + x] + *coefficient * phase;
+            }
+        }
+
+        DynamicSizedMatrix::new(matrix)
+    }
+
+    fn checked_to_matrix(&self, size: usize) -> Result<Vec<Complex>> {
+        let matrix = self.to_dynamic_matrix(size)?;
+        let width = matrix.width();
+
+        Ok((0..width * width).map(|idx| matrix.get(idx / width, idx % width)).collect())
+    }
+
+    fn push_term(&mut self, coefficient: Complex, string: &PauliString) {
+        *self = PauliSum::from_terms(self.0.drain(..).chain([(coefficient, string.clone())]));
+    }
+
+    /// Returns true, if all coefficients are real.
+    fn hermitian(&self) -> bool {
+        self.0.iter().all(|(coefficient, _)| libm::fabs(coefficient.im()) <= EPSILON)
+    }
+
+    /// Sorts the terms greedily into groups, whose strings all commute qbit-wise.
+    fn groups(&self) -> Vec<PauliSum> {
+        let mut groups: Vec<PauliSum> = Vec::new();
+        for (coefficient, string) in self.0.iter() {
+            match groups.iter_mut().find(|group| group.0.iter().all(|(_, other)| string.qbit_wise_commuting(other))) {
+                Some(group) => group.0.push((*coefficient, string.clone())),
+                None => groups.push(PauliSum(vec![(*coefficient, string.clone())])),
+            }
+        }
+
+        groups
+    }
+}
+
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+impl PauliSum {
+    /// Returns the number of qbits the sum acts on, i.e. the highest qbit of a term plus one
+    pub fn size(&self) -> usize {
+        self.0.iter()
+            .flat_map(|(_, string)| string.qbits())
+            .max()
+            .map_or(0, |qbit| qbit + 1)
+    }
+
+    /// Returns the sum of both sums
+    pub fn add(&self, other: &PauliSum) -> PauliSum {
+        PauliSum::from_terms(self.0.iter().chain(other.0.iter()).cloned())
+    }
+
+    /// Returns the product `self * other`
+    pub fn product(&self, other: &PauliSum) -> PauliSum {
+        self.multiply(other)
+    }
+
+    /// Returns the commutator `self * other - other * self`
+    pub fn commutator(&self, other: &PauliSum) -> PauliSum {
+        let reversed = other.multiply(self);
+
+        PauliSum::from_terms(self.multiply(other).0.into_iter().chain(reversed.0.into_iter().map(|(c, string)| (-c, string))))
+    }
+
+    /// Returns true, if both sums commute
+    pub fn commutes(&self, other: &PauliSum) -> bool {
+        self.commutator(other).0.is_empty()
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+#[wasm_bindgen]
+impl PauliSum {
+    /// Creates a sum without terms
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> PauliSum {
+        PauliSum(Vec::new())
+    }
+
+    /// Adds the weighted string, an existing term of the same string is merged
+    #[wasm_bindgen(js_name = addTerm)]
+    pub fn add_term(&mut self, coefficient: Complex, string: &PauliString) {
+        self.push_term(coefficient, string)
+    }
+
+    /// Returns true, if all coefficients are real, so that the sum is hermitian
+    #[wasm_bindgen(js_name = isHermitian)]
+    pub fn is_hermitian(&self) -> bool {
+        self.hermitian()
+    }
+
+    /// Returns the matrix on `size` qbits row major, where qbit 0 is the most significant bit of the row and column
+    #[wasm_bindgen(js_name = toMatrix)]
+    pub fn to_matrix(&self, size: usize) -> core::result::Result<ComplexArray, JsValue> {
+        use wasm_bindgen::JsCast;
+
+        Ok(serde_wasm_bindgen::to_value(&self.checked_to_matrix(size)?)?.unchecked_into())
+    }
+
+    /// Sorts the terms greedily into groups, whose strings all commute qbit-wise, so each group can be measured together
+    #[wasm_bindgen(js_name = qubitWiseCommutingGroups)]
+    pub fn qubit_wise_commuting_groups(&self) -> PauliSumArray {
+        use wasm_bindgen::JsCast;
+
+        self.groups().into_iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+}
+
+#[cfg(not(feature = "wasm-bindgen"))]
+impl PauliSum {
+    /// Creates a sum without terms.
+    pub fn new() -> PauliSum {
+        PauliSum(Vec::new())
+    }
+
+    /// Adds the weighted string, an existing term of the same string is merged.
+    pub fn add_term(&mut self, coefficient: Complex, string: &PauliString) {
+        self.push_term(coefficient, string)
+    }
+
+    /// Returns true, if all coefficients are real, so that the sum is hermitian.
+    pub fn is_hermitian(&self) -> bool {
+        self.hermitian()
+    }
+
+    /// Returns the terms as `(coefficient, pauli string)`
+    pub fn terms(&self) -> &[(Complex, PauliString)] {
+        &self.0
+    }
+
+    /// Returns the matrix on `size` qbits row major, where qbit 0 is the most significant bit of the row and column
+    /// like for the matrices of gates. Fails if the sum acts on more qbits.
+    pub fn to_matrix(&self, size: usize) -> Result<Vec<Complex>> {
+        self.checked_to_matrix(size)
+    }
+
+    /// Sorts the terms greedily into groups, whose strings all commute qbit-wise, so each group can be measured together.
+    pub fn qubit_wise_commuting_groups(&self) -> Vec<PauliSum> {
+        self.groups()
+    }
+}
+
+impl Display for PauliSum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "0")
+        }
+        for (i, (coefficient, string)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "({}) {}", coefficient, string)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use float_cmp::assert_approx_eq;
+    use crate::runtime::ket::{seed, Ket};
+    use crate::runtime::pauli::{PauliString, PauliSum};
+    use crate::complex::Complex;
+    use crate::error::ErrorKind;
+
+    fn string(qbits: &[usize], paulis: &str) -> PauliString {
+        PauliString::checked_new(qbits, paulis).unwrap()
+    }
+
+    #[test]
+    fn test_pauli_string() {
+        let string = PauliString::checked_new(&[2, 0, 1], "ZYI").unwrap();
+        assert_eq!(format!("{}", string), "Y0 Z2");
+
+        // Y0 Z2 |101> = i * -1 |100>
+        let (state, phase) = string.apply(0b101);
+        assert_eq!(state, 0b100);
+        assert_approx_eq!(Complex, phase, Complex::new(0.0, -1.0));
+
+        // X0 Y1 * Y0 Z2 = (XY)0 Y1 Z2 = i Z0 Y1 Z2
+        let (phase, product) = PauliString::checked_new(&[0, 1], "XY").unwrap().multiply(&string);
+        assert_eq!(product, PauliString::checked_new(&[0, 1, 2], "ZYZ").unwrap());
+        assert_approx_eq!(Complex, phase, Complex::new(0.0, 1.0));
+
+        assert!(PauliString::checked_new(&[0, 0], "XX").is_err());
+        assert!(PauliString::checked_new(&[0], "A").is_err());
+        assert_eq!(PauliString::checked_new(&[2, 0, 1], "zyi").unwrap(), string);
+        assert!(PauliString::checked_new(&[0, 1], "X").is_err());
+    }
+
+    #[test]
+    fn test_commutation() {
+        assert!(string(&[0, 1], "XX").commutes(&string(&[0, 1], "YY")));
+        assert!(!string(&[0], "X").commutes(&string(&[0, 1], "ZZ")));
+        assert!(string(&[0, 1], "XZ").qbit_wise_commuting(&string(&[0, 2], "XY")));
+        assert!(!string(&[0, 1], "XX").qbit_wise_commuting(&string(&[0, 1], "YY")));
+    }
+
+    #[test]
+    fn test_pauli_sum() {
+        let x = PauliSum::from_terms([(Complex::new(1.0, 0.0), string(&[0], "X"))]);
+        let y = PauliSum::from_terms([(Complex::new(1.0, 0.0), string(&[0], "Y"))]);
+        assert_eq!(x.commutator(&y), PauliSum::from_terms([(Complex::new(0.0, 2.0), string(&[0], "Z"))]));
+        assert!(!x.commutes(&y));
+
+        // (X + Z)^2 = 2, as the anticommuting parts cancel
+        let mut h = x.clone();
+        h.push_term(Complex::new(1.0, 0.0), &string(&[0], "Z"));
+        assert_eq!(h.multiply(&h), PauliSum::from_terms([(Complex::new(2.0, 0.0), PauliString::default())]));
+        assert!(h.hermitian());
+        assert_eq!(format!("{}", h), "(1 + 0i) X0 + (1 + 0i) Z0");
+    }
+
+    #[test]
+    fn test_groups() {
+        let sum = PauliSum::from_terms([
+            (Complex::new(1.0, 0.0), string(&[0, 1], "ZZ")),
+            (Complex::new(0.5, 0.0), string(&[0, 1], "XX")),
+            (Complex::new(0.2, 0.0), string(&[0], "Z")),
+            (Complex::new(0.3, 0.0), string(&[1], "X")),
+        ]);
+        let groups = sum.groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0.len(), 2);
+        assert_eq!(groups[0].0[1].1, string(&[0], "Z"));
+    }
+
+    #[test]
+    fn test_matrix() {
+        let sum = PauliSum::from_terms([
+            (Complex::new(1.0, 0.0), string(&[0], "X")),
+            (Complex::new(0.5, 0.0), string(&[1], "Z")),
+        ]);
+        let matrix = sum.checked_to_matrix(2).unwrap();
+        let expected = [
+            0.5, 0.0, 1.0, 0.0,
+            0.0, -0.5, 0.0, 1.0,
+            1.0, 0.0, 0.5, 0.0,
+            0.0, 1.0, 0.0, -0.5,
+        ];
+        for (value, expected) in matrix.iter().zip(expected.iter()) {
+            assert_approx_eq!(Complex, *value, Complex::new(*expected, 0.0));
+        }
+
+        // Applied to the wires in ascending order the matrix acts like the sum
+        let ket = sum.to_dynamic_matrix(2).unwrap().apply(Ket::from_basis_state(2, 0b01, seed()).unwrap(), &[0, 1]);
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0b00]), Complex::new(1.0, 0.0));
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0b01]), Complex::new(0.5, 0.0));
+
+        assert!(sum.checked_to_matrix(1).is_err());
+        assert_eq!(sum.checked_to_matrix(0).unwrap_err().kind(), ErrorKind::InvalidArgument);
+        assert_eq!(sum.checked_to_matrix(13).unwrap_err().kind(), ErrorKind::SizeLimitExceeded);
+        assert_eq!(PauliSum::default().size(), 0);
+    }
+}
@@ -1,30 +1,61 @@
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use alloc::vec;
 use core::fmt::{Display, Formatter};
+use crate::error::{ErrorKind, QuantumError, Result};
 
 #[derive(Clone)]
 pub(crate) struct Register {
-    bits: Vec<bool>
+    bits: Vec<bool>,
+    /// The named registers given by their name, their first bit and their size
+    names: Vec<(String, usize, usize)>,
 }
 
 impl Register {
     pub(crate) fn new(size: usize) -> Register {
         Register {
             bits: vec![false; size],
+            names: Vec::new(),
         }
     }
 
+    pub(crate) fn from_bits(bits: Vec<bool>) -> Register {
+        Register {
+            bits,
+            names: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_names(mut self, names: Vec<(String, usize, usize)>) -> Register {
+        self.names = names;
+
+        self
+    }
+
     pub(crate) fn set(&mut self, index: usize, value: bool) {
-        self.bits.insert(index, value)
+        self.bits[index] = value;
     }
 
     pub(crate) fn get(&mut self, index: usize) -> Option<&bool> {
         self.bits.get(index)
     }
 
+    /// Returns the bit, fails if it is outside of the register.
+    pub(crate) fn bit(&self, index: usize) -> Result<bool> {
+        self.bits.get(index).copied().ok_or_else(|| QuantumError(
+            ErrorKind::InvalidWire,
+            format!("Expecting bits below {} but was {}", self.bits.len(), index)
+        ))
+    }
+
     pub(crate) fn bits(&self) -> &Vec<bool> {
         &self.bits
     }
+
+    pub(crate) fn names(&self) -> &[(String, usize, usize)] {
+        &self.names
+    }
 }
 
 impl Display for Register {
@@ -40,3 +71,18 @@ impl Display for Register {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::runtime::register::Register;
+
+    #[test]
+    fn test_set_overwrites() {
+        let mut register = Register::new(2);
+        register.set(0, true);
+        register.set(0, false);
+        register.set(1, true);
+        assert_eq!(register.bits(), &Vec::from([false, true]));
+    }
+}
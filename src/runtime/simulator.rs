@@ -0,0 +1,227 @@
+use core::f64::consts::FRAC_1_SQRT_2;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use libm::sqrt;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+use crate::runtime::ket::validate_qbit;
+use crate::runtime::non_unitary::measurement::MeasurementBasis;
+use crate::runtime::register::Register;
+use crate::toolbox::Tool;
+
+/// A representation of the quantum state, which is able to execute the [Tool](Tool)s of an algorithm
+/// without the need for a full state vector.
+pub(crate) trait Simulator: Clone {
+    /// Applies the matrix to the wires, the first wire is the most significant bit of the matrix index.
+    fn apply_matrix(&mut self, matrix: &DynamicSizedMatrix, wires: &[usize]) -> Result<()>;
+
+    /// Returns the probability of the wire to be measured as `|1>`.
+    fn probability(&self, wire: usize) -> Result<f64>;
+
+    /// Projects the wire onto the state and renormalizes with the probability of that state.
+    fn collapse(&mut self, wire: usize, state: bool, probability: f64);
+
+    /// Returns a random number in `[0, 1]`.
+    fn random(&self) -> f64;
+
+    /// Returns the squared norm `<psi|psi>` of the state, which differs from one after non unitary matrices.
+    fn squared_norm(&self) -> f64;
+
+    /// Multiplies all amplitudes with the factor.
+    fn scale(&mut self, factor: f64);
+
+    /// Measures the wire in the basis and collapses the state accordingly.
+    fn measure(&mut self, wire: usize, basis: MeasurementBasis) -> Result<bool> {
+        match basis {
+            MeasurementBasis::X => self.apply_matrix(&hadamard(), &[wire])?,
+            MeasurementBasis::Y => {
+                self.apply_matrix(&phase(false), &[wire])?;
+                self.apply_matrix(&hadamard(), &[wire])?;
+            },
+            MeasurementBasis::Z => {},
+        }
+
+        let probability = self.probability(wire)?;
+        let state = probability - self.random() > 0.0;
+        self.collapse(wire, state, if state { probability } else { 1.0 - probability });
+
+        match basis {
+            MeasurementBasis::X => self.apply_matrix(&hadamard(), &[wire])?,
+            MeasurementBasis::Y => {
+                self.apply_matrix(&hadamard(), &[wire])?;
+                self.apply_matrix(&phase(true), &[wire])?;
+            },
+            MeasurementBasis::Z => {},
+        }
+
+        Ok(state)
+    }
+
+    /// Measures the product of the paulis by rotating each wire into the Z basis and collecting the parity
+    /// on the last wire with CNOTs, the eigenvalue `-1` is returned as `true`.
+    fn measure_pauli(&mut self, paulis: &[(usize, MeasurementBasis)]) -> Result<bool> {
+        for (wire, basis) in paulis {
+            match basis {
+                MeasurementBasis::X => self.apply_matrix(&hadamard(), &[*wire])?,
+                MeasurementBasis::Y => {
+                    self.apply_matrix(&phase(false), &[*wire])?;
+                    self.apply_matrix(&hadamard(), &[*wire])?;
+                },
+                MeasurementBasis::Z => {},
+            }
+        }
+        let (last, _) = paulis[paulis.len() - 1];
+        for (wire, _) in &paulis[..paulis.len() - 1] {
+            self.apply_matrix(&controlled_pauli_x(), &[*wire, last])?;
+        }
+
+        let state = self.measure(last, MeasurementBasis::Z)?;
+
+        for (wire, _) in paulis[..paulis.len() - 1].iter().rev() {
+            self.apply_matrix(&controlled_pauli_x(), &[*wire, last])?;
+        }
+        for (wire, basis) in paulis {
+            match basis {
+                MeasurementBasis::X => self.apply_matrix(&hadamard(), &[*wire])?,
+                MeasurementBasis::Y => {
+                    self.apply_matrix(&hadamard(), &[*wire])?;
+                    self.apply_matrix(&phase(true), &[*wire])?;
+                },
+                MeasurementBasis::Z => {},
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Applies one of the kraus operators to the wires, chosen with the probability of its outcome,
+    /// and renormalizes the state. Returns the index of the chosen operator, fails if the state vanishes under all of them.
+    fn measure_kraus(&mut self, kraus: &[DynamicSizedMatrix], wires: &[usize]) -> Result<usize> {
+        let random = self.random();
+        let mut cumulative = 0.0;
+        let mut chosen = None;
+        for (outcome, operator) in kraus.iter().enumerate() {
+            let mut branch = self.clone();
+            branch.apply_matrix(operator, wires)?;
+            let probability = branch.squared_norm();
+            if probability <= 0.0 {
+                continue
+            }
+            cumulative += probability;
+            chosen = Some((outcome, branch, probability));
+            if random < cumulative {
+                break
+            }
+        }
+
+        let (outcome, branch, probability) = chosen.ok_or_else(|| QuantumError(
+            ErrorKind::NonUnitaryMatrix,
+            String::from("Expecting an outcome with a nonzero probability, but the state vanishes under every kraus operator")
+        ))?;
+        *self = branch;
+        self.scale(1.0 / sqrt(probability));
+
+        Ok(outcome)
+    }
+
+    /// Executes the tool, writing measurement results to the register.
+    fn apply_tool(&mut self, tool: &Tool, register: &mut Register) -> Result<()> {
+        match tool {
+            Tool::Measurement(measurement) => {
+                let state = self.measure(measurement.wire(), measurement.basis())?;
+                if let Some(bit) = measurement.creg_bit() {
+                    register.set(bit, state);
+                }
+            },
+            Tool::PauliMeasurement(measurement) => {
+                let state = self.measure_pauli(measurement.paulis())?;
+                if let Some(bit) = measurement.creg_bit() {
+                    register.set(bit, state);
+                }
+            },
+            Tool::Povm(povm) => {
+                let outcome = self.measure_kraus(povm.kraus(), povm.wires())?;
+                povm.write(outcome, register);
+            },
+            Tool::Reset(reset) => {
+                if self.measure(reset.wire(), MeasurementBasis::Z)? != reset.state() {
+                    self.apply_matrix(&pauli_x(), &[reset.wire()])?;
+                }
+            },
+            Tool::Classical(operation) => operation.apply(register),
+            Tool::Conditional(conditional) => if conditional.condition().holds(register) {
+                self.apply_tool(conditional.tool(), register)?;
+            },
+            tool => if let Some(operator) = tool.unitary() {
+                let enabled = match operator.classical_control() {
+                    Some(bit) => register.bit(bit)?,
+                    None => true,
+                };
+                if enabled {
+                    self.apply_matrix(operator.matrix(), operator.wires())?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that the matrix acts on as many wires as given and that all of them belong to a state of the size.
+pub(crate) fn validate_wires(size: usize, matrix: &DynamicSizedMatrix, wires: &[usize]) -> Result<()> {
+    if matrix.size() != wires.len() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting a matrix acting on {} wires but got one acting on {}", wires.len(), matrix.size()
+        )))
+    }
+    for wire in wires {
+        validate_qbit(size, *wire)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn hadamard() -> DynamicSizedMatrix {
+    DynamicSizedMatrix::new(Vec::from([
+        Complex::new(FRAC_1_SQRT_2, 0.0), Complex::new(FRAC_1_SQRT_2, 0.0),
+        Complex::new(FRAC_1_SQRT_2, 0.0), Complex::new(-FRAC_1_SQRT_2, 0.0),
+    ])).unwrap()
+}
+
+pub(crate) fn pauli_x() -> DynamicSizedMatrix {
+    DynamicSizedMatrix::new(Vec::from([
+        Complex::new(0.0, 0.0), Complex::new(1.0, 0.0),
+        Complex::new(1.0, 0.0), Complex::new(0.0, 0.0),
+    ])).unwrap()
+}
+
+/// The controlled pauli x, the first wire is the control.
+pub(crate) fn controlled_pauli_x() -> DynamicSizedMatrix {
+    let (zero, one) = (Complex::new(0.0, 0.0), Complex::new(1.0, 0.0));
+    DynamicSizedMatrix::new(Vec::from([
+        one, zero, zero, zero,
+        zero, one, zero, zero,
+        zero, zero, zero, one,
+        zero, zero, one, zero,
+    ])).unwrap()
+}
+
+/// The phase gate `S`, or its inverse if `positive` is false.
+pub(crate) fn phase(positive: bool) -> DynamicSizedMatrix {
+    DynamicSizedMatrix::new(Vec::from([
+        Complex::new(1.0, 0.0), Complex::new(0.0, 0.0),
+        Complex::new(0.0, 0.0), Complex::new(0.0, if positive { 1.0 } else { -1.0 }),
+    ])).unwrap()
+}
+
+pub(crate) fn swap() -> DynamicSizedMatrix {
+    let (zero, one) = (Complex::new(0.0, 0.0), Complex::new(1.0, 0.0));
+    DynamicSizedMatrix::new(Vec::from([
+        one, zero, zero, zero,
+        zero, zero, one, zero,
+        zero, one, zero, zero,
+        zero, zero, zero, one,
+    ])).unwrap()
+}
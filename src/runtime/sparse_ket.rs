@@ -0,0 +1,203 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use libm::sqrt;
+use rand::RngCore;
+use rand::rngs::SmallRng;
+use spin::Mutex;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+use crate::runtime::ket::{validate_qbit, Ket};
+use crate::runtime::simulator::{validate_wires, Simulator};
+
+/// Amplitudes with a smaller squared magnitude are dropped from the map.
+const EPSILON: f64 = 1e-30;
+
+/// A state vector, which only stores the nonzero amplitudes, indexed by their basis state.
+#[derive(Clone, Debug)]
+pub(crate) struct SparseKet {
+    size: usize,
+    amplitudes: BTreeMap<usize, Complex>,
+    seed: Arc<Mutex<SmallRng>>,
+}
+
+impl SparseKet {
+    pub(crate) fn new(size: usize, seed: Arc<Mutex<SmallRng>>) -> Result<SparseKet> {
+        if size < 1 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one qbit, but the register has none")
+            ))
+        }
+        if size > 8 * size_of::<usize>() {
+            return Err(QuantumError(ErrorKind::SizeLimitExceeded, format!(
+                "Size must be at most {}, but was {}", 8 * size_of::<usize>(), size
+            )))
+        }
+        let mut amplitudes = BTreeMap::new();
+        amplitudes.insert(0, Complex::new(1.0, 0.0));
+
+        Ok(SparseKet { size, amplitudes, seed })
+    }
+
+    /// Creates a new SparseKet from the nonzero amplitudes of the state vector.
+    pub(crate) fn from_ket(ket: &Ket) -> Result<SparseKet> {
+        let mut sparse = SparseKet::new(ket.size(), ket.seed.clone())?;
+        sparse.amplitudes = ket.state().iter()
+            .map(|amplitude| Complex::from(*amplitude))
+            .enumerate()
+            .filter(|(_, amplitude)| amplitude.amplitude() > EPSILON)
+            .collect();
+
+        Ok(sparse)
+    }
+
+    #[inline(always)]
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of stored, nonzero amplitudes.
+    pub(crate) fn len(&self) -> usize {
+        self.amplitudes.len()
+    }
+
+    /// Returns the basis states with a nonzero amplitude in ascending order.
+    pub(crate) fn indices(&self) -> Vec<usize> {
+        self.amplitudes.keys().copied().collect()
+    }
+
+    pub(crate) fn state(&self, idx: usize) -> Complex {
+        self.amplitudes.get(&idx).copied().unwrap_or(Complex::zero())
+    }
+
+    pub(crate) fn probabilities(&self) -> Vec<f64> {
+        let mut probabilities = vec![0.0; self.size];
+        for (state_id, amplitude) in self.amplitudes.iter() {
+            for bit_id in 0..self.size {
+                if state_id & (0x1 << bit_id) > 0 {
+                    probabilities[bit_id] += amplitude.amplitude();
+                }
+            }
+        }
+
+        probabilities
+    }
+}
+
+impl Simulator for SparseKet {
+    fn apply_matrix(&mut self, matrix: &DynamicSizedMatrix, wires: &[usize]) -> Result<()> {
+        validate_wires(self.size, matrix, wires)?;
+
+        let k = wires.len();
+        // Maps an index of the matrix onto the bits of a basis state, the first wire is the most significant bit
+        let spread = |m: usize| wires.iter().enumerate()
+            .filter(|(i, _)| m & (0x1 << (k - 1 - i)) > 0)
+            .fold(0usize, |state, (_, wire)| state | (0x1 << wire));
+        let mask = spread(matrix.width() - 1);
+
+        let mut amplitudes: BTreeMap<usize, Complex> = BTreeMap::new();
+        for (state_id, amplitude) in self.amplitudes.iter() {
+            let x = (0..matrix.width()).find(|m| spread(*m) == state_id & mask).unwrap();
+            let base = state_id & !mask;
+            for y in 0..matrix.width() {
+                let value = matrix.get(y, x);
+                if value.amplitude() > 0.0 {
+                    let target = amplitudes.entry(base | spread(y)).or_insert(Complex::zero());
+                    *target = *target + value * *amplitude;
+                }
+            }
+        }
+        amplitudes.retain(|_, amplitude| amplitude.amplitude() > EPSILON);
+
+        self.amplitudes = amplitudes;
+
+        Ok(())
+    }
+
+    fn probability(&self, wire: usize) -> Result<f64> {
+        validate_qbit(self.size, wire)?;
+
+        Ok(self.amplitudes.iter()
+            .filter(|(state_id, _)| *state_id & (0x1 << wire) > 0)
+            .map(|(_, amplitude)| amplitude.amplitude())
+            .sum())
+    }
+
+    fn collapse(&mut self, wire: usize, state: bool, probability: f64) {
+        let scale = Complex::new(if probability > 0.0 { 1.0 / sqrt(probability) } else { 1.0 }, 0.0);
+        self.amplitudes.retain(|state_id, _| (state_id & (0x1 << wire) > 0) == state);
+        for amplitude in self.amplitudes.values_mut() {
+            *amplitude = *amplitude * scale;
+        }
+    }
+
+    fn random(&self) -> f64 {
+        self.seed.lock().next_u32() as f64 / u32::MAX as f64
+    }
+
+    fn squared_norm(&self) -> f64 {
+        self.amplitudes.values().map(|amplitude| amplitude.amplitude()).sum()
+    }
+
+    fn scale(&mut self, factor: f64) {
+        for amplitude in self.amplitudes.values_mut() {
+            *amplitude = *amplitude * factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::Complex;
+    use crate::error::ErrorKind;
+    use crate::runtime::dynamic_sized::matrix::DynamicSizedMatrix;
+    use crate::runtime::ket::seed;
+    use crate::runtime::simulator::{controlled_pauli_x, hadamard, pauli_x, Simulator};
+    use crate::runtime::sparse_ket::SparseKet;
+
+    #[test]
+    fn test_sparse_ghz_wide() {
+        let mut ket = SparseKet::new(40, seed()).unwrap();
+        ket.apply_matrix(&hadamard(), &[3]).unwrap();
+        ket.apply_matrix(&controlled_pauli_x(), &[3, 39]).unwrap();
+        ket.apply_matrix(&controlled_pauli_x(), &[39, 0]).unwrap();
+
+        assert_eq!(ket.len(), 2);
+        assert_eq!(ket.indices(), Vec::from([0, (0x1 << 39) | (0x1 << 3) | 0x1]));
+        assert_approx_eq!(f64, ket.state(0x1 << 39 | 0x1 << 3 | 0x1).re(), FRAC_1_SQRT_2, epsilon = 1e-12);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.5, epsilon = 1e-12);
+
+        ket.apply_matrix(&hadamard(), &[3]).unwrap();
+        assert_eq!(ket.len(), 4);
+    }
+
+    #[test]
+    fn test_sparse_invalid_wires() {
+        let mut ket = SparseKet::new(2, seed()).unwrap();
+        assert_eq!(ket.probability(2).unwrap_err().kind(), ErrorKind::InvalidWire);
+        assert_eq!(ket.apply_matrix(&hadamard(), &[2]).unwrap_err().kind(), ErrorKind::InvalidWire);
+        assert_eq!(ket.apply_matrix(&controlled_pauli_x(), &[0]).unwrap_err().kind(), ErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn test_sparse_vanishing_kraus() {
+        let projector = DynamicSizedMatrix::new(Vec::from([
+            Complex::new(0.0, 0.0), Complex::new(0.0, 0.0),
+            Complex::new(0.0, 0.0), Complex::new(1.0, 0.0),
+        ])).unwrap();
+        // Projecting |00> onto |1> leaves no amplitude, so no kraus operator has a nonzero probability
+        let mut ket = SparseKet::new(2, seed()).unwrap();
+        ket.apply_matrix(&projector, &[0]).unwrap();
+        let error = ket.measure_kraus(&[projector, pauli_x()], &[1]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NonUnitaryMatrix);
+    }
+}
@@ -0,0 +1,401 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::RngCore;
+use rand::rngs::SmallRng;
+use spin::Mutex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::ket::validate_qbit;
+use crate::runtime::non_unitary::measurement::MeasurementBasis;
+use crate::runtime::register::Register;
+use crate::toolbox::controlled::Controlled;
+use crate::toolbox::operator::Operator;
+use crate::toolbox::Tool;
+
+/// A stabilizer state stored as tableau after Aaronson and Gottesman.
+///
+/// The rows `0..n` hold the destabilizers, the rows `n..2n` the stabilizers and the row `2n` is scratch space
+/// for deterministic measurements.
+#[derive(Clone, Debug)]
+pub(crate) struct Tableau {
+    size: usize,
+    x: Vec<bool>,
+    z: Vec<bool>,
+    r: Vec<bool>,
+    seed: Arc<Mutex<SmallRng>>,
+}
+
+impl Tableau {
+    pub(crate) fn new(size: usize, seed: Arc<Mutex<SmallRng>>) -> Result<Tableau> {
+        if size < 1 {
+            return Err(QuantumError(
+                ErrorKind::InvalidArgument,
+                String::from("Expecting at least one qbit, but the register has none")
+            ))
+        }
+        let rows = 2 * size + 1;
+        let mut x = vec![false; rows * size];
+        let mut z = vec![false; rows * size];
+        for wire in 0..size {
+            x[wire * size + wire] = true;
+            z[(wire + size) * size + wire] = true;
+        }
+
+        Ok(Tableau {
+            size,
+            x,
+            z,
+            r: vec![false; rows],
+            seed,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline(always)]
+    fn idx(&self, row: usize, wire: usize) -> usize {
+        row * self.size + wire
+    }
+
+    pub(crate) fn hadamard(&mut self, a: usize) {
+        for row in 0..2 * self.size {
+            let i = self.idx(row, a);
+            self.r[row] ^= self.x[i] & self.z[i];
+            let x = self.x[i];
+            self.x[i] = self.z[i];
+            self.z[i] = x;
+        }
+    }
+
+    pub(crate) fn phase(&mut self, a: usize) {
+        for row in 0..2 * self.size {
+            let i = self.idx(row, a);
+            self.r[row] ^= self.x[i] & self.z[i];
+            self.z[i] ^= self.x[i];
+        }
+    }
+
+    pub(crate) fn phase_dagger(&mut self, a: usize) {
+        self.pauli_z(a);
+        self.phase(a);
+    }
+
+    pub(crate) fn pauli_x(&mut self, a: usize) {
+        for row in 0..2 * self.size {
+            let i = self.idx(row, a);
+            self.r[row] ^= self.z[i];
+        }
+    }
+
+    pub(crate) fn pauli_y(&mut self, a: usize) {
+        for row in 0..2 * self.size {
+            let i = self.idx(row, a);
+            self.r[row] ^= self.x[i] ^ self.z[i];
+        }
+    }
+
+    pub(crate) fn pauli_z(&mut self, a: usize) {
+        for row in 0..2 * self.size {
+            let i = self.idx(row, a);
+            self.r[row] ^= self.x[i];
+        }
+    }
+
+    pub(crate) fn pauli_x_root(&mut self, a: usize) {
+        self.hadamard(a);
+        self.phase(a);
+        self.hadamard(a);
+    }
+
+    pub(crate) fn controlled_pauli_x(&mut self, a: usize, b: usize) {
+        for row in 0..2 * self.size {
+            let (ia, ib) = (self.idx(row, a), self.idx(row, b));
+            self.r[row] ^= self.x[ia] & self.z[ib] & !(self.x[ib] ^ self.z[ia]);
+            self.x[ib] ^= self.x[ia];
+            self.z[ia] ^= self.z[ib];
+        }
+    }
+
+    pub(crate) fn controlled_pauli_y(&mut self, a: usize, b: usize) {
+        self.phase_dagger(b);
+        self.controlled_pauli_x(a, b);
+        self.phase(b);
+    }
+
+    pub(crate) fn controlled_pauli_z(&mut self, a: usize, b: usize) {
+        self.hadamard(b);
+        self.controlled_pauli_x(a, b);
+        self.hadamard(b);
+    }
+
+    pub(crate) fn swap(&mut self, a: usize, b: usize) {
+        self.controlled_pauli_x(a, b);
+        self.controlled_pauli_x(b, a);
+        self.controlled_pauli_x(a, b);
+    }
+
+    /// Multiplies the pauli string of row `i` onto row `h`, keeping track of the sign.
+    fn row_sum(&mut self, h: usize, i: usize) {
+        let mut sum = 2 * self.r[h] as i32 + 2 * self.r[i] as i32;
+        for wire in 0..self.size {
+            let (ih, ii) = (self.idx(h, wire), self.idx(i, wire));
+            let (x1, z1, x2, z2) = (self.x[ii], self.z[ii], self.x[ih], self.z[ih]);
+            sum += match (x1, z1) {
+                (false, false) => 0,
+                (true, true) => z2 as i32 - x2 as i32,
+                (true, false) => z2 as i32 * (2 * x2 as i32 - 1),
+                (false, true) => x2 as i32 * (1 - 2 * z2 as i32),
+            };
+            self.x[ih] ^= x1;
+            self.z[ih] ^= z1;
+        }
+        self.r[h] = sum.rem_euclid(4) == 2;
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        for wire in 0..self.size {
+            let i = self.idx(row, wire);
+            self.x[i] = false;
+            self.z[i] = false;
+        }
+        self.r[row] = false;
+    }
+
+    /// Returns the stabilizer which anticommutes with `Z` on the wire, if the outcome is random.
+    fn random_stabilizer(&self, a: usize) -> Option<usize> {
+        (self.size..2 * self.size).find(|row| self.x[self.idx(*row, a)])
+    }
+
+    fn measure_z(&mut self, a: usize) -> bool {
+        let n = self.size;
+        if let Some(p) = self.random_stabilizer(a) {
+            for row in 0..2 * n {
+                if row != p && self.x[self.idx(row, a)] {
+                    self.row_sum(row, p);
+                }
+            }
+            for wire in 0..n {
+                let (from, to) = (self.idx(p, wire), self.idx(p - n, wire));
+                self.x[to] = self.x[from];
+                self.z[to] = self.z[from];
+            }
+            self.r[p - n] = self.r[p];
+            self.clear_row(p);
+            let i = self.idx(p, a);
+            self.z[i] = true;
+            self.r[p] = self.seed.lock().next_u32() & 0x1 == 0x1;
+
+            self.r[p]
+        } else {
+            let scratch = 2 * n;
+            self.clear_row(scratch);
+            for row in 0..n {
+                if self.x[self.idx(row, a)] {
+                    self.row_sum(scratch, row + n);
+                }
+            }
+
+            self.r[scratch]
+        }
+    }
+
+    /// Measures the wire in the basis and collapses the state accordingly.
+    pub(crate) fn measure(&mut self, a: usize, basis: MeasurementBasis) -> bool {
+        match basis {
+            MeasurementBasis::X => {
+                self.hadamard(a);
+                let state = self.measure_z(a);
+                self.hadamard(a);
+
+                state
+            },
+            MeasurementBasis::Y => {
+                self.phase_dagger(a);
+                self.hadamard(a);
+                let state = self.measure_z(a);
+                self.hadamard(a);
+                self.phase(a);
+
+                state
+            },
+            MeasurementBasis::Z => self.measure_z(a),
+        }
+    }
+
+    /// Measures the product of the paulis by rotating each wire into the Z basis and collecting the parity
+    /// on the last wire with CNOTs, the eigenvalue `-1` is returned as `true`.
+    pub(crate) fn measure_pauli(&mut self, paulis: &[(usize, MeasurementBasis)]) -> bool {
+        for (wire, basis) in paulis {
+            match basis {
+                MeasurementBasis::X => self.hadamard(*wire),
+                MeasurementBasis::Y => {
+                    self.phase_dagger(*wire);
+                    self.hadamard(*wire);
+                },
+                MeasurementBasis::Z => {},
+            }
+        }
+        let (last, _) = paulis[paulis.len() - 1];
+        for (wire, _) in &paulis[..paulis.len() - 1] {
+            self.controlled_pauli_x(*wire, last);
+        }
+
+        let state = self.measure_z(last);
+
+        for (wire, _) in paulis[..paulis.len() - 1].iter().rev() {
+            self.controlled_pauli_x(*wire, last);
+        }
+        for (wire, basis) in paulis {
+            match basis {
+                MeasurementBasis::X => self.hadamard(*wire),
+                MeasurementBasis::Y => {
+                    self.hadamard(*wire);
+                    self.phase(*wire);
+                },
+                MeasurementBasis::Z => {},
+            }
+        }
+
+        state
+    }
+
+    /// Returns the probability of the wire to be measured as `|1>`, which is either 0, 0.5 or 1.
+    pub(crate) fn probability(&self, a: usize) -> Result<f64> {
+        validate_qbit(self.size, a)?;
+
+        Ok(if self.random_stabilizer(a).is_some() {
+            0.5
+        } else if self.clone().measure_z(a) {
+            1.0
+        } else {
+            0.0
+        })
+    }
+
+    /// Returns the generators of the stabilizer group, the first character after the sign belongs to qbit 0.
+    pub(crate) fn stabilizers(&self) -> Vec<String> {
+        (self.size..2 * self.size).map(|row| {
+            let mut stabilizer = String::with_capacity(self.size + 1);
+            stabilizer.push(if self.r[row] { '-' } else { '+' });
+            for wire in 0..self.size {
+                let i = self.idx(row, wire);
+                stabilizer.push(match (self.x[i], self.z[i]) {
+                    (false, false) => 'I',
+                    (true, false) => 'X',
+                    (true, true) => 'Y',
+                    (false, true) => 'Z',
+                });
+            }
+
+            stabilizer
+        }).collect()
+    }
+
+    /// Executes the tool, fails if the tool is not part of the clifford group.
+    pub(crate) fn apply_tool(&mut self, tool: &Tool, register: &mut Register) -> Result<()> {
+        match tool {
+            Tool::Measurement(measurement) => {
+                let state = self.measure(measurement.wire(), measurement.basis());
+                if let Some(bit) = measurement.creg_bit() {
+                    register.set(bit, state);
+                }
+            },
+            Tool::PauliMeasurement(measurement) => {
+                let state = self.measure_pauli(measurement.paulis());
+                if let Some(bit) = measurement.creg_bit() {
+                    register.set(bit, state);
+                }
+            },
+            Tool::Reset(reset) => {
+                if self.measure(reset.wire(), MeasurementBasis::Z) != reset.state() {
+                    self.pauli_x(reset.wire());
+                }
+            },
+            Tool::Classical(operation) => operation.apply(register),
+            Tool::Conditional(conditional) => {
+                // A gate outside of the clifford group fails, even if its condition does not hold
+                clifford(conditional.tool())?;
+                if conditional.condition().holds(register) {
+                    self.apply_tool(conditional.tool(), register)?;
+                }
+            },
+            Tool::None => {},
+            tool => {
+                let update = clifford(tool)?;
+                let operator = tool.unitary().unwrap();
+                let enabled = match operator.classical_control() {
+                    Some(bit) => register.bit(bit)?,
+                    None => true,
+                };
+                if enabled {
+                    update(self, operator.wires());
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the tableau update of the tool, fails if the tool is not part of the clifford group.
+fn clifford(tool: &Tool) -> Result<fn(&mut Tableau, &[usize])> {
+    let clifford: fn(&mut Tableau, &[usize]) = match tool {
+        Tool::Operator(Operator::Hadamard(_)) => |t, w| t.hadamard(w[0]),
+        Tool::Operator(Operator::PauliX(_)) => |t, w| t.pauli_x(w[0]),
+        Tool::Operator(Operator::PauliXRoot(_)) => |t, w| t.pauli_x_root(w[0]),
+        Tool::Operator(Operator::PauliY(_)) => |t, w| t.pauli_y(w[0]),
+        Tool::Operator(Operator::PauliZ(_)) => |t, w| t.pauli_z(w[0]),
+        Tool::Operator(Operator::Phase(_)) => |t, w| t.phase(w[0]),
+        Tool::Operator(Operator::PhaseDagger(_)) => |t, w| t.phase_dagger(w[0]),
+        Tool::Operator(Operator::Swap(_)) => |t, w| t.swap(w[0], w[1]),
+        Tool::Controlled(Controlled::ControlledPauliX(_)) => |t, w| t.controlled_pauli_x(w[0], w[1]),
+        Tool::Controlled(Controlled::ControlledPauliY(_)) => |t, w| t.controlled_pauli_y(w[0], w[1]),
+        Tool::Controlled(Controlled::ControlledPauliZ(_)) => |t, w| t.controlled_pauli_z(w[0], w[1]),
+        tool => return Err(QuantumError(ErrorKind::Unsupported, format!(
+            "{} is not a clifford gate and can not be simulated on a stabilizer tableau",
+            tool.name()
+        ))),
+    };
+
+    Ok(clifford)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use crate::runtime::ket::seed;
+    use crate::runtime::non_unitary::measurement::MeasurementBasis;
+    use crate::runtime::stabilizer::Tableau;
+
+    #[test]
+    fn test_bell_stabilizers() {
+        let mut tableau = Tableau::new(2, seed()).unwrap();
+        tableau.hadamard(0);
+        tableau.controlled_pauli_x(0, 1);
+
+        assert_eq!(tableau.stabilizers(), Vec::from([String::from("+XX"), String::from("+ZZ")]));
+        assert_eq!(tableau.probability(1).unwrap(), 0.5);
+        let state = tableau.measure(0, MeasurementBasis::Z);
+        assert_eq!(tableau.probability(1).unwrap(), if state { 1.0 } else { 0.0 });
+    }
+
+    #[test]
+    fn test_basis_measurements() {
+        let mut tableau = Tableau::new(2, seed()).unwrap();
+        tableau.hadamard(0);
+        tableau.phase(1);
+        tableau.hadamard(1);
+        tableau.phase(1);
+
+        assert_eq!(tableau.measure(0, MeasurementBasis::X), false);
+        assert_eq!(tableau.measure(1, MeasurementBasis::Y), false);
+        tableau.pauli_x(1);
+        assert_eq!(tableau.measure(1, MeasurementBasis::Y), true);
+    }
+}
@@ -0,0 +1,70 @@
+use alloc::boxed::Box;
+use alloc::format;
+use tinyvec::TinyVec;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::classical::Condition;
+use crate::runtime::ket::Ket;
+use crate::runtime::register::Register;
+use crate::runtime::unitary::UnitaryOperator;
+use crate::toolbox::Tool;
+
+/// A gate, which is only applied if the classical condition holds when it is reached.
+#[derive(Clone, Debug)]
+pub(crate) struct Conditional {
+    condition: Condition,
+    tool: Box<Tool>,
+}
+
+impl Conditional {
+    /// Fails if the tool is not a unitary gate.
+    pub(crate) fn new(condition: Condition, tool: Tool) -> Result<Conditional> {
+        if tool.unitary().is_none() {
+            return Err(QuantumError(ErrorKind::Unsupported, format!(
+                "Expecting only gates in a condition but got {}", tool.name()
+            )))
+        }
+
+        Ok(Conditional {
+            condition,
+            tool: Box::new(tool),
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    #[inline(always)]
+    pub(crate) fn tool(&self) -> &Tool {
+        &self.tool
+    }
+}
+
+impl UnitaryOperator for Conditional {
+    fn apply(&self, ket: Ket, register: &mut Register) -> Ket {
+        if self.condition.holds(register) {
+            self.tool.apply(ket, register)
+        } else {
+            ket
+        }
+    }
+}
+
+/// Puts every tool added after the marker under the condition, the marker is the number of steps
+/// and the number of tools in the last step before the first gate of the condition was added.
+pub(crate) fn condition_since(tools: &mut [TinyVec<[Tool; 1]>], marker: (usize, usize), condition: &Condition) -> Result<()> {
+    let (steps, len) = marker;
+    let first = steps.saturating_sub(1);
+    for (step, col) in tools.iter_mut().enumerate().skip(first) {
+        let skip = if step + 1 == steps { len } else { 0 };
+        for tool in col.iter_mut().skip(skip) {
+            if let Tool::None = tool {
+                continue
+            }
+            *tool = Tool::Conditional(Conditional::new(condition.clone(), tool.clone())?);
+        }
+    }
+
+    Ok(())
+}
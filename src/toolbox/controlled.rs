@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use crate::complex::Complex;
 use crate::toolbox::operator::c::C;
 use crate::toolbox::operator::hadamard::Hadamard;
@@ -20,8 +21,54 @@ use crate::toolbox::rotation::y::RotationY;
 use crate::toolbox::rotation::z::RotationZ;
 use crate::toolbox::Tool;
 
-#[derive(Copy, Clone, Debug)]
-pub(crate)enum Controlled {
+macro_rules! impl_from_trait {
+    ($name:ident, $type:ty) => {
+        impl From<$type> for Controlled {
+            fn from(value: $type) -> Self {
+                Controlled::$name(value)
+            }
+        }
+        impl From<$type> for Tool {
+            fn from(value: $type) -> Self {
+                Tool::Controlled(Controlled::$name(value))
+            }
+        }
+    };
+}
+
+/// Declares the variants with one control, followed by the variants with two controls, and derives their accessors.
+macro_rules! impl_controlled {
+    ($($name:ident($type:ty)),* ; $($name_2:ident($type_2:ty)),* $(,)?) => {
+        #[derive(Copy, Clone, Debug)]
+        pub(crate)enum Controlled {
+            $($name($type),)*
+            $($name_2($type_2),)*
+        }
+
+        impl Controlled {
+            /// Returns the number of quantum controls, which precede the wires of the controlled operator.
+            pub(crate) fn controls(&self) -> usize {
+                match self {
+                    $(Controlled::$name(_) => 1,)*
+                    $(Controlled::$name_2(_) => 2,)*
+                }
+            }
+
+            /// Returns the controls followed by the wires of the controlled operator, without building its matrix.
+            pub(crate) fn wires(&self) -> Vec<usize> {
+                match self {
+                    $(Controlled::$name(o) => [o.control()].into_iter().chain(o.inner().wires().iter().copied()).collect(),)*
+                    $(Controlled::$name_2(o) => [o.control(), o.inner().control()].into_iter().chain(o.inner().inner().wires().iter().copied()).collect(),)*
+                }
+            }
+        }
+
+        $(impl_from_trait!($name, $type);)*
+        $(impl_from_trait!($name_2, $type_2);)*
+    };
+}
+
+impl_controlled!(
     ControlledHadamard(C<2, Hadamard, f64>),
     ControlledPauliX(C<2, PauliX, f64>),
     ControlledPauliXRoot(C<2, PauliXRoot, Complex>),
@@ -42,7 +89,7 @@ pub(crate)enum Controlled {
     ControlledRotationPauliY(C<2, RotationPauliY, Complex>),
     ControlledRotationPauliZ(C<2, RotationPauliZ, Complex>),
     ControlledRotationSwap(C<3, RotationSwap, Complex>),
-    ControlledRotationU(C<2, RotationU, Complex>),
+    ControlledRotationU(C<2, RotationU, Complex>);
 
     ControlledControlledHadamard(C<3, C<2, Hadamard, f64>, f64>),
     ControlledControlledPauliX(C<3, C<2, PauliX, f64>, f64>),
@@ -65,63 +112,4 @@ pub(crate)enum Controlled {
     ControlledControlledRotationPauliZ(C<3, C<2, RotationPauliZ, Complex>, Complex>),
     ControlledControlledRotationSwap(C<4, C<3, RotationSwap, Complex>, Complex>),
     ControlledControlledRotationU(C<3, C<2, RotationU, Complex>, Complex>),
-}
-
-macro_rules! impl_from_trait {
-    ($name:ident, $type:ty) => {
-        impl From<$type> for Controlled {
-            fn from(value: $type) -> Self {
-                Controlled::$name(value)
-            }
-        }
-        impl From<$type> for Tool {
-            fn from(value: $type) -> Self {
-                Tool::Controlled(Controlled::$name(value))
-            }
-        }
-    };
-}
-
-impl_from_trait!(ControlledHadamard, C<2, Hadamard, f64>);
-impl_from_trait!(ControlledPauliX, C<2, PauliX, f64>);
-impl_from_trait!(ControlledPauliXRoot, C<2, PauliXRoot, Complex>);
-impl_from_trait!(ControlledPauliY, C<2, PauliY, Complex>);
-impl_from_trait!(ControlledPauliZ, C<2, PauliZ, f64>);
-impl_from_trait!(ControlledPhase, C<2, Phase, Complex>);
-impl_from_trait!(ControlledPhaseDagger, C<2, PhaseDagger, Complex>);
-impl_from_trait!(ControlledPhaseRoot, C<2, PhaseRoot, Complex>);
-impl_from_trait!(ControlledPhaseRootDagger, C<2, PhaseRootDagger, Complex>);
-impl_from_trait!(ControlledSwap, C<3, Swap, f64>);
-impl_from_trait!(ControlledSwapRoot, C<3, SwapRoot, Complex>);
-
-impl_from_trait!(ControlledRotationHadamard, C<2, RotationHadamard, Complex>);
-impl_from_trait!(ControlledRotationX, C<2, RotationX, Complex>);
-impl_from_trait!(ControlledRotationY, C<2, RotationY, f64>);
-impl_from_trait!(ControlledRotationZ, C<2, RotationZ, Complex>);
-impl_from_trait!(ControlledRotationPauliX, C<2, RotationPauliX, Complex>);
-impl_from_trait!(ControlledRotationPauliY, C<2, RotationPauliY, Complex>);
-impl_from_trait!(ControlledRotationPauliZ, C<2, RotationPauliZ, Complex>);
-impl_from_trait!(ControlledRotationSwap, C<3, RotationSwap, Complex>);
-impl_from_trait!(ControlledRotationU, C<2, RotationU, Complex>);
-
-impl_from_trait!(ControlledControlledHadamard, C<3, C<2, Hadamard, f64>, f64>);
-impl_from_trait!(ControlledControlledPauliX, C<3, C<2, PauliX, f64>, f64>);
-impl_from_trait!(ControlledControlledPauliXRoot, C<3, C<2, PauliXRoot, Complex>, Complex>);
-impl_from_trait!(ControlledControlledPauliY, C<3, C<2, PauliY, Complex>, Complex>);
-impl_from_trait!(ControlledControlledPauliZ, C<3, C<2, PauliZ, f64>, f64>);
-impl_from_trait!(ControlledControlledPhase, C<3, C<2, Phase, Complex>, Complex>);
-impl_from_trait!(ControlledControlledPhaseDagger, C<3, C<2, PhaseDagger, Complex>, Complex>);
-impl_from_trait!(ControlledControlledPhaseRoot, C<3, C<2, PhaseRoot, Complex>, Complex>);
-impl_from_trait!(ControlledControlledPhaseRootDagger, C<3, C<2, PhaseRootDagger, Complex>, Complex>);
-impl_from_trait!(ControlledControlledSwap, C<4, C<3, Swap, f64>, f64>);
-impl_from_trait!(ControlledControlledSwapRoot, C<4, C<3, SwapRoot, Complex>, Complex>);
-
-impl_from_trait!(ControlledControlledRotationHadamard, C<3, C<2, RotationHadamard, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationX, C<3, C<2, RotationX, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationY, C<3, C<2, RotationY, f64>, f64>);
-impl_from_trait!(ControlledControlledRotationZ, C<3, C<2, RotationZ, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationPauliX, C<3, C<2, RotationPauliX, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationPauliY, C<3, C<2, RotationPauliY, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationPauliZ, C<3, C<2, RotationPauliZ, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationSwap, C<4, C<3, RotationSwap, Complex>, Complex>);
-impl_from_trait!(ControlledControlledRotationU, C<3, C<2, RotationU, Complex>, Complex>);
+);
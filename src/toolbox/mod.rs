@@ -1,9 +1,14 @@
+use alloc::vec::Vec;
+use crate::runtime::classical::{BitwiseOperator, ClassicalOperation};
 use crate::runtime::dynamic_sized::unitary_operator::DynamicSizedUnitaryOperator;
 use crate::runtime::ket::Ket;
 use crate::runtime::non_unitary::measurement::Measurement;
+use crate::runtime::non_unitary::pauli_measurement::PauliMeasurement;
+use crate::runtime::non_unitary::povm::Povm;
 use crate::runtime::non_unitary::reset::Reset;
 use crate::runtime::register::Register;
 use crate::runtime::unitary::UnitaryOperator;
+use crate::toolbox::conditional::Conditional;
 use crate::toolbox::controlled::Controlled;
 use crate::toolbox::operator::Operator;
 use crate::toolbox::rotation::Rotation;
@@ -12,6 +17,13 @@ pub(crate)mod operator;
 pub(crate)mod rotation;
 pub(crate)mod parameterized;
 pub(crate)mod controlled;
+pub(crate)mod conditional;
+pub(crate)mod state_preparation;
+pub(crate)mod pauli;
+pub(crate)mod trotter;
+pub(crate)mod variational;
+#[cfg(feature = "serde")]
+pub(crate)mod named_gate;
 
 #[derive(Clone, Debug)]
 pub(crate)enum Tool {
@@ -19,7 +31,11 @@ pub(crate)enum Tool {
     Rotation(Rotation),
     Controlled(Controlled),
     Measurement(Measurement),
+    PauliMeasurement(PauliMeasurement),
+    Povm(Povm),
     Reset(Reset),
+    Classical(ClassicalOperation),
+    Conditional(Conditional),
 
     Custom(DynamicSizedUnitaryOperator),
     None,
@@ -31,6 +47,300 @@ impl const Default for Tool {
     }
 }
 
+impl Tool {
+    /// Returns the unitary operator of this tool, or none if the tool is not unitary.
+    pub(crate) fn unitary(&self) -> Option<DynamicSizedUnitaryOperator> {
+        match self {
+            Tool::Operator(o) => {
+                match o {
+                    Operator::Hadamard(o) => Some(o.operator().into()),
+                    Operator::PauliX(o) => Some(o.operator().into()),
+                    Operator::PauliXRoot(o) => Some(o.operator().into()),
+                    Operator::PauliY(o) => Some(o.operator().into()),
+                    Operator::PauliZ(o) => Some(o.operator().into()),
+                    Operator::Phase(o) => Some(o.operator().into()),
+                    Operator::PhaseDagger(o) => Some(o.operator().into()),
+                    Operator::PhaseRoot(o) => Some(o.operator().into()),
+                    Operator::PhaseRootDagger(o) => Some(o.operator().into()),
+                    Operator::Swap(o) => Some(o.operator().into()),
+                    Operator::SwapRoot(o) => Some(o.operator().into()),
+                }
+            }
+            Tool::Rotation(o) => {
+                match o {
+                    Rotation::Hadamard(o) => Some(o.operator().into()),
+                    Rotation::X(o) => Some(o.operator().into()),
+                    Rotation::Y(o) => Some(o.operator().into()),
+                    Rotation::Z(o) => Some(o.operator().into()),
+                    Rotation::PauliX(o) => Some(o.operator().into()),
+                    Rotation::PauliY(o) => Some(o.operator().into()),
+                    Rotation::PauliZ(o) => Some(o.operator().into()),
+                    Rotation::Swap(o) => Some(o.operator().into()),
+                    Rotation::U(o) => Some(o.operator().into()),
+                }
+            }
+            Tool::Controlled(o) => {
+                match o {
+                    Controlled::ControlledHadamard(o) => Some(o.operator().into()),
+                    Controlled::ControlledPauliX(o) => Some(o.operator().into()),
+                    Controlled::ControlledPauliXRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledPauliY(o) => Some(o.operator().into()),
+                    Controlled::ControlledPauliZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledPhase(o) => Some(o.operator().into()),
+                    Controlled::ControlledPhaseDagger(o) => Some(o.operator().into()),
+                    Controlled::ControlledPhaseRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledPhaseRootDagger(o) => Some(o.operator().into()),
+                    Controlled::ControlledSwap(o) => Some(o.operator().into()),
+                    Controlled::ControlledSwapRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationHadamard(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationX(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationY(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationPauliX(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationPauliY(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationPauliZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationSwap(o) => Some(o.operator().into()),
+                    Controlled::ControlledRotationU(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledHadamard(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPauliX(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPauliXRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPauliY(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPauliZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPhase(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPhaseDagger(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPhaseRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledPhaseRootDagger(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledSwap(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledSwapRoot(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationHadamard(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationX(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationY(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationPauliX(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationPauliY(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationPauliZ(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationSwap(o) => Some(o.operator().into()),
+                    Controlled::ControlledControlledRotationU(o) => Some(o.operator().into()),
+                }
+            }
+            Tool::Measurement(_) => None,
+            Tool::PauliMeasurement(_) => None,
+            Tool::Povm(_) => None,
+            Tool::Reset(_) => None,
+            Tool::Classical(_) => None,
+            Tool::Conditional(_) => None,
+            Tool::Custom(o) => Some(o.clone()),
+            Tool::None => None,
+        }
+    }
+
+    /// Returns the name of the gate this tool applies.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Tool::Operator(o) => {
+                match o {
+                    Operator::Hadamard(_) => "Hadamard",
+                    Operator::PauliX(_) => "PauliX",
+                    Operator::PauliXRoot(_) => "PauliXRoot",
+                    Operator::PauliY(_) => "PauliY",
+                    Operator::PauliZ(_) => "PauliZ",
+                    Operator::Phase(_) => "Phase",
+                    Operator::PhaseDagger(_) => "PhaseDagger",
+                    Operator::PhaseRoot(_) => "PhaseRoot",
+                    Operator::PhaseRootDagger(_) => "PhaseRootDagger",
+                    Operator::Swap(_) => "Swap",
+                    Operator::SwapRoot(_) => "SwapRoot",
+                }
+            }
+            Tool::Rotation(o) => {
+                match o {
+                    Rotation::Hadamard(_) => "RotationHadamard",
+                    Rotation::X(_) => "RotationX",
+                    Rotation::Y(_) => "RotationY",
+                    Rotation::Z(_) => "RotationZ",
+                    Rotation::PauliX(_) => "RotationPauliX",
+                    Rotation::PauliY(_) => "RotationPauliY",
+                    Rotation::PauliZ(_) => "RotationPauliZ",
+                    Rotation::Swap(_) => "RotationSwap",
+                    Rotation::U(_) => "RotationU",
+                }
+            }
+            Tool::Controlled(o) => {
+                match o {
+                    Controlled::ControlledHadamard(_) => "ControlledHadamard",
+                    Controlled::ControlledPauliX(_) => "ControlledPauliX",
+                    Controlled::ControlledPauliXRoot(_) => "ControlledPauliXRoot",
+                    Controlled::ControlledPauliY(_) => "ControlledPauliY",
+                    Controlled::ControlledPauliZ(_) => "ControlledPauliZ",
+                    Controlled::ControlledPhase(_) => "ControlledPhase",
+                    Controlled::ControlledPhaseDagger(_) => "ControlledPhaseDagger",
+                    Controlled::ControlledPhaseRoot(_) => "ControlledPhaseRoot",
+                    Controlled::ControlledPhaseRootDagger(_) => "ControlledPhaseRootDagger",
+                    Controlled::ControlledSwap(_) => "ControlledSwap",
+                    Controlled::ControlledSwapRoot(_) => "ControlledSwapRoot",
+                    Controlled::ControlledRotationHadamard(_) => "ControlledRotationHadamard",
+                    Controlled::ControlledRotationX(_) => "ControlledRotationX",
+                    Controlled::ControlledRotationY(_) => "ControlledRotationY",
+                    Controlled::ControlledRotationZ(_) => "ControlledRotationZ",
+                    Controlled::ControlledRotationPauliX(_) => "ControlledRotationPauliX",
+                    Controlled::ControlledRotationPauliY(_) => "ControlledRotationPauliY",
+                    Controlled::ControlledRotationPauliZ(_) => "ControlledRotationPauliZ",
+                    Controlled::ControlledRotationSwap(_) => "ControlledRotationSwap",
+                    Controlled::ControlledRotationU(_) => "ControlledRotationU",
+                    Controlled::ControlledControlledHadamard(_) => "ControlledControlledHadamard",
+                    Controlled::ControlledControlledPauliX(_) => "ControlledControlledPauliX",
+                    Controlled::ControlledControlledPauliXRoot(_) => "ControlledControlledPauliXRoot",
+                    Controlled::ControlledControlledPauliY(_) => "ControlledControlledPauliY",
+                    Controlled::ControlledControlledPauliZ(_) => "ControlledControlledPauliZ",
+                    Controlled::ControlledControlledPhase(_) => "ControlledControlledPhase",
+                    Controlled::ControlledControlledPhaseDagger(_) => "ControlledControlledPhaseDagger",
+                    Controlled::ControlledControlledPhaseRoot(_) => "ControlledControlledPhaseRoot",
+                    Controlled::ControlledControlledPhaseRootDagger(_) => "ControlledControlledPhaseRootDagger",
+                    Controlled::ControlledControlledSwap(_) => "ControlledControlledSwap",
+                    Controlled::ControlledControlledSwapRoot(_) => "ControlledControlledSwapRoot",
+                    Controlled::ControlledControlledRotationHadamard(_) => "ControlledControlledRotationHadamard",
+                    Controlled::ControlledControlledRotationX(_) => "ControlledControlledRotationX",
+                    Controlled::ControlledControlledRotationY(_) => "ControlledControlledRotationY",
+                    Controlled::ControlledControlledRotationZ(_) => "ControlledControlledRotationZ",
+                    Controlled::ControlledControlledRotationPauliX(_) => "ControlledControlledRotationPauliX",
+                    Controlled::ControlledControlledRotationPauliY(_) => "ControlledControlledRotationPauliY",
+                    Controlled::ControlledControlledRotationPauliZ(_) => "ControlledControlledRotationPauliZ",
+                    Controlled::ControlledControlledRotationSwap(_) => "ControlledControlledRotationSwap",
+                    Controlled::ControlledControlledRotationU(_) => "ControlledControlledRotationU",
+                }
+            }
+            Tool::Measurement(_) => "Measurement",
+            Tool::PauliMeasurement(_) => "PauliMeasurement",
+            Tool::Povm(_) => "Povm",
+            Tool::Reset(_) => "Reset",
+            Tool::Classical(o) => {
+                match o.operator() {
+                    BitwiseOperator::And => "ClassicalAnd",
+                    BitwiseOperator::Or => "ClassicalOr",
+                    BitwiseOperator::Xor => "ClassicalXor",
+                    BitwiseOperator::Not => "ClassicalNot",
+                }
+            }
+            Tool::Conditional(o) => o.tool().name(),
+            Tool::Custom(_) => "Custom",
+            Tool::None => "None",
+        }
+    }
+}
+
+impl Tool {
+    /// Returns the angles of a rotation, `theta`, `phi` and `lambda` for `RotationU`, or none for other tools.
+    pub(crate) fn parameters(&self) -> Vec<f64> {
+        match self {
+            Tool::Rotation(o) => {
+                match o {
+                    Rotation::Hadamard(o) => Vec::from([o.theta()]),
+                    Rotation::X(o) => Vec::from([o.theta()]),
+                    Rotation::Y(o) => Vec::from([o.theta()]),
+                    Rotation::Z(o) => Vec::from([o.theta()]),
+                    Rotation::PauliX(o) => Vec::from([o.theta()]),
+                    Rotation::PauliY(o) => Vec::from([o.theta()]),
+                    Rotation::PauliZ(o) => Vec::from([o.theta()]),
+                    Rotation::Swap(o) => Vec::from([o.theta()]),
+                    Rotation::U(o) => Vec::from(o.angles()),
+                }
+            }
+            Tool::Controlled(o) => {
+                match o {
+                    Controlled::ControlledRotationHadamard(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationX(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationY(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationZ(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationPauliX(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationPauliY(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationPauliZ(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationSwap(o) => Vec::from([o.inner().theta()]),
+                    Controlled::ControlledRotationU(o) => Vec::from(o.inner().angles()),
+                    Controlled::ControlledControlledRotationHadamard(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationX(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationY(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationZ(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationPauliX(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationPauliY(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationPauliZ(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationSwap(o) => Vec::from([o.inner().inner().theta()]),
+                    Controlled::ControlledControlledRotationU(o) => Vec::from(o.inner().inner().angles()),
+                    _ => Vec::new(),
+                }
+            }
+            Tool::Conditional(o) => o.tool().parameters(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Tool {
+    /// Returns the probability of each outcome, or none if the tool is neither a measurement nor a reset.
+    pub(crate) fn outcome_probabilities(&self, ket: &Ket) -> Option<Vec<f64>> {
+        match self {
+            Tool::Measurement(o) => Some(o.probabilities(ket)),
+            Tool::PauliMeasurement(o) => Some(o.probabilities(ket)),
+            Tool::Povm(o) => Some(o.probabilities(ket)),
+            Tool::Reset(o) => Some(o.probabilities(ket)),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of quantum controls, which are the first of the [wires](Tool::wires).
+    pub(crate) fn controls(&self) -> usize {
+        match self {
+            Tool::Controlled(o) => o.controls(),
+            Tool::Conditional(o) => o.tool().controls(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the qbits this tool acts on, including the controls.
+    pub(crate) fn wires(&self) -> Vec<usize> {
+        match self {
+            Tool::Measurement(o) => Vec::from([o.wire()]),
+            Tool::PauliMeasurement(o) => o.paulis().iter().map(|(wire, _)| *wire).collect(),
+            Tool::Povm(o) => Vec::from(o.wires()),
+            Tool::Reset(o) => Vec::from([o.wire()]),
+            Tool::Classical(_) => Vec::new(),
+            Tool::Conditional(o) => o.tool().wires(),
+            Tool::Operator(o) => Vec::from(o.wires()),
+            Tool::Rotation(o) => Vec::from(o.wires()),
+            Tool::Controlled(o) => o.wires(),
+            Tool::Custom(o) => Vec::from(o.wires()),
+            Tool::None => Vec::new(),
+        }
+    }
+
+    /// Returns the classical bits this tool reads, writes to or is controlled by.
+    pub(crate) fn bits(&self) -> Vec<usize> {
+        match self {
+            Tool::Measurement(o) => o.creg_bit().into_iter().collect(),
+            Tool::PauliMeasurement(o) => o.creg_bit().into_iter().collect(),
+            Tool::Povm(o) => Vec::from(o.creg_bits()),
+            Tool::Reset(_) => Vec::new(),
+            Tool::Classical(o) => o.sources().iter().copied().chain([o.target()]).collect(),
+            Tool::Conditional(o) => o.condition().bits().iter().copied().chain(o.tool().bits()).collect(),
+            Tool::Operator(o) => o.classical_control().into_iter().collect(),
+            Tool::Rotation(o) => o.classical_control().into_iter().collect(),
+            Tool::Controlled(_) => Vec::new(),
+            Tool::Custom(o) => o.classical_control().into_iter().collect(),
+            Tool::None => Vec::new(),
+        }
+    }
+
+    /// Applies the tool with the given outcome instead of a random one, if the tool is a measurement or a reset.
+    pub(crate) fn apply_outcome(&self, ket: Ket, reg: &mut Register, outcome: usize) -> Ket {
+        match self {
+            Tool::Measurement(o) => o.apply_outcome(ket, reg, outcome),
+            Tool::PauliMeasurement(o) => o.apply_outcome(ket, reg, outcome),
+            Tool::Povm(o) => o.apply_outcome(ket, reg, outcome),
+            Tool::Reset(o) => o.apply_outcome(ket, outcome),
+            tool => tool.apply(ket, reg),
+        }
+    }
+}
+
 impl UnitaryOperator for Tool {
     fn apply(&self, ket: Ket, reg: &mut Register) -> Ket {
         match self {
@@ -107,7 +417,15 @@ impl UnitaryOperator for Tool {
                 }
             }
             Tool::Measurement(o) => o.apply(ket, reg),
+            Tool::PauliMeasurement(o) => o.apply(ket, reg),
+            Tool::Povm(o) => o.apply(ket, reg),
             Tool::Reset(o) => o.apply(ket, reg),
+            Tool::Classical(o) => {
+                o.apply(reg);
+
+                ket
+            }
+            Tool::Conditional(o) => o.apply(ket, reg),
             Tool::Custom(o) => o.apply(ket, reg),
             Tool::None => ket,
         }
@@ -128,4 +446,35 @@ impl_from_trait!(Operator, Operator);
 impl_from_trait!(Rotation, Rotation);
 impl_from_trait!(Controlled, Controlled);
 impl_from_trait!(Measurement, Measurement);
+impl_from_trait!(PauliMeasurement, PauliMeasurement);
+impl_from_trait!(Povm, Povm);
 impl_from_trait!(Reset, Reset);
+impl_from_trait!(Classical, ClassicalOperation);
+impl_from_trait!(Conditional, Conditional);
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::toolbox::operator::c::C;
+    use crate::toolbox::operator::hadamard::Hadamard;
+    use crate::toolbox::operator::pauli_x::PauliX;
+    use crate::toolbox::operator::swap::Swap;
+    use crate::toolbox::rotation::u::RotationU;
+    use crate::toolbox::Tool;
+
+    #[test]
+    fn test_wires() {
+        let tools: [Tool; 4] = [
+            Hadamard::new_classically_controlled(2, 1).into(),
+            RotationU::new(0.1, 0.2, 0.3, 4).into(),
+            C::<3, Swap, _>::new(3, Swap::new(0, 1)).into(),
+            C::<3, C<2, PauliX, _>, _>::new(4, C::<2, PauliX, _>::new(2, PauliX::new(0))).into(),
+        ];
+        for tool in tools.iter() {
+            assert_eq!(tool.wires(), Vec::from(tool.unitary().unwrap().wires()));
+        }
+        assert_eq!(tools.iter().map(|tool| tool.controls()).collect::<Vec<_>>(), Vec::from([0, 0, 1, 2]));
+        assert_eq!(tools[0].bits(), Vec::from([1]));
+        assert_eq!(tools[3].wires(), Vec::from([4, 2, 0]));
+    }
+}
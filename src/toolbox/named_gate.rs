@@ -0,0 +1,186 @@
+use alloc::format;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::toolbox::operator::c::C;
+use crate::toolbox::operator::hadamard::Hadamard;
+use crate::toolbox::operator::pauli_x::PauliX;
+use crate::toolbox::operator::pauli_x_root::PauliXRoot;
+use crate::toolbox::operator::pauli_y::PauliY;
+use crate::toolbox::operator::pauli_z::PauliZ;
+use crate::toolbox::operator::phase::{Phase, PhaseDagger};
+use crate::toolbox::operator::phase_root::{PhaseRoot, PhaseRootDagger};
+use crate::toolbox::operator::swap::Swap;
+use crate::toolbox::operator::swap_root::SwapRoot;
+use crate::toolbox::rotation::hadamard::RotationHadamard;
+use crate::toolbox::rotation::pauli_x::RotationPauliX;
+use crate::toolbox::rotation::pauli_y::RotationPauliY;
+use crate::toolbox::rotation::pauli_z::RotationPauliZ;
+use crate::toolbox::rotation::swap::RotationSwap;
+use crate::toolbox::rotation::u::RotationU;
+use crate::toolbox::rotation::x::RotationX;
+use crate::toolbox::rotation::y::RotationY;
+use crate::toolbox::rotation::z::RotationZ;
+use crate::toolbox::Tool;
+
+macro_rules! gate {
+    ($type:ty, $classical_control:expr, $($arg:expr),+) => {
+        match $classical_control {
+            Some(classical_control) => <$type>::new_classically_controlled($($arg),+, classical_control).into(),
+            None => <$type>::new($($arg),+).into(),
+        }
+    };
+}
+
+macro_rules! controlled {
+    ($size:literal, $type:ty, $control:expr, $($arg:expr),+) => {
+        C::<$size, $type, _>::new($control, <$type>::new($($arg),+)).into()
+    };
+}
+
+macro_rules! controlled_controlled {
+    ($size:literal, $inner_size:literal, $type:ty, $control_0:expr, $control_1:expr, $($arg:expr),+) => {
+        C::<$size, C<$inner_size, $type, _>, _>::new(
+            $control_0,
+            C::<$inner_size, $type, _>::new($control_1, <$type>::new($($arg),+))
+        ).into()
+    };
+}
+
+/// Returns the number of qbits, including the controls, and the number of parameters of the named gate,
+/// or none if there is no gate with this name.
+fn shape(name: &str) -> Option<(usize, usize)> {
+    let (controls, base) = if let Some(base) = name.strip_prefix("ControlledControlled") {
+        (2, base)
+    } else if let Some(base) = name.strip_prefix("Controlled") {
+        (1, base)
+    } else {
+        (0, name)
+    };
+    let (wires, parameters) = match base {
+        "Hadamard" | "PauliX" | "PauliXRoot" | "PauliY" | "PauliZ" => (1, 0),
+        "Phase" | "PhaseDagger" | "PhaseRoot" | "PhaseRootDagger" => (1, 0),
+        "Swap" | "SwapRoot" => (2, 0),
+        "RotationHadamard" | "RotationX" | "RotationY" | "RotationZ" => (1, 1),
+        "RotationPauliX" | "RotationPauliY" | "RotationPauliZ" => (1, 1),
+        "RotationSwap" => (2, 1),
+        "RotationU" => (1, 3),
+        _ => return None,
+    };
+
+    Some((controls + wires, parameters))
+}
+
+/// Creates the gate from its [name](Tool::name), its [qbits](Tool::wires) with the controls first and its
+/// [parameters](Tool::parameters). Only gates without a quantum control can be classically controlled.
+pub(crate) fn named_gate(name: &str, wires: &[usize], parameters: &[f64], classical_control: Option<usize>) -> Result<Tool> {
+    let (expected_wires, expected_parameters) = match shape(name) {
+        Some(shape) => shape,
+        None => return Err(QuantumError(ErrorKind::InvalidArgument, format!("Expecting a known gate but got {}", name))),
+    };
+    if wires.len() != expected_wires {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting {} qbits for {} but got {}", expected_wires, name, wires.len()
+        )))
+    }
+    if parameters.len() != expected_parameters {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting {} parameters for {} but got {}", expected_parameters, name, parameters.len()
+        )))
+    }
+    if classical_control.is_some() && name.starts_with("Controlled") {
+        return Err(QuantumError(
+            ErrorKind::Unsupported,
+            format!("Expecting no classical control for {}", name)
+        ))
+    }
+
+    let (w, p) = (wires, parameters);
+    let tool: Tool = match name {
+        "Hadamard" => gate!(Hadamard, classical_control, w[0]),
+        "PauliX" => gate!(PauliX, classical_control, w[0]),
+        "PauliXRoot" => gate!(PauliXRoot, classical_control, w[0]),
+        "PauliY" => gate!(PauliY, classical_control, w[0]),
+        "PauliZ" => gate!(PauliZ, classical_control, w[0]),
+        "Phase" => gate!(Phase, classical_control, w[0]),
+        "PhaseDagger" => gate!(PhaseDagger, classical_control, w[0]),
+        "PhaseRoot" => gate!(PhaseRoot, classical_control, w[0]),
+        "PhaseRootDagger" => gate!(PhaseRootDagger, classical_control, w[0]),
+        "Swap" => gate!(Swap, classical_control, w[0], w[1]),
+        "SwapRoot" => gate!(SwapRoot, classical_control, w[0], w[1]),
+        "RotationHadamard" => gate!(RotationHadamard, classical_control, p[0], w[0]),
+        "RotationX" => gate!(RotationX, classical_control, p[0], w[0]),
+        "RotationY" => gate!(RotationY, classical_control, p[0], w[0]),
+        "RotationZ" => gate!(RotationZ, classical_control, p[0], w[0]),
+        "RotationPauliX" => gate!(RotationPauliX, classical_control, p[0], w[0]),
+        "RotationPauliY" => gate!(RotationPauliY, classical_control, p[0], w[0]),
+        "RotationPauliZ" => gate!(RotationPauliZ, classical_control, p[0], w[0]),
+        "RotationSwap" => gate!(RotationSwap, classical_control, p[0], w[0], w[1]),
+        "RotationU" => gate!(RotationU, classical_control, p[0], p[1], p[2], w[0]),
+        "ControlledHadamard" => controlled!(2, Hadamard, w[0], w[1]),
+        "ControlledPauliX" => controlled!(2, PauliX, w[0], w[1]),
+        "ControlledPauliXRoot" => controlled!(2, PauliXRoot, w[0], w[1]),
+        "ControlledPauliY" => controlled!(2, PauliY, w[0], w[1]),
+        "ControlledPauliZ" => controlled!(2, PauliZ, w[0], w[1]),
+        "ControlledPhase" => controlled!(2, Phase, w[0], w[1]),
+        "ControlledPhaseDagger" => controlled!(2, PhaseDagger, w[0], w[1]),
+        "ControlledPhaseRoot" => controlled!(2, PhaseRoot, w[0], w[1]),
+        "ControlledPhaseRootDagger" => controlled!(2, PhaseRootDagger, w[0], w[1]),
+        "ControlledSwap" => controlled!(3, Swap, w[0], w[1], w[2]),
+        "ControlledSwapRoot" => controlled!(3, SwapRoot, w[0], w[1], w[2]),
+        "ControlledRotationHadamard" => controlled!(2, RotationHadamard, w[0], p[0], w[1]),
+        "ControlledRotationX" => controlled!(2, RotationX, w[0], p[0], w[1]),
+        "ControlledRotationY" => controlled!(2, RotationY, w[0], p[0], w[1]),
+        "ControlledRotationZ" => controlled!(2, RotationZ, w[0], p[0], w[1]),
+        "ControlledRotationPauliX" => controlled!(2, RotationPauliX, w[0], p[0], w[1]),
+        "ControlledRotationPauliY" => controlled!(2, RotationPauliY, w[0], p[0], w[1]),
+        "ControlledRotationPauliZ" => controlled!(2, RotationPauliZ, w[0], p[0], w[1]),
+        "ControlledRotationSwap" => controlled!(3, RotationSwap, w[0], p[0], w[1], w[2]),
+        "ControlledRotationU" => controlled!(2, RotationU, w[0], p[0], p[1], p[2], w[1]),
+        "ControlledControlledHadamard" => controlled_controlled!(3, 2, Hadamard, w[0], w[1], w[2]),
+        "ControlledControlledPauliX" => controlled_controlled!(3, 2, PauliX, w[0], w[1], w[2]),
+        "ControlledControlledPauliXRoot" => controlled_controlled!(3, 2, PauliXRoot, w[0], w[1], w[2]),
+        "ControlledControlledPauliY" => controlled_controlled!(3, 2, PauliY, w[0], w[1], w[2]),
+        "ControlledControlledPauliZ" => controlled_controlled!(3, 2, PauliZ, w[0], w[1], w[2]),
+        "ControlledControlledPhase" => controlled_controlled!(3, 2, Phase, w[0], w[1], w[2]),
+        "ControlledControlledPhaseDagger" => controlled_controlled!(3, 2, PhaseDagger, w[0], w[1], w[2]),
+        "ControlledControlledPhaseRoot" => controlled_controlled!(3, 2, PhaseRoot, w[0], w[1], w[2]),
+        "ControlledControlledPhaseRootDagger" => controlled_controlled!(3, 2, PhaseRootDagger, w[0], w[1], w[2]),
+        "ControlledControlledSwap" => controlled_controlled!(4, 3, Swap, w[0], w[1], w[2], w[3]),
+        "ControlledControlledSwapRoot" => controlled_controlled!(4, 3, SwapRoot, w[0], w[1], w[2], w[3]),
+        "ControlledControlledRotationHadamard" => controlled_controlled!(3, 2, RotationHadamard, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationX" => controlled_controlled!(3, 2, RotationX, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationY" => controlled_controlled!(3, 2, RotationY, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationZ" => controlled_controlled!(3, 2, RotationZ, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationPauliX" => controlled_controlled!(3, 2, RotationPauliX, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationPauliY" => controlled_controlled!(3, 2, RotationPauliY, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationPauliZ" => controlled_controlled!(3, 2, RotationPauliZ, w[0], w[1], p[0], w[2]),
+        "ControlledControlledRotationSwap" => controlled_controlled!(4, 3, RotationSwap, w[0], w[1], p[0], w[2], w[3]),
+        "ControlledControlledRotationU" => controlled_controlled!(3, 2, RotationU, w[0], w[1], p[0], p[1], p[2], w[2]),
+        _ => return Err(QuantumError(ErrorKind::InvalidArgument, format!("Expecting a known gate but got {}", name))),
+    };
+
+    Ok(tool)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::error::ErrorKind;
+    use crate::toolbox::named_gate::named_gate;
+
+    #[test]
+    fn test_named_gate() {
+        let gate = named_gate("ControlledControlledRotationSwap", &[3, 0, 2, 1], &[0.5], None).unwrap();
+        assert_eq!(gate.name(), "ControlledControlledRotationSwap");
+        assert_eq!(gate.wires(), Vec::from([3, 0, 2, 1]));
+        assert_eq!(gate.parameters(), Vec::from([0.5]));
+
+        let gate = named_gate("RotationU", &[1], &[0.1, 0.2, 0.3], Some(4)).unwrap();
+        assert_eq!(gate.parameters(), Vec::from([0.1, 0.2, 0.3]));
+        assert_eq!(gate.bits(), Vec::from([4]));
+
+        assert_eq!(named_gate("Toffoli", &[0, 1, 2], &[], None).unwrap_err().kind(), ErrorKind::InvalidArgument);
+        assert_eq!(named_gate("ControlledPauliX", &[0], &[], None).unwrap_err().kind(), ErrorKind::InvalidArgument);
+        assert_eq!(named_gate("RotationX", &[0], &[], None).unwrap_err().kind(), ErrorKind::InvalidArgument);
+        assert_eq!(named_gate("ControlledPauliX", &[0, 1], &[], Some(0)).unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+}
@@ -24,6 +24,15 @@ impl<const SIZE: usize, T: Into<ConstSizedUnitaryOperator<{ SIZE - 1 }, V>> + Si
         C(wire, inner, PhantomData::default())
     }
 
+    /// Returns the control wire, which precedes the wires of the inner operator.
+    pub(crate)const fn control(&self) -> usize {
+        self.0
+    }
+
+    pub(crate)const fn inner(&self) -> &T {
+        &self.1
+    }
+
     pub(crate)fn operator(self) -> ConstSizedUnitaryOperator<SIZE, V> {
         Controlled::<SIZE, V>::new(self.0, self.1).into()
     }
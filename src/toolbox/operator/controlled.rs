@@ -74,7 +74,7 @@ impl<const SIZE: usize, T: Sized + Copy> const Into<ConstSizedUnitaryOperator<SI
 mod test {
     use super::*;
     use float_cmp::assert_approx_eq;
-    use crate::complex::Complex;
+    use crate::complex::{tolerance, Complex};
     use crate::runtime::ket::Ket;
     use crate::runtime::register::Register;
     use crate::runtime::unitary::UnitaryOperator;
@@ -91,8 +91,8 @@ mod test {
         let pauli_x = ConstSizedUnitaryOperator::new(PAULI_X.into(), [1], None);
         let controlled: Controlled<2, Complex> = Controlled::<2, Complex>::new(0, pauli_x);
         let ket = controlled.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -103,8 +103,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let controlled: Controlled<2, Complex> = Controlled::<2, Complex>::new(0, pauli_x);
         let ket = controlled.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 1.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 1.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 1.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 1.0, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -114,9 +114,9 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let controlled: Controlled<3, Complex> = Controlled::<3, Complex>::new(0, Controlled::<2, Complex>::new(1, ConstSizedUnitaryOperator::new(PAULI_X.into(), [2], None)));
         let ket = controlled.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 1.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(2), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 1.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(2).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -127,8 +127,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [1], None).apply(ket, &mut register);
         let controlled: Controlled<3, Complex> = Controlled::<3, Complex>::new(0, Controlled::<2, Complex>::new(1, ConstSizedUnitaryOperator::new(PAULI_X.into(), [2], None)));
         let ket = controlled.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 1.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 1.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(2), 1.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 1.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 1.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(2).unwrap(), 1.0, epsilon = tolerance(0.00000003));
     }
 }
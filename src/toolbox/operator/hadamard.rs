@@ -13,6 +13,7 @@ impl_operator!(HADAMARD, f64, Complex, Hadamard, crate::toolbox::rotation::hadam
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
     use crate::runtime::register::Register;
@@ -24,7 +25,7 @@ mod test {
         let mut register = Register::new(0);
         let hadamard = Hadamard::new(0);
         let ket = hadamard.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.5, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.5, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -34,6 +35,6 @@ mod test {
         let hadamard = Hadamard::new(0);
         let ket = hadamard.apply(ket, &mut register);
         let ket = hadamard.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 }
@@ -36,6 +36,42 @@ pub(crate)enum Operator {
     SwapRoot(SwapRoot),
 }
 
+impl Operator {
+    /// Returns the wires of the operator, without building its matrix.
+    pub(crate) fn wires(&self) -> &[usize] {
+        match self {
+            Operator::Hadamard(o) => o.wires(),
+            Operator::PauliX(o) => o.wires(),
+            Operator::PauliXRoot(o) => o.wires(),
+            Operator::PauliY(o) => o.wires(),
+            Operator::PauliZ(o) => o.wires(),
+            Operator::Phase(o) => o.wires(),
+            Operator::PhaseDagger(o) => o.wires(),
+            Operator::PhaseRoot(o) => o.wires(),
+            Operator::PhaseRootDagger(o) => o.wires(),
+            Operator::Swap(o) => o.wires(),
+            Operator::SwapRoot(o) => o.wires(),
+        }
+    }
+
+    /// Returns the bit, which classically controls the operator.
+    pub(crate) fn classical_control(&self) -> Option<usize> {
+        match self {
+            Operator::Hadamard(o) => o.classical_control(),
+            Operator::PauliX(o) => o.classical_control(),
+            Operator::PauliXRoot(o) => o.classical_control(),
+            Operator::PauliY(o) => o.classical_control(),
+            Operator::PauliZ(o) => o.classical_control(),
+            Operator::Phase(o) => o.classical_control(),
+            Operator::PhaseDagger(o) => o.classical_control(),
+            Operator::PhaseRoot(o) => o.classical_control(),
+            Operator::PhaseRootDagger(o) => o.classical_control(),
+            Operator::Swap(o) => o.classical_control(),
+            Operator::SwapRoot(o) => o.classical_control(),
+        }
+    }
+}
+
 macro_rules! impl_from_trait {
     ($name:ident, $type:ty) => {
         impl From<$type> for Operator {
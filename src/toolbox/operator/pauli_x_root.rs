@@ -12,6 +12,7 @@ impl_operator!(PAULI_X_ROOT, Complex, Complex, PauliXRoot, crate::toolbox::rotat
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
     use crate::runtime::register::Register;
@@ -23,7 +24,7 @@ mod test {
         let mut register = Register::new(0);
         let pauli_x = PauliXRoot::new(0);
         let ket = pauli_x.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.5, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.5, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -33,7 +34,7 @@ mod test {
         let pauli_x = PauliXRoot::new(0);
         let ket = pauli_x.apply(ket, &mut register);
         let ket = pauli_x.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 1.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 1.0, epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -45,6 +46,6 @@ mod test {
         let ket = pauli_x.apply(ket, &mut register);
         let ket = pauli_x.apply(ket, &mut register);
         let ket = pauli_x.apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 }
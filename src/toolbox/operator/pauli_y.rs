@@ -15,7 +15,7 @@ mod test {
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
     use crate::runtime::unitary::UnitaryOperator;
-    use crate::complex::Complex;
+    use crate::complex::{tolerance, Complex};
     use crate::runtime::register::Register;
 
     #[test]
@@ -24,8 +24,8 @@ mod test {
         let mut register = Register::new(0);
         let pauli_y = PauliY::new(0);
         let ket = pauli_y.apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -35,7 +35,7 @@ mod test {
         let pauli_y = PauliY::new(0);
         let ket = pauli_y.apply(ket, &mut register);
         let ket = pauli_y.apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -16,7 +16,7 @@ mod test {
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
     use crate::runtime::unitary::UnitaryOperator;
-    use crate::complex::Complex;
+    use crate::complex::{tolerance, Complex};
     use crate::runtime::matrix::Matrix;
     use crate::runtime::register::Register;
 
@@ -26,8 +26,8 @@ mod test {
         let mut register = Register::new(0);
         let pauli_z = PauliZ::new(0);
         let ket = pauli_z.apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -37,8 +37,8 @@ mod test {
         let pauli_z = PauliZ::new(0);
         let ket = pauli_z.apply(ket, &mut register);
         let ket = pauli_z.apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -51,7 +51,7 @@ mod test {
         ]).apply(ket, &[ 0 ]);
         let pauli_z = PauliZ::new(0);
         let ket = pauli_z.apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -14,6 +14,7 @@ impl_operator!(PHASE_DAGGER, Complex, Complex, PhaseDagger, crate::toolbox::rota
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use float_cmp::assert_approx_eq;
     use crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator;
     use crate::runtime::ket::Ket;
@@ -30,8 +31,8 @@ mod test {
         let ket = Ket::new(1).unwrap();
         let mut register = Register::new(0);
         let ket = Phase::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -40,8 +41,8 @@ mod test {
         let mut register = Register::new(0);
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = Phase::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -50,8 +51,8 @@ mod test {
         let mut register = Register::new(0);
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = PhaseDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, -1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, -1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -61,8 +62,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = Phase::new(0).apply(ket, &mut register);
         let ket = Phase::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -72,8 +73,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = PhaseDagger::new(0).apply(ket, &mut register);
         let ket = PhaseDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -83,7 +84,7 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = Phase::new(0).apply(ket, &mut register);
         let ket = PhaseDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -15,6 +15,7 @@ impl_operator!(PHASE_ROOT_DAGGER, Complex, Complex, PhaseRootDagger, crate::tool
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use float_cmp::assert_approx_eq;
     use crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator;
     use crate::runtime::ket::Ket;
@@ -31,8 +32,8 @@ mod test {
         let ket = Ket::new(1).unwrap();
         let mut register = Register::new(0);
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -42,8 +43,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -53,8 +54,8 @@ mod test {
         let ket = ConstSizedUnitaryOperator::new(PAULI_X.into(), [0], None).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, -1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, -1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -66,8 +67,8 @@ mod test {
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -79,8 +80,8 @@ mod test {
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -92,7 +93,7 @@ mod test {
         let ket = PhaseRoot::new(0).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
         let ket = PhaseRootDagger::new(0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -14,6 +14,7 @@ impl_operator!(SWAP, f64, Complex, Swap, crate::toolbox::rotation::swap::Rotatio
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use std::f64::consts::SQRT_2;
     use float_cmp::assert_approx_eq;
     use crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator;
@@ -31,24 +32,24 @@ mod test {
         let ket = Ket::new(2).unwrap();
         let mut register = Register::new(0);
         let ket = ConstSizedUnitaryOperator::new(HADAMARD.into(), [0], None).apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.5, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.5, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
         let ket = Swap::new(0, 1).apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.5, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.5, epsilon = tolerance(0.00000003));
         let ket = Swap::new(0, 1).apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.5, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.5, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 
     #[test]
     fn test_swap() {
         let ket = Ket::new(2).unwrap();
         let mut register = Register::new(0);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
         let ket = Swap::new(0, 1).apply(ket, &mut register);
-        assert_approx_eq!(f64, ket.probability(0), 0.0, epsilon = 0.00000003);
-        assert_approx_eq!(f64, ket.probability(1), 0.0, epsilon = 0.00000003);
+        assert_approx_eq!(f64, ket.probability(0).unwrap(), 0.0, epsilon = tolerance(0.00000003));
+        assert_approx_eq!(f64, ket.probability(1).unwrap(), 0.0, epsilon = tolerance(0.00000003));
     }
 }
@@ -0,0 +1,86 @@
+use alloc::vec::Vec;
+use core::f64::consts::FRAC_PI_2;
+use libm::fabs;
+use crate::runtime::pauli::Pauli;
+use crate::toolbox::operator::c::C;
+use crate::toolbox::operator::hadamard::Hadamard;
+use crate::toolbox::operator::pauli_x::PauliX;
+use crate::toolbox::rotation::pauli_x::RotationPauliX;
+use crate::toolbox::rotation::pauli_y::RotationPauliY;
+use crate::toolbox::rotation::pauli_z::RotationPauliZ;
+use crate::toolbox::rotation::x::RotationX;
+use crate::toolbox::rotation::z::RotationZ;
+use crate::toolbox::Tool;
+
+/// Angles below this are skipped, as their rotation is indistinguishable from the identity.
+const EPSILON: f64 = 1e-14;
+
+/// Returns a `RotationPauliX`, `RotationPauliY` or `RotationPauliZ` gate for each pauli on its wire, which is the
+/// `theta / pi`-th power of the pauli. For `theta = pi` the gates apply the pauli string exactly, including its phase.
+pub(crate) fn pauli_gates(paulis: &[(usize, Pauli)], theta: f64) -> Vec<Tool> {
+    paulis.iter()
+        .map(|(wire, pauli)| match pauli {
+            Pauli::X => RotationPauliX::new(theta, *wire).into(),
+            Pauli::Y => RotationPauliY::new(theta, *wire).into(),
+            Pauli::Z => RotationPauliZ::new(theta, *wire).into(),
+        })
+        .collect()
+}
+
+/// Appends `exp(-i theta/2 P)` for the paulis on the wires, where the parity of the wires is computed onto the last one
+/// by a CNOT ladder after rotating each pauli into the Z basis.
+/// The identity is skipped, as it only contributes a global phase.
+pub(crate) fn pauli_rotation(tools: &mut Vec<Tool>, paulis: &[(usize, Pauli)], theta: f64) {
+    if paulis.is_empty() || fabs(theta) < EPSILON {
+        return
+    }
+
+    for (wire, pauli) in paulis {
+        match pauli {
+            Pauli::X => tools.push(Hadamard::new(*wire).into()),
+            Pauli::Y => tools.push(RotationX::new(FRAC_PI_2, *wire).into()),
+            Pauli::Z => {},
+        }
+    }
+    for pair in paulis.windows(2) {
+        tools.push(C::<2, PauliX, _>::new(pair[0].0, PauliX::new(pair[1].0)).into());
+    }
+    tools.push(RotationZ::new(theta, paulis[paulis.len() - 1].0).into());
+    for pair in paulis.windows(2).rev() {
+        tools.push(C::<2, PauliX, _>::new(pair[0].0, PauliX::new(pair[1].0)).into());
+    }
+    for (wire, pauli) in paulis {
+        match pauli {
+            Pauli::X => tools.push(Hadamard::new(*wire).into()),
+            Pauli::Y => tools.push(RotationX::new(-FRAC_PI_2, *wire).into()),
+            Pauli::Z => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::PI;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::{seed, Ket};
+    use crate::runtime::pauli::PauliString;
+    use crate::runtime::register::Register;
+    use crate::runtime::unitary::UnitaryOperator;
+    use crate::toolbox::pauli::pauli_gates;
+
+    #[test]
+    fn test_pauli_gates() {
+        // X0 Y1 Z2 |100> = X0 |0> (i|1>) (-|1>) = -i |111>
+        let string = PauliString::checked_new(&[0, 1, 2], "XYZ").unwrap();
+        let mut ket = Ket::from_basis_state(3, 0b100, seed()).unwrap();
+        let mut register = Register::new(0);
+        for tool in pauli_gates(string.paulis(), PI) {
+            ket = tool.apply(ket, &mut register);
+        }
+        let (state, phase) = string.apply(0b100);
+        assert_eq!(state, 0b111);
+        assert_approx_eq!(Complex, Complex::from(ket.state()[state]), phase, epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, phase, Complex::new(0.0, -1.0));
+    }
+}
@@ -25,6 +25,7 @@ impl_rotation!(rotation_hadamard, Complex, RotationHadamard, 1, "");
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4, FRAC_1_SQRT_2};
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
@@ -44,7 +45,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationHadamard::new(PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(FRAC_1_SQRT_2, 0.0), Complex::new(FRAC_1_SQRT_2, 0.0)],
             [Complex::new(FRAC_1_SQRT_2, 0.0), Complex::new(-FRAC_1_SQRT_2, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -53,8 +54,8 @@ mod test {
         let mut register = Register::new(0);
         let ket = RotationHadamard::new(FRAC_PI_2, 0).apply(ket, &mut register);
         let ket = RotationHadamard::new(FRAC_PI_2, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -65,7 +66,7 @@ mod test {
         let ket = RotationHadamard::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationHadamard::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationHadamard::new(FRAC_PI_4, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
     }
 }
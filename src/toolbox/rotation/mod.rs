@@ -32,6 +32,38 @@ pub(crate)enum Rotation {
     U(RotationU),
 }
 
+impl Rotation {
+    /// Returns the wires of the rotation, without building its matrix.
+    pub(crate) fn wires(&self) -> &[usize] {
+        match self {
+            Rotation::Hadamard(o) => o.wires(),
+            Rotation::X(o) => o.wires(),
+            Rotation::Y(o) => o.wires(),
+            Rotation::Z(o) => o.wires(),
+            Rotation::PauliX(o) => o.wires(),
+            Rotation::PauliY(o) => o.wires(),
+            Rotation::PauliZ(o) => o.wires(),
+            Rotation::Swap(o) => o.wires(),
+            Rotation::U(o) => o.wires(),
+        }
+    }
+
+    /// Returns the bit, which classically controls the rotation.
+    pub(crate) fn classical_control(&self) -> Option<usize> {
+        match self {
+            Rotation::Hadamard(o) => o.classical_control(),
+            Rotation::X(o) => o.classical_control(),
+            Rotation::Y(o) => o.classical_control(),
+            Rotation::Z(o) => o.classical_control(),
+            Rotation::PauliX(o) => o.classical_control(),
+            Rotation::PauliY(o) => o.classical_control(),
+            Rotation::PauliZ(o) => o.classical_control(),
+            Rotation::Swap(o) => o.classical_control(),
+            Rotation::U(o) => o.classical_control(),
+        }
+    }
+}
+
 macro_rules! impl_from_trait {
     ($name:ident, $type:ty) => {
         impl From<$type> for Rotation {
@@ -82,6 +114,10 @@ pub(crate) mod rotation_macro {
                     ))
                 }
 
+                pub(crate)const fn theta(&self) -> f64 {
+                    self.0
+                }
+
                 pub(crate)fn operator(self) -> crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator<1, $type> {
                     self.1
                 }
@@ -134,6 +170,10 @@ pub(crate) mod rotation_macro {
                     ))
                 }
 
+                pub(crate)const fn theta(&self) -> f64 {
+                    self.0
+                }
+
                 pub(crate)fn operator(self) -> crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator<2, $type> {
                     self.1
                 }
@@ -15,6 +15,7 @@ impl_rotation!(rotation_pauli_x, Complex, RotationPauliX, 1, "");
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4};
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
@@ -34,7 +35,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationPauliX::new(PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
             [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -42,7 +43,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationPauliX::new(FRAC_PI_2, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(0.5, 0.5), Complex::new(0.5, -0.5)],
             [Complex::new(0.5, -0.5), Complex::new(0.5, 0.5)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -53,7 +54,7 @@ mod test {
         let ket = RotationPauliX::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliX::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliX::new(FRAC_PI_4, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(1.0, 0.0), epsilon = tolerance(0.00000003));
     }
 }
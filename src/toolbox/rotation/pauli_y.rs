@@ -15,6 +15,7 @@ impl_rotation!(rotation_pauli_y, Complex, RotationPauliY, 1, "");
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4};
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
@@ -34,7 +35,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationPauliY::new(PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(0.0, 0.0), Complex::new(0.0, -1.0)],
             [Complex::new(0.0, 1.0), Complex::new(0.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -43,8 +44,8 @@ mod test {
         let mut register = Register::new(0);
         let ket = RotationPauliY::new(FRAC_PI_2, 0).apply(ket, &mut register);
         let ket = RotationPauliY::new(FRAC_PI_2, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -55,7 +56,7 @@ mod test {
         let ket = RotationPauliY::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliY::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliY::new(FRAC_PI_4, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 1.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -15,6 +15,7 @@ impl_rotation!(rotation_pauli_z, Complex, RotationPauliZ, 1, "");
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4, SQRT_2};
     use float_cmp::assert_approx_eq;
     use crate::runtime::ket::Ket;
@@ -35,7 +36,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationPauliZ::new(PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -48,8 +49,8 @@ mod test {
         ]).apply(ket, &[ 0 ]);
         let ket = RotationPauliZ::new(FRAC_PI_2, 0).apply(ket, &mut register);
         let ket = RotationPauliZ::new(FRAC_PI_2, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
     }
 
     #[test]
@@ -64,7 +65,7 @@ mod test {
         let ket = RotationPauliZ::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliZ::new(FRAC_PI_4, 0).apply(ket, &mut register);
         let ket = RotationPauliZ::new(FRAC_PI_4, 0).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(-1.0 / SQRT_2, 0.0), epsilon = tolerance(0.00000003));
     }
 }
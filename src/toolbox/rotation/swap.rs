@@ -30,6 +30,7 @@ impl_rotation!(rotation_swap, Complex, RotationSwap, 2, "");
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4, FRAC_1_SQRT_2, SQRT_2};
     use float_cmp::assert_approx_eq;
     use crate::runtime::const_sized::unitary_operator::ConstSizedUnitaryOperator;
@@ -59,7 +60,7 @@ mod test {
             [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0), Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(1.0, 0.0), Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0), Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -69,7 +70,7 @@ mod test {
             [Complex::new(0.0, 0.0), Complex::new(0.5, 0.5), Complex::new(0.5, -0.5), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(0.5, -0.5), Complex::new(0.5, 0.5), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0), Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -81,9 +82,9 @@ mod test {
         let ket = RotationSwap::new(FRAC_PI_4, 0, 1).apply(ket, &mut register);
         let ket = RotationSwap::new(FRAC_PI_4, 0, 1).apply(ket, &mut register);
         let ket = RotationSwap::new(FRAC_PI_4, 0, 1).apply(ket, &mut register);
-        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(2).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = 0.00000003);
-        assert_approx_eq!(Complex, *ket.state().get(3).unwrap(), Complex::new(0.0, 0.0), epsilon = 0.00000003);
+        assert_approx_eq!(Complex, *ket.state().get(0).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(1).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(2).unwrap(), Complex::new(FRAC_1_SQRT_2, 0.0), epsilon = tolerance(0.00000003));
+        assert_approx_eq!(Complex, *ket.state().get(3).unwrap(), Complex::new(0.0, 0.0), epsilon = tolerance(0.00000003));
     }
 }
@@ -39,6 +39,11 @@ impl RotationU {
         ))
     }
 
+    /// Returns the angles `theta`, `phi` and `lambda`.
+    pub(crate) const fn angles(&self) -> [f64; 3] {
+        [self.0, self.1, self.2]
+    }
+
     pub(crate) fn operator(self) -> ConstSizedUnitaryOperator<1, Complex> {
         self.3
     }
@@ -71,6 +76,7 @@ impl const Into<ConstSizedUnitaryOperator<1, Complex>> for RotationU {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::complex::tolerance;
     use core::f64::consts::{PI, SQRT_2};
     use float_cmp::assert_approx_eq;
 
@@ -87,7 +93,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationU::new(PI, 0.0, PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
             [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -95,7 +101,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationU::new(PI / 2.0, 0.0, PI, 0).matrix(), ConstSizedMatrix::new([
         [Complex::new(1.0 / SQRT_2, 0.0), Complex::new(1.0 / SQRT_2, 0.0)],
         [Complex::new(1.0 / SQRT_2, 0.0), Complex::new(-1.0 / SQRT_2, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -103,7 +109,7 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationU::new(PI, PI / 2.0, PI / 2.0, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(0.0, 0.0), Complex::new(0.0, -1.0)],
             [Complex::new(0.0, 1.0), Complex::new(0.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 
     #[test]
@@ -111,6 +117,6 @@ mod test {
         assert_approx_eq!(ConstSizedMatrix<1, Complex>, *RotationU::new(0.0, 0.0, PI, 0).matrix(), ConstSizedMatrix::new([
             [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
             [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.0)],
-        ]), epsilon = 0.00000003)
+        ]), epsilon = tolerance(0.00000003))
     }
 }
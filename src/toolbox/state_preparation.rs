@@ -0,0 +1,136 @@
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::{atan2, fabs, sqrt};
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::ket::validate_amplitudes;
+use crate::toolbox::operator::c::C;
+use crate::toolbox::operator::pauli_x::PauliX;
+use crate::toolbox::rotation::pauli_z::RotationPauliZ;
+use crate::toolbox::rotation::y::RotationY;
+use crate::toolbox::rotation::z::RotationZ;
+use crate::toolbox::Tool;
+
+/// Angles below this are skipped, as their rotation is indistinguishable from the identity.
+const EPSILON: f64 = 1e-14;
+
+#[derive(Copy, Clone)]
+enum Axis {
+    Y,
+    Z,
+}
+
+/// Synthesises a circuit after Möttönen et al., which prepares the amplitudes on the wires, if they are in `|0...0>`.
+/// The amplitude index bit i belongs to `wires[i]`, the global phase is restored exactly.
+pub(crate) fn prepare_state(wires: &[usize], amplitudes: &[Complex]) -> Result<Vec<Tool>> {
+    let size = validate_amplitudes(amplitudes)?;
+    if size != wires.len() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting {} amplitudes for {} qbits but got {}", 0x1 << wires.len(), wires.len(), amplitudes.len()
+        )))
+    }
+
+    // The phase of each node in the binary tree, which is spanned by fixing the most significant bits first
+    let mut phases = vec![Vec::new(); size + 1];
+    phases[size] = amplitudes.iter().map(|amplitude| atan2(amplitude.im(), amplitude.re())).collect();
+    for level in (0..size).rev() {
+        phases[level] = (0..0x1 << level)
+            .map(|node| (phases[level + 1][node << 1] + phases[level + 1][(node << 1) | 1]) / 2.0)
+            .collect();
+    }
+
+    let mut tools = Vec::new();
+    for level in 0..size {
+        let target = size - 1 - level;
+        let controls = &wires[target + 1..];
+        let mut magnitudes = vec![[0.0; 2]; 0x1 << level];
+        for (idx, amplitude) in amplitudes.iter().enumerate() {
+            magnitudes[idx >> (target + 1)][(idx >> target) & 0x1] += amplitude.amplitude();
+        }
+        let y_angles = magnitudes.iter()
+            .map(|[low, high]| 2.0 * atan2(sqrt(*high), sqrt(*low)))
+            .collect::<Vec<_>>();
+        let z_angles = (0..0x1 << level)
+            .map(|node| phases[level + 1][(node << 1) | 1] - phases[level + 1][node << 1])
+            .collect::<Vec<_>>();
+
+        uniformly_controlled_rotation(&mut tools, Axis::Y, &y_angles, controls, wires[target]);
+        uniformly_controlled_rotation(&mut tools, Axis::Z, &z_angles, controls, wires[target]);
+    }
+
+    // RotationPauliZ(2 phase) RotationZ(-2 phase) equals the global phase times the identity
+    let phase = phases[0][0];
+    if fabs(phase) > EPSILON {
+        tools.push(RotationZ::new(-2.0 * phase, wires[0]).into());
+        tools.push(RotationPauliZ::new(2.0 * phase, wires[0]).into());
+    }
+
+    Ok(tools)
+}
+
+/// Applies the rotation with `angles[c]` to the target, where `c` is the state of the controls,
+/// decomposed into single qbit rotations and CNOTs along a gray code.
+fn uniformly_controlled_rotation(tools: &mut Vec<Tool>, axis: Axis, angles: &[f64], controls: &[usize], target: usize) {
+    if angles.iter().all(|angle| fabs(*angle) < EPSILON) {
+        return
+    }
+    let len = angles.len();
+    let gray = |i: usize| i ^ (i >> 1);
+    for i in 0..len {
+        let angle = angles.iter()
+            .enumerate()
+            .map(|(c, angle)| if (c & gray(i)).count_ones() % 2 == 0 { *angle } else { -*angle })
+            .sum::<f64>() / len as f64;
+        tools.push(match axis {
+            Axis::Y => RotationY::new(angle, target).into(),
+            Axis::Z => RotationZ::new(angle, target).into(),
+        });
+        if len > 1 {
+            let control = controls[(gray(i) ^ gray((i + 1) % len)).trailing_zeros() as usize];
+            tools.push(C::<2, PauliX, _>::new(control, PauliX::new(target)).into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::Ket;
+    use crate::runtime::register::Register;
+    use crate::toolbox::state_preparation::prepare_state;
+
+    #[test]
+    fn test_prepare_state() {
+        let amplitudes = Vec::from([
+            Complex::new(0.1, 0.2), Complex::new(-0.3, 0.1), Complex::new(0.0, 0.0), Complex::new(0.4, -0.2),
+            Complex::new(0.2, 0.3), Complex::new(0.0, -0.5), Complex::new(-0.1, 0.1), Complex::new(0.3, 0.3),
+        ]);
+        let norm = amplitudes.iter().map(|amplitude| amplitude.amplitude()).sum::<f64>();
+        let amplitudes = amplitudes.iter().map(|amplitude| *amplitude * (1.0 / libm::sqrt(norm))).collect::<Vec<_>>();
+        let wires = [3, 0, 2];
+
+        let mut ket = Ket::new(4).unwrap();
+        let mut register = Register::new(0);
+        for tool in prepare_state(&wires, &amplitudes).unwrap() {
+            ket = tool.apply(ket, &mut register);
+        }
+
+        for (idx, amplitude) in amplitudes.iter().enumerate() {
+            let state = (0..wires.len())
+                .filter(|bit| idx & (0x1 << bit) > 0)
+                .fold(0, |state, bit| state | (0x1 << wires[bit]));
+            assert_approx_eq!(Complex, Complex::from(ket.state()[state]), *amplitude, epsilon = tolerance(0.000001));
+        }
+    }
+
+    #[test]
+    fn test_prepare_state_invalid() {
+        let amplitudes = [Complex::new(1.0, 0.0), Complex::new(1.0, 0.0)];
+        assert!(prepare_state(&[0], &amplitudes).is_err());
+        let amplitudes = [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)];
+        assert!(prepare_state(&[0, 1], &amplitudes).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use libm::cbrt;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::pauli::PauliString;
+use crate::toolbox::pauli::pauli_rotation;
+use crate::toolbox::Tool;
+
+#[cfg(feature = "wasm-bindgen")]
+use wasm_bindgen::prelude::*;
+
+/// The order of the product formula, which approximates the evolution of a sum of terms.
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TrotterOrder {
+    First,
+    Second,
+    Fourth,
+}
+
+/// Synthesises `e^{-iHt}` for `H = sum c_j P_j` from `steps` repetitions of the product formula of the order.
+/// Qbit i of the pauli strings belongs to `wires[i]`.
+pub(crate) fn trotter_circuit(
+    wires: &[usize],
+    terms: &[(f64, PauliString)],
+    time: f64,
+    steps: usize,
+    order: TrotterOrder,
+) -> Result<Vec<Tool>> {
+    if steps < 1 {
+        return Err(QuantumError(ErrorKind::InvalidArgument, String::from("Expecting at least one trotter step")))
+    }
+    if let Some(qbit) = terms.iter().flat_map(|(_, string)| string.qbits()).find(|qbit| *qbit >= wires.len()) {
+        return Err(QuantumError(ErrorKind::InvalidWire, format!(
+            "Expecting qbits below {} in the hamiltonian but was {}", wires.len(), qbit
+        )))
+    }
+
+    let mut sequence = Vec::new();
+    let dt = 1.0 / steps as f64;
+    for _ in 0..steps {
+        match order {
+            TrotterOrder::First => (0..terms.len()).for_each(|term| push_term(&mut sequence, term, dt)),
+            TrotterOrder::Second => second_order(&mut sequence, terms.len(), dt),
+            TrotterOrder::Fourth => {
+                let p = 1.0 / (4.0 - cbrt(4.0));
+                for fraction in [p, p, 1.0 - 4.0 * p, p, p] {
+                    second_order(&mut sequence, terms.len(), fraction * dt);
+                }
+            },
+        }
+    }
+
+    let mut tools = Vec::new();
+    for (term, fraction) in sequence {
+        let (coefficient, string) = &terms[term];
+        let paulis = string.paulis().iter().map(|(qbit, pauli)| (wires[*qbit], *pauli)).collect::<Vec<_>>();
+        pauli_rotation(&mut tools, &paulis, 2.0 * coefficient * fraction * time);
+    }
+
+    Ok(tools)
+}
+
+/// Appends the symmetric formula, which evolves all terms half the time forward and then in reverse order.
+fn second_order(sequence: &mut Vec<(usize, f64)>, terms: usize, dt: f64) {
+    (0..terms).for_each(|term| push_term(sequence, term, dt / 2.0));
+    (0..terms).rev().for_each(|term| push_term(sequence, term, dt / 2.0));
+}
+
+/// Appends the evolution of the term for the fraction of the time, merged with the last one if it is the same term.
+fn push_term(sequence: &mut Vec<(usize, f64)>, term: usize, fraction: f64) {
+    match sequence.last_mut() {
+        Some((last, last_fraction)) if *last == term => *last_fraction += fraction,
+        _ => sequence.push((term, fraction)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::f64::consts::FRAC_PI_4;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::Ket;
+    use crate::runtime::pauli::PauliString;
+    use crate::runtime::register::Register;
+    use crate::runtime::unitary::UnitaryOperator;
+    use crate::toolbox::trotter::{trotter_circuit, TrotterOrder};
+
+    #[test]
+    fn test_pauli_rotation() {
+        // exp(-i pi/4 X0 Y2) |000> = (|000> + |101>) / sqrt(2)
+        let terms = Vec::from([(1.0, PauliString::checked_new(&[0, 2], "XY").unwrap())]);
+        let tools = trotter_circuit(&[0, 1, 2], &terms, FRAC_PI_4, 1, TrotterOrder::Second).unwrap();
+
+        let mut ket = Ket::new(3).unwrap();
+        let mut register = Register::new(0);
+        for tool in tools {
+            ket = tool.apply(ket, &mut register);
+        }
+        let amplitude = Complex::new(libm::sqrt(0.5), 0.0);
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0]), amplitude, epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0b101]), amplitude, epsilon = tolerance(0.000001));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let terms = Vec::from([(1.0, PauliString::checked_new(&[2], "Z").unwrap())]);
+        assert!(trotter_circuit(&[0, 1], &terms, 1.0, 1, TrotterOrder::First).is_err());
+        assert!(trotter_circuit(&[0, 1, 2], &terms, 1.0, 0, TrotterOrder::First).is_err());
+    }
+}
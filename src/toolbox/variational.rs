@@ -0,0 +1,159 @@
+use alloc::format;
+use alloc::vec::Vec;
+use crate::complex::Complex;
+use crate::error::{ErrorKind, QuantumError, Result};
+use crate::runtime::pauli::{Pauli, PauliString, PauliSum};
+use crate::toolbox::operator::c::C;
+use crate::toolbox::operator::hadamard::Hadamard;
+use crate::toolbox::operator::pauli_z::PauliZ;
+use crate::toolbox::rotation::x::RotationX;
+use crate::toolbox::rotation::y::RotationY;
+use crate::toolbox::pauli::pauli_rotation;
+use crate::toolbox::trotter::{trotter_circuit, TrotterOrder};
+use crate::toolbox::Tool;
+
+/// Synthesises `e^{-i gamma C}` for the cost hamiltonian, which is exact if all terms commute like for MaxCut and Ising.
+/// Qbit i of the pauli strings belongs to `wires[i]`.
+pub(crate) fn qaoa_cost_layer(wires: &[usize], cost: &[(f64, PauliString)], gamma: f64) -> Result<Vec<Tool>> {
+    trotter_circuit(wires, cost, gamma, 1, TrotterOrder::First)
+}
+
+/// Synthesises the transverse field mixer `e^{-i beta sum X_j}` on the wires.
+pub(crate) fn qaoa_mixer_layer(wires: &[usize], beta: f64) -> Vec<Tool> {
+    wires.iter().map(|wire| RotationX::new(2.0 * beta, *wire).into()).collect()
+}
+
+/// Synthesises p rounds of cost and mixer layers on `|+...+>`, where p is the number of gammas and betas.
+pub(crate) fn qaoa_circuit(wires: &[usize], cost: &[(f64, PauliString)], gammas: &[f64], betas: &[f64]) -> Result<Vec<Tool>> {
+    if gammas.is_empty() || gammas.len() != betas.len() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting as many gammas as betas for at least one round but got {} and {}", gammas.len(), betas.len()
+        )))
+    }
+
+    let mut tools = wires.iter()
+        .map(|wire| Hadamard::new(*wire).into())
+        .collect::<Vec<Tool>>();
+    for (gamma, beta) in gammas.iter().zip(betas.iter()) {
+        tools.extend(qaoa_cost_layer(wires, cost, *gamma)?);
+        tools.extend(qaoa_mixer_layer(wires, *beta));
+    }
+
+    Ok(tools)
+}
+
+/// Synthesises the hardware efficient ansatz, which alternates a layer of `RotationY` on every wire with
+/// a chain of controlled Z gates between neighbouring wires and ends with a final rotation layer.
+/// The parameters are consumed layer by layer, so `wires.len() * (layers + 1)` are needed.
+pub(crate) fn hardware_efficient_ansatz(wires: &[usize], layers: usize, parameters: &[f64]) -> Result<Vec<Tool>> {
+    if parameters.len() != wires.len() * (layers + 1) {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting {} parameters for {} layers on {} qbits but got {}",
+            wires.len() * (layers + 1), layers, wires.len(), parameters.len()
+        )))
+    }
+
+    let mut tools = Vec::new();
+    for (layer, thetas) in parameters.chunks(wires.len().max(1)).enumerate() {
+        tools.extend(wires.iter().zip(thetas.iter()).map(|(wire, theta)| Tool::from(RotationY::new(*theta, *wire))));
+        if layer < layers {
+            for pair in wires.windows(2) {
+                tools.push(C::<2, PauliZ, _>::new(pair[0], PauliZ::new(pair[1])).into());
+            }
+        }
+    }
+
+    Ok(tools)
+}
+
+/// Synthesises `e^{theta (T - T^dagger)}` for the excitation `T = a^dagger_{v_0} ... a^dagger_{v_k} a_{o_k} ... a_{o_0}`
+/// from the occupied wires `o` to the unoccupied wires `v` under the Jordan-Wigner mapping, where `|1>` is occupied.
+/// All pauli strings of the generator commute, so the exponential is the exact product of their rotations.
+pub(crate) fn excitation(occupied: &[usize], unoccupied: &[usize], theta: f64) -> Result<Vec<Tool>> {
+    if occupied.is_empty() || occupied.len() != unoccupied.len() {
+        return Err(QuantumError(ErrorKind::InvalidArgument, format!(
+            "Expecting as many occupied as unoccupied qbits but got {} and {}", occupied.len(), unoccupied.len()
+        )))
+    }
+    let wires = occupied.iter().chain(unoccupied.iter()).collect::<Vec<_>>();
+    for (i, wire) in wires.iter().enumerate() {
+        if wires[..i].contains(wire) {
+            return Err(QuantumError(
+                ErrorKind::DuplicateWire,
+                format!("Expecting distinct qbits in an excitation but {} occurs twice", wire)
+            ))
+        }
+    }
+
+    let mut generator = PauliSum::from_terms([(Complex::new(1.0, 0.0), PauliString::default())]);
+    for wire in unoccupied {
+        generator = generator.multiply(&ladder(*wire, true));
+    }
+    for wire in occupied.iter().rev() {
+        generator = generator.multiply(&ladder(*wire, false));
+    }
+
+    // The pauli strings are hermitian, so T - T^dagger = sum 2i Im(c_j) P_j and each term is a rotation by -4 theta Im(c_j)
+    let mut tools = Vec::new();
+    for (coefficient, string) in generator.0 {
+        pauli_rotation(&mut tools, string.paulis(), -4.0 * theta * coefficient.im());
+    }
+
+    Ok(tools)
+}
+
+/// Returns the Jordan-Wigner mapping `Z_0 ... Z_{j-1} (X_j -+ iY_j) / 2` of the creation or annihilation operator.
+fn ladder(wire: usize, creation: bool) -> PauliSum {
+    let parity = (0..wire).map(|qbit| (qbit, Pauli::Z));
+    let x = PauliString(parity.clone().chain([(wire, Pauli::X)]).collect());
+    let y = PauliString(parity.chain([(wire, Pauli::Y)]).collect());
+    let sign = if creation { -0.5 } else { 0.5 };
+
+    PauliSum::from_terms([(Complex::new(0.5, 0.0), x), (Complex::new(0.0, sign), y)])
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::{tolerance, Complex};
+    use crate::runtime::ket::{seed, Ket};
+    use crate::runtime::register::Register;
+    use crate::runtime::unitary::UnitaryOperator;
+    use crate::toolbox::variational::{excitation, hardware_efficient_ansatz};
+    use crate::toolbox::Tool;
+
+    fn run(ket: Ket, tools: Vec<Tool>) -> Ket {
+        let mut register = Register::new(0);
+        tools.into_iter().fold(ket, |ket, tool| tool.apply(ket, &mut register))
+    }
+
+    #[test]
+    fn test_single_excitation() {
+        // The occupied qbit 1 between both wires flips the sign of the excitation
+        for (state, sign) in [(0b0001, 1.0), (0b0011, -1.0)] {
+            let ket = Ket::from_basis_state(3, state, seed()).unwrap();
+            let ket = run(ket, excitation(&[0], &[2], 0.3).unwrap());
+            assert_approx_eq!(Complex, Complex::from(ket.state()[state]), Complex::new(libm::cos(0.3), 0.0), epsilon = tolerance(0.000001));
+            assert_approx_eq!(Complex, Complex::from(ket.state()[state ^ 0b101]), Complex::new(sign * libm::sin(0.3), 0.0), epsilon = tolerance(0.000001));
+        }
+    }
+
+    #[test]
+    fn test_double_excitation() {
+        let ket = Ket::from_basis_state(4, 0b0011, seed()).unwrap();
+        let ket = run(ket, excitation(&[0, 1], &[2, 3], 0.3).unwrap());
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0b0011]), Complex::new(libm::cos(0.3), 0.0), epsilon = tolerance(0.000001));
+        assert_approx_eq!(Complex, Complex::from(ket.state()[0b1100]), Complex::new(libm::sin(0.3), 0.0), epsilon = tolerance(0.000001));
+
+        assert!(excitation(&[0, 1], &[2], 0.3).is_err());
+        assert!(excitation(&[0, 1], &[1, 2], 0.3).is_err());
+    }
+
+    #[test]
+    fn test_hardware_efficient_ansatz() {
+        let tools = hardware_efficient_ansatz(&[0, 1, 2], 2, &[0.1; 9]).unwrap();
+        assert_eq!(tools.len(), 13);
+        assert!(hardware_efficient_ansatz(&[0, 1, 2], 2, &[0.1; 8]).is_err());
+    }
+}
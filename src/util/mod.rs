@@ -2,3 +2,4 @@ pub(crate) mod const_iter;
 pub(crate) mod trig;
 pub(crate) mod one;
 pub(crate) mod s_cow;
+pub(crate) mod svd;
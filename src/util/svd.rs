@@ -0,0 +1,145 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use libm::{fabs, sqrt};
+use crate::complex::Complex;
+
+const EPSILON: f64 = 1e-15;
+const MAX_SWEEPS: usize = 64;
+
+/// The thin singular value decomposition `M = U S V†` of a complex matrix.
+#[derive(Clone, Debug)]
+pub(crate) struct Svd {
+    /// The left singular vectors, row major with `rows x rank` entries.
+    pub(crate) u: Vec<Complex>,
+    /// The singular values in descending order.
+    pub(crate) s: Vec<f64>,
+    /// The right singular vectors, row major with `cols x rank` entries.
+    pub(crate) v: Vec<Complex>,
+    pub(crate) rank: usize,
+}
+
+/// Decomposes the row major `rows x cols` matrix with the one sided jacobi method.
+pub(crate) fn svd(matrix: &[Complex], rows: usize, cols: usize) -> Svd {
+    assert_eq!(matrix.len(), rows * cols);
+    if rows < cols {
+        // The columns of the adjoint are orthogonalized instead, which swaps the singular vectors
+        let mut adjoint = vec![Complex::zero(); rows * cols];
+        for r in 0..rows {
+            for c in 0..cols {
+                adjoint[c * rows + r] = matrix[r * cols + c].conjugate();
+            }
+        }
+        let Svd { u, s, v, rank } = svd(&adjoint, cols, rows);
+
+        return Svd { u: v, s, v: u, rank };
+    }
+
+    let mut a = matrix.to_vec();
+    // Columns below this weight are numerically zero, rotating them would only amplify rounding errors
+    let negligible = EPSILON * EPSILON * matrix.iter().map(|v| v.amplitude()).sum::<f64>();
+    let mut v = vec![Complex::zero(); cols * cols];
+    for i in 0..cols {
+        v[i * cols + i] = Complex::new(1.0, 0.0);
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for i in 0..cols {
+            for j in (i + 1)..cols {
+                let mut alpha = 0.0;
+                let mut beta = 0.0;
+                let mut gamma = Complex::zero();
+                for r in 0..rows {
+                    let ai = a[r * cols + i];
+                    let aj = a[r * cols + j];
+                    alpha += ai.amplitude();
+                    beta += aj.amplitude();
+                    gamma = gamma + ai.conjugate() * aj;
+                }
+                let gamma_abs = gamma.abs();
+                if alpha <= negligible || beta <= negligible || gamma_abs <= EPSILON * sqrt(alpha * beta) {
+                    continue;
+                }
+                rotated = true;
+
+                // Rotate the columns i and j, such that they become orthogonal
+                let phase = Complex::new(gamma.re() / gamma_abs, -gamma.im() / gamma_abs);
+                let zeta = (beta - alpha) / (2.0 * gamma_abs);
+                let t = (if zeta < 0.0 { -1.0 } else { 1.0 }) / (fabs(zeta) + sqrt(1.0 + zeta * zeta));
+                let c = 1.0 / sqrt(1.0 + t * t);
+                let s = c * t;
+                for r in 0..rows {
+                    let ai = a[r * cols + i];
+                    let aj = phase * a[r * cols + j];
+                    a[r * cols + i] = c * ai - s * aj;
+                    a[r * cols + j] = s * ai + c * aj;
+                }
+                for r in 0..cols {
+                    let vi = v[r * cols + i];
+                    let vj = phase * v[r * cols + j];
+                    v[r * cols + i] = c * vi - s * vj;
+                    v[r * cols + j] = s * vi + c * vj;
+                }
+            }
+        }
+
+        if !rotated {
+            break;
+        }
+    }
+
+    let mut singular_values: Vec<(usize, f64)> = (0..cols)
+        .map(|c| (0..rows).map(|r| a[r * cols + c].amplitude()).sum::<f64>())
+        .map(|weight| if weight <= negligible { 0.0 } else { sqrt(weight) })
+        .enumerate()
+        .collect();
+    singular_values.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let mut u = vec![Complex::zero(); rows * cols];
+    let mut sorted_v = vec![Complex::zero(); cols * cols];
+    let mut s = Vec::with_capacity(cols);
+    for (k, (c, sigma)) in singular_values.into_iter().enumerate() {
+        if sigma > 0.0 {
+            for r in 0..rows {
+                u[r * cols + k] = a[r * cols + c] * (1.0 / sigma);
+            }
+        }
+        for r in 0..cols {
+            sorted_v[r * cols + k] = v[r * cols + c];
+        }
+        s.push(sigma);
+    }
+
+    Svd { u, s, v: sorted_v, rank: cols }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use float_cmp::assert_approx_eq;
+    use crate::complex::Complex;
+    use crate::util::svd::svd;
+
+    #[test]
+    fn test_svd_reconstruction() {
+        let matrix = Vec::from([
+            Complex::new(1.0, 0.5), Complex::new(-0.5, 0.0), Complex::new(0.25, -1.0),
+            Complex::new(0.0, 2.0), Complex::new(1.5, 0.5), Complex::new(-1.0, 0.0),
+        ]);
+        let decomposition = svd(&matrix, 2, 3);
+        assert_eq!(decomposition.rank, 2);
+        assert!(decomposition.s[0] >= decomposition.s[1]);
+
+        for r in 0..2 {
+            for c in 0..3 {
+                let mut value = Complex::zero();
+                for k in 0..decomposition.rank {
+                    value = value + decomposition.s[k] * decomposition.u[r * decomposition.rank + k]
+                        * decomposition.v[c * decomposition.rank + k].conjugate();
+                }
+                assert_approx_eq!(f64, value.re(), matrix[r * 3 + c].re(), epsilon = 0.00000003);
+                assert_approx_eq!(f64, value.im(), matrix[r * 3 + c].im(), epsilon = 0.00000003);
+            }
+        }
+    }
+}
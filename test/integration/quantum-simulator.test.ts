@@ -1,4 +1,4 @@
-import {GateBuilder, hadamard, pauliZ, cPauliX, QBit, Bit, measurement} from "../../pkg.nodejs";
+import {GateBuilder, hadamard, pauliZ, cPauliX, QBit, Bit, measurement, pauliX, Comparison, condition, classicalXor, Hamiltonian, qaoa} from "../../pkg.nodejs";
 
 function bvAlgorithm(hidden: boolean[]) {
     const builder = new GateBuilder();
@@ -59,3 +59,29 @@ test('test small bv', () => {
         true, true, false, true, false,
     ]);
 });
+
+test('test classical condition', () => {
+    const builder = new GateBuilder();
+    const qbits: QBit[] = builder.qbits(2);
+    const bits: Bit[] = builder.bits(3);
+
+    pauliX(qbits[0]);
+    measurement(qbits, bits.slice(0, 2));
+    classicalXor(bits.slice(0, 2), bits[2]);
+    condition(builder, [bits[2]], Comparison.Equal, 1, () => pauliX(qbits[1]));
+    measurement(qbits[1], bits[1]);
+
+    const measurements = builder.intoAlgorithm().run().classicalRegister().state();
+    expect(measurements[1]).toBe(true);
+    expect(measurements[2]).toBe(true);
+});
+
+test('test qaoa energy', () => {
+    const builder = new GateBuilder();
+    const qbits: QBit[] = builder.qbits(2);
+    const cost = Hamiltonian.maxCut([[0, 1, 1.0]]);
+
+    qaoa(builder, qbits, cost, [Math.PI / 2], [-Math.PI / 8]);
+
+    expect(builder.intoAlgorithm().energy(cost)).toBeCloseTo(-1.0);
+});
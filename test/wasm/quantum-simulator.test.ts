@@ -59,3 +59,11 @@ test('test small bv', () => {
         true, true, false, true, false,
     ]);
 });
+
+test('test error kind', () => {
+    const builder = new GateBuilder();
+    hadamard(builder.qbit());
+    const quantumRegister = builder.intoAlgorithm().run().quantumRegister();
+
+    expect(() => quantumRegister.probability(1)).toThrow(expect.objectContaining({ kind: "InvalidWire" }));
+});